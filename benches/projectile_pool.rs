@@ -0,0 +1,94 @@
+//! Stress test for projectile pooling: fires thousands of fragment shells into
+//! a target and reports how mesh, material and entity counts change.
+//!
+//! Run with `cargo bench --bench projectile_pool`.
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{CollisionEvent, ExternalImpulse};
+use bevy_rapier3d::rapier::geometry::CollisionEventFlags;
+use std::time::Instant;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::AmmoType;
+use rust_and_ruin::systems::*;
+
+const FRAMES: usize = 1000;
+const SHOTS_PER_FRAME: usize = 5;
+const WARM_UP_FRAMES: usize = 10;
+
+#[derive(Resource)]
+struct Target(Entity);
+
+/// Fires a volley and reports every shell still in flight as having hit the target.
+fn fire_volley_system(
+    mut commands: Commands,
+    assets: Res<ProjectileAssets>,
+    mut pool: ResMut<ProjectilePool>,
+    target: Res<Target>,
+    shells: Query<Entity, With<TankShell>>,
+    mut collisions: EventWriter<CollisionEvent>,
+) {
+    for shell in shells.iter() {
+        collisions.send(CollisionEvent::Started(shell, target.0, CollisionEventFlags::empty()));
+    }
+    for i in 0..SHOTS_PER_FRAME {
+        let direction = Vec2::from_angle(i as f32 * 0.2);
+        spawn_tank_shell(&mut commands, &assets, &mut pool, Vec2::ZERO, direction, 10.0, AmmoType::Fragmentation);
+    }
+}
+
+/// Stands in for lifetimes running out so fragments and flashes go back to the pool every frame.
+fn expire_effects_system(mut commands: Commands, effects: Query<Entity, (With<Pooled>, Or<(With<ShellFragment>, With<HitFlash>)>)>) {
+    for effect in effects.iter() {
+        commands.add(Recycle(effect));
+    }
+}
+
+fn counts(app: &mut App) -> (usize, usize, usize) {
+    let entities = app.world.query::<Entity>().iter(&app.world).count();
+    (
+        app.world.resource::<Assets<Mesh>>().len(),
+        app.world.resource::<Assets<StandardMaterial>>().len(),
+        entities,
+    )
+}
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.add_event::<CollisionEvent>();
+    app.add_event::<DamageEvent>();
+    app.add_event::<EnemyDestroyedEvent>();
+    app.add_event::<ExplosionEvent>();
+    let target = app.world.spawn((
+        Enemy,
+        Health::new(f32::MAX),
+        ExternalImpulse::default(),
+        TransformBundle::from_transform(Transform::from_xyz(3.0, 0.75, 0.0)),
+    )).id();
+    app.insert_resource(Target(target));
+    app.add_systems(Update, (expire_effects_system, fire_volley_system, collision_detection_system).chain());
+
+    // Let the pool fill up to the steady-state number of shells, fragments and flashes in flight
+    for _ in 0..WARM_UP_FRAMES {
+        app.update();
+    }
+    let (meshes_before, materials_before, entities_before) = counts(&mut app);
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        app.update();
+    }
+    let elapsed = start.elapsed();
+    let (meshes_after, materials_after, entities_after) = counts(&mut app);
+
+    let shots = FRAMES * SHOTS_PER_FRAME;
+    println!("projectile_pool: {} shots over {} frames in {:.2?} ({:.2?}/frame)", shots, FRAMES, elapsed, elapsed / FRAMES as u32);
+    println!("  meshes:    {} -> {}", meshes_before, meshes_after);
+    println!("  materials: {} -> {}", materials_before, materials_after);
+    println!("  entities:  {} -> {}", entities_before, entities_after);
+    assert_eq!((meshes_before, materials_before), (meshes_after, materials_after), "Firing should not add assets");
+    assert_eq!(entities_before, entities_after, "Spent projectiles should be reused");
+}
@@ -0,0 +1,80 @@
+//! Scaling check for the spatial index: times a rebuild plus one radius and one
+//! nearest-enemy-in-range query per unit, against the brute-force scans they replace.
+//!
+//! Run with `cargo bench --bench spatial_index`.
+
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::hint::black_box;
+use std::time::Instant;
+use rust_and_ruin::systems::*;
+
+const UNIT_COUNTS: [usize; 4] = [1_000, 5_000, 10_000, 20_000];
+const UNITS_PER_SQUARE_METRE: f32 = 0.05;
+const SENSOR_RADIUS: f32 = 8.0;
+
+fn scatter(count: usize) -> Vec<SpatialEntry> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let half_extent = (count as f32 / UNITS_PER_SQUARE_METRE).sqrt() / 2.0;
+    (0..count)
+        .map(|i| SpatialEntry {
+            entity: Entity::from_raw(i as u32),
+            position: Vec2::new(rng.gen_range(-half_extent..half_extent), rng.gen_range(-half_extent..half_extent)),
+            radius: DEFAULT_UNIT_RADIUS,
+            side: if i % 2 == 0 { UnitSide::Friendly } else { UnitSide::Enemy },
+        })
+        .collect()
+}
+
+/// Every unit looks for neighbours and its closest enemy in sensor range, using the index.
+fn indexed(index: &mut SpatialIndex, units: &[SpatialEntry]) -> usize {
+    index.clear();
+    for unit in units {
+        index.insert(*unit);
+    }
+    let mut found = 0;
+    for unit in units {
+        found += index.query_radius(unit.position, SENSOR_RADIUS).len();
+        found += index.nearest(unit.position, SENSOR_RADIUS, |e| e.side != unit.side).into_iter().count();
+    }
+    found
+}
+
+/// The same lookups done by walking every unit.
+fn brute_force(units: &[SpatialEntry]) -> usize {
+    let mut found = 0;
+    for unit in units {
+        found += units.iter().filter(|e| e.position.distance(unit.position) <= SENSOR_RADIUS + e.radius).count();
+        found += units.iter()
+            .filter(|e| e.side != unit.side && e.position.distance(unit.position) <= SENSOR_RADIUS)
+            .min_by(|a, b| a.position.distance(unit.position).total_cmp(&b.position.distance(unit.position)))
+            .into_iter()
+            .count();
+    }
+    found
+}
+
+fn main() {
+    let mut index = SpatialIndex::default();
+    for count in UNIT_COUNTS {
+        let units = scatter(count);
+
+        let start = Instant::now();
+        let indexed_found = black_box(indexed(&mut index, &units));
+        let indexed_time = start.elapsed();
+
+        let start = Instant::now();
+        let brute_found = black_box(brute_force(&units));
+        let brute_time = start.elapsed();
+
+        println!(
+            "spatial_index: {:>6} units  indexed {:>10.2?}  brute force {:>10.2?}  ({:.1}x)",
+            count,
+            indexed_time,
+            brute_time,
+            brute_time.as_secs_f64() / indexed_time.as_secs_f64(),
+        );
+        assert_eq!(indexed_found, brute_found, "Index and brute force should find the same units");
+    }
+}
@@ -5,6 +5,8 @@ use rust_and_ruin::mech::*;
 use rust_and_ruin::components::*;
 use rust_and_ruin::systems::*;
 use rust_and_ruin::resources::*;
+use rust_and_ruin::map::spawn_hero_mech;
+use rust_and_ruin::camera::{CameraController, CameraControllerPlugin, CameraShake, CameraShakePlugin};
 use rust_and_ruin::systems::attack_target_propagation::propagate_attack_target_system;
 use rand::Rng;
 
@@ -38,6 +40,7 @@ fn demo_enemy_selection_system(
     mouse_button: Res<Input<MouseButton>>,
     keyboard_input: Res<Input<KeyCode>>,
     mouse_world_pos: Res<MouseWorldPosition>,
+    spatial_index: Res<SpatialIndex>,
     hero_query: Query<(Entity, &Transform, &Children), With<Hero>>,
     enemy_query: Query<(Entity, &Transform), With<Enemy>>,
     existing_indicators: Query<Entity, With<TargetIndicator>>,
@@ -49,23 +52,14 @@ fn demo_enemy_selection_system(
         let click_pos = mouse_world_pos.position;
         
         // Find the closest enemy within a reasonable distance
-        let mut closest_enemy = None;
-        let mut closest_distance = f32::MAX;
         const SELECTION_RADIUS: f32 = 2.0;
-        
-        for (enemy_entity, enemy_transform) in enemy_query.iter() {
-            let enemy_pos_2d = Vec2::new(enemy_transform.translation.x, enemy_transform.translation.z);
-            let distance = enemy_pos_2d.distance(click_pos);
-            
-            if distance < SELECTION_RADIUS && distance < closest_distance {
-                closest_distance = distance;
-                closest_enemy = Some(enemy_entity);
-            }
-        }
+        let closest_enemy = spatial_index.nearest(click_pos, SELECTION_RADIUS, |entry| {
+            entry.side == UnitSide::Enemy && enemy_query.contains(entry.entity)
+        });
         
         // If we found an enemy, set it as the attack target for all tank_bases
-        if let Some(target_entity) = closest_enemy {
-            info!("Selected enemy at distance: {}", closest_distance);
+        if let Some(&SpatialEntry { entity: target_entity, position, .. }) = closest_enemy {
+            info!("Selected enemy at distance: {}", position.distance(click_pos));
             
             // Remove any existing target indicators
             for indicator in existing_indicators.iter() {
@@ -109,74 +103,6 @@ fn demo_enemy_selection_system(
     }
 }
 
-// Simple mech spawn function for demo using old components like main.rs
-fn spawn_mech(
-    commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
-    position: Vec3,
-    rotation: f32,
-) -> Entity {
-    // Create main mech entity (just transform, no visual)
-    let mech_entity = commands.spawn((
-        SpatialBundle {
-            transform: Transform::from_translation(position)
-                .with_rotation(Quat::from_rotation_y(rotation)),
-            ..default()
-        },
-    )).id();
-    
-    // Tank base (box shape)
-    let tank_base = commands.spawn((
-        MechLowerPart,
-        PbrBundle {
-            mesh: meshes.add(Mesh::from(shape::Box::new(1.5, 0.5, 2.0))),
-            material: materials.add(Color::rgb(0.3, 0.3, 0.3).into()),
-            transform: Transform::from_xyz(0.0, 0.25, 0.0),
-            ..default()
-        },
-    )).id();
-    
-    // Turret base (cylinder)
-    let turret_base = commands.spawn((
-        MechUpperPart,
-        TurretRotation {
-            target_angle: 0.0,
-            current_angle: 0.0,
-        },
-        TurretCannon::default(),
-        PbrBundle {
-            mesh: meshes.add(Mesh::from(shape::Cylinder {
-                radius: 0.5,
-                height: 0.4,
-                resolution: 16,
-                segments: 1,
-            })),
-            material: materials.add(Color::rgb(0.2, 0.6, 1.0).into()),
-            transform: Transform::from_xyz(0.0, 0.5, 0.0),
-            ..default()
-        },
-    )).id();
-    
-    // Cannon barrel (box)
-    let cannon = commands.spawn((
-        CannonBarrel,
-        PbrBundle {
-            mesh: meshes.add(Mesh::from(shape::Box::new(0.2, 0.2, 1.0))),
-            material: materials.add(Color::rgb(0.6, 0.6, 0.6).into()),
-            transform: Transform::from_xyz(0.0, 0.0, 0.6),
-            ..default()
-        },
-    )).id();
-    
-    // Set up hierarchy
-    commands.entity(mech_entity).push_children(&[tank_base]);
-    commands.entity(tank_base).push_children(&[turret_base]);
-    commands.entity(turret_base).push_children(&[cannon]);
-    
-    mech_entity
-}
-
 // Clear attack targets when the targeted enemy no longer exists
 fn clear_invalid_attack_targets_system(
     mut commands: Commands,
@@ -295,93 +221,32 @@ fn enemy_respawn_system(
           position);
 }
 
-// Zoom control resource
-#[derive(Resource)]
-struct ZoomLevel {
-    current: f32,
-    min: f32,
-    max: f32,
-    speed: f32,
-}
-
-impl Default for ZoomLevel {
-    fn default() -> Self {
-        Self {
-            current: 0.1,   // Default zoom level
-            min: 0.02,      // Zoomed in (much closer view)
-            max: 0.3,       // Zoomed out (further view)
-            speed: 0.01,    // Zoom speed
-        }
-    }
-}
-
-// Camera zoom system
-fn camera_zoom_system(
-    mut scroll_events: EventReader<bevy::input::mouse::MouseWheel>,
-    keyboard_input: Res<Input<KeyCode>>,
-    mut zoom_level: ResMut<ZoomLevel>,
-    mut camera_query: Query<&mut Projection, With<Camera3d>>,
-) {
-    let mut zoom_delta = 0.0;
-    
-    // Mouse wheel zoom
-    for event in scroll_events.read() {
-        zoom_delta -= event.y * zoom_level.speed;
-    }
-    
-    // Keyboard zoom - zoom in with = or ] keys
-    if keyboard_input.just_pressed(KeyCode::Equals) 
-        || keyboard_input.just_pressed(KeyCode::NumpadAdd)
-        || keyboard_input.just_pressed(KeyCode::BracketRight) {
-        zoom_delta -= zoom_level.speed;
-    }
-    
-    // Keyboard zoom - zoom out with - or [ keys
-    if keyboard_input.just_pressed(KeyCode::Minus) 
-        || keyboard_input.just_pressed(KeyCode::NumpadSubtract)
-        || keyboard_input.just_pressed(KeyCode::BracketLeft) {
-        zoom_delta += zoom_level.speed;
-    }
-    
-    // Continuous zoom when holding keys
-    if keyboard_input.pressed(KeyCode::Equals) 
-        || keyboard_input.pressed(KeyCode::NumpadAdd)
-        || keyboard_input.pressed(KeyCode::BracketRight) {
-        zoom_delta -= zoom_level.speed * 0.5;
-    }
-    if keyboard_input.pressed(KeyCode::Minus) 
-        || keyboard_input.pressed(KeyCode::NumpadSubtract)
-        || keyboard_input.pressed(KeyCode::BracketLeft) {
-        zoom_delta += zoom_level.speed * 0.5;
-    }
-    
-    // Apply zoom changes
-    if zoom_delta != 0.0 {
-        zoom_level.current = (zoom_level.current + zoom_delta).clamp(zoom_level.min, zoom_level.max);
-        
-        for mut projection in camera_query.iter_mut() {
-            if let Projection::Orthographic(ref mut ortho) = projection.as_mut() {
-                ortho.scale = zoom_level.current;
-            }
-        }
-    }
-}
-
 fn main() {
     App::new()
         .add_plugins((
             DefaultPlugins,
             RapierPhysicsPlugin::<NoUserData>::default(),
+            CameraControllerPlugin,
+            CameraShakePlugin,
         ))
         .insert_resource(MouseWorldPosition { position: Vec2::ZERO })
         .insert_resource(ClearColor(Color::rgb(0.1, 0.1, 0.1)))
-        .init_resource::<ZoomLevel>()
         .init_resource::<EnemyRespawnRequest>()
+        .init_resource::<PickingState>()
+        .init_resource::<ProjectileAssets>()
+        .init_resource::<ProjectilePool>()
+        .init_resource::<SpatialIndex>()
+        .add_event::<DamageEvent>()
+        .add_event::<EnemyDestroyedEvent>()
+        .add_event::<ExplosionEvent>()
         .add_systems(Startup, setup)
+        .add_systems(PreUpdate, spatial_index_system)
         .add_systems(Update, (
             (
                 bevy::transform::systems::propagate_transforms,
                 input::mouse_position_system,
+                picking::cursor_ray_system,
+                picking::entity_picking_system,
                 input::click_to_move_system,
                 demo_enemy_selection_system,  // Use custom version that adds AttackTarget to hero
                 input::update_target_indicator_system,
@@ -389,13 +254,15 @@ fn main() {
                 propagate_attack_target_system,  // Propagate AttackTarget down hierarchy
             ).chain(),
             (
-                // Use proper turret and firing systems
-                turret_control_system,
-                projectile::auto_fire_system,
+                // Aim the upper body and fire its mounted weapons
+                upper_body_control_system,
+                weapon_elevation_system,
+                weapon_cooldown_system,
+                heat_system,
+                weapon_control_system,
             ).chain(),
             (
-                movement::movement_system,
-                tank_movement_system,
+                mech_movement_system,
                 projectile::rocket_acceleration_system,
                 projectile::tank_shell_movement_system,
                 projectile::projectile_lifetime_system,
@@ -403,15 +270,19 @@ fn main() {
             ).chain(),
             (
                 collision_detection_system,
+                fragment_collision_system,
+                explosion_system,
                 visual_effects::hit_flash_system,
                 visual_effects::fragment_lifetime_system,
                 visual_effects::fragment_visual_fade_system,
+                click_marker_lifetime_system,
                 clear_invalid_attack_targets_system,
                 enemy_health_monitor_system,
                 enemy_respawn_system,
             ).chain(),
-            camera_zoom_system,
             debug_info_system,
+            weapon_range_ring_system,
+            firing_arc_system,
         ))
         .run();
 }
@@ -422,21 +293,28 @@ fn setup(
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     // Spawn camera with orthographic projection
-    let camera_angle = 63.435_f32.to_radians();
-    let camera_distance = 50.0;
-    let camera_height = camera_distance * camera_angle.sin();
-    let camera_horizontal = camera_distance * camera_angle.cos();
+    let camera_controller = CameraController {
+        pitch: 63.435,
+        distance: 50.0,
+        zoom: 0.1,
+        min_zoom: 0.02,  // Zoomed in (much closer view)
+        max_zoom: 0.3,   // Zoomed out (further view)
+        ..default()
+    };
     
-    commands.spawn(Camera3dBundle {
-        projection: OrthographicProjection {
-            scale: 0.1,
+    commands.spawn((
+        Camera3dBundle {
+            projection: OrthographicProjection {
+                scale: camera_controller.zoom,
+                ..default()
+            }.into(),
+            transform: camera_controller.camera_transform(),
+            tonemapping: Tonemapping::None,
             ..default()
-        }.into(),
-        transform: Transform::from_xyz(0.0, camera_height, camera_horizontal)
-            .looking_at(Vec3::ZERO, Vec3::Y),
-        tonemapping: Tonemapping::None,
-        ..default()
-    });
+        },
+        camera_controller,
+        CameraShake::default(),
+    ));
     
     // Add lighting
     commands.spawn(DirectionalLightBundle {
@@ -454,20 +332,8 @@ fn setup(
         ..default()
     });
     
-    // Spawn mech at center
-    let mech_entity = spawn_mech(
-        &mut commands,
-        &mut meshes,
-        &mut materials,
-        Vec3::ZERO,
-        0.0,
-    );
-    
-    // Add Hero and movement components to mech
-    commands.entity(mech_entity).insert((
-        Hero,
-        TankMovement::default(),
-    ));
+    // Spawn the hero mech at center
+    spawn_hero_mech(&mut commands, &mut meshes, &mut materials, Vec3::ZERO, 0.0);
     
     // Add ground plane for shells to bounce on
     commands.spawn((
@@ -537,41 +403,41 @@ fn setup(
     info!("Turret Lock Demo Started");
     info!("- Press Q near the red enemy to lock turret");
     info!("- Left click to move the tank");
-    info!("- Use mouse wheel or -/= keys (or [/] keys) to zoom in/out");
+    info!("- Use mouse wheel or -/= keys to zoom in/out, WASD or screen edges to pan");
     info!("- Watch how the turret tracks the enemy while moving");
 }
 
 fn debug_info_system(
-    hero_query: Query<(&Transform, Option<&AttackTarget>, Option<&TankMovement>, &Children), With<Hero>>,
-    children_query: Query<&Children>,
-    turret_query: Query<(&Transform, &TurretRotation, &TurretCannon), With<TurretCannon>>,
+    hero_query: Query<(&Transform, Option<&AttackTarget>, &MechMovement, &MechLowerBody, &MechHierarchy), With<Hero>>,
+    turret_query: Query<(&GlobalTransform, &MechRotation)>,
+    weapon_query: Query<&MechWeapon>,
     enemy_query: Query<&Transform, With<Enemy>>,
     mut text_query: Query<&mut Text>,
-    zoom_level: Res<ZoomLevel>,
+    camera_query: Query<&CameraController>,
 ) {
-    if let Ok((hero_transform, attack_target, tank_movement, children)) = hero_query.get_single() {
+    if let Ok((hero_transform, attack_target, movement, lower_body, hierarchy)) = hero_query.get_single() {
         if let Ok(mut text) = text_query.get_single_mut() {
-            let mut status = String::from("Press Q near enemy to lock turret\nLeft click to move tank\nMouse wheel or -/= or [/] to zoom\n\n");
+            let mut status = String::from("Press Q near enemy to lock turret\nLeft click to move tank\nMouse wheel or -/= to zoom, WASD to pan\n\n");
             
             status.push_str(&format!("Mech Position: ({:.1}, {:.1})\n", 
                 hero_transform.translation.x, 
                 hero_transform.translation.z));
             
-            // Add tank movement state info
-            if let Some(movement) = tank_movement {
-                status.push_str(&format!("Tank State: {:?}, Speed: {:.1}/{:.1}\n", 
-                    movement.rotation_state,
-                    movement.current_speed,
-                    movement.max_speed));
-                status.push_str(&format!("Tank Rotation: current={:.1}°, target={:.1}°\n",
-                    hero_transform.rotation.to_euler(EulerRot::YXZ).0.to_degrees(),
-                    movement.target_rotation));
-            }
+            // Add mech movement state info
+            status.push_str(&format!("Tank State: {:?}, Speed: {:.1}/{:.1}\n", 
+                movement.movement_state,
+                movement.current_speed,
+                lower_body.movement_stats.max_speed));
+            status.push_str(&format!("Tank Rotation: current={:.1}°, target={:.1}°\n",
+                hero_transform.rotation.to_euler(EulerRot::YXZ).0.to_degrees(),
+                movement.target_rotation));
             
-            status.push_str(&format!("Zoom Level: {:.2} (Min: {:.2}, Max: {:.2})\n", 
-                zoom_level.current, 
-                zoom_level.min, 
-                zoom_level.max));
+            if let Ok(camera_controller) = camera_query.get_single() {
+                status.push_str(&format!("Zoom Level: {:.2} (Min: {:.2}, Max: {:.2})\n", 
+                    camera_controller.zoom, 
+                    camera_controller.min_zoom, 
+                    camera_controller.max_zoom));
+            }
             
             if let Some(attack_target) = attack_target {
                 status.push_str("Turret Status: LOCKED ON TARGET\n");
@@ -582,68 +448,44 @@ fn debug_info_system(
                         enemy_transform.translation.z));
                 }
                 
-                // Find turret in the hierarchy (hero -> tank_base -> turret_base)
-                let mut found_turret = false;
-                // First, find tank_base
-                if let Some(&tank_base_entity) = children.iter().next() {
-                    // Then get children of tank_base (which should include turret_base)
-                    if let Ok(tank_children) = children_query.get(tank_base_entity) {
-                        for turret_entity in tank_children {
-                            if let Ok((turret_transform, turret_rotation, turret_cannon)) = turret_query.get(*turret_entity) {
-                                found_turret = true;
-                                status.push_str(&format!("Turret Angle: {:.1}° (Target: {:.1}°)\n",
-                                    turret_rotation.current_angle,
-                                    turret_rotation.target_angle));
-                                
-                                // Check firing conditions
-                                if let Ok(enemy_transform) = enemy_query.get(attack_target.entity) {
-                                    let hero_pos = Vec2::new(hero_transform.translation.x, hero_transform.translation.z);
-                                    let enemy_pos = Vec2::new(enemy_transform.translation.x, enemy_transform.translation.z);
-                                    let distance = hero_pos.distance(enemy_pos);
-                                    
-                                    status.push_str(&format!("Enemy Distance: {:.1} (Range: 10.0)\n", distance));
-                                    status.push_str(&format!("Fire Rate: {} shots/sec\n", 1.0 / turret_cannon.fire_rate));
-                                    
-                                    if distance > 10.0 {
-                                        status.push_str("Firing Status: OUT OF RANGE\n");
-                                    } else {
-                                        // Check if turret is facing target
-                                        let angle_diff = (turret_rotation.current_angle - turret_rotation.target_angle).abs();
-                                        if angle_diff > 5.0 && angle_diff < 355.0 {
-                                            status.push_str(&format!("Firing Status: ROTATING (diff: {:.1}°)\n", angle_diff.min(360.0 - angle_diff)));
-                                        } else {
-                                            status.push_str("Firing Status: READY TO FIRE\n");
-                                        }
-                                    }
-                                }
-                                
-                                // Calculate if turret is facing target
-                                // Need to calculate global transform through hierarchy
-                                let tank_transform = Transform::from_xyz(0.0, 0.25, 0.0); // tank's local transform
-                                let turret_local = Transform::from_xyz(0.0, 0.5, 0.0); // turret's local transform
-                                let global_turret = hero_transform
-                                    .mul_transform(tank_transform)
-                                    .mul_transform(turret_local)
-                                    .mul_transform(Transform::from_rotation(turret_transform.rotation));
-                                let turret_pos = Vec2::new(global_turret.translation.x, global_turret.translation.z);
-                                
-                                if let Ok(enemy_transform) = enemy_query.get(attack_target.entity) {
-                                    let enemy_pos = Vec2::new(enemy_transform.translation.x, enemy_transform.translation.z);
-                                    let is_facing = is_turret_facing_target(
-                                        &global_turret,
-                                        turret_pos,
-                                        enemy_pos,
-                                        5.0,
-                                    );
-                                    status.push_str(&format!("Facing Target: {}\n", if is_facing { "YES" } else { "NO" }));
-                                }
-                                break;
-                            }
+                // The upper body hangs directly off the hero and carries the weapons
+                let turret = hierarchy.upper.and_then(|upper| turret_query.get(upper).ok());
+                let weapon = hierarchy.weapons.first().and_then(|weapon| weapon_query.get(*weapon).ok());
+                if let (Some((turret_transform, turret_rotation)), Some(weapon)) = (turret, weapon) {
+                    status.push_str(&format!("Turret Angle: {:.1}° (Target: {:.1}°)\n",
+                        turret_rotation.current_angle,
+                        turret_rotation.target_angle));
+                    
+                    if let Ok(enemy_transform) = enemy_query.get(attack_target.entity) {
+                        let range = weapon.weapon_stats.range;
+                        let hero_pos = Vec2::new(hero_transform.translation.x, hero_transform.translation.z);
+                        let enemy_pos = Vec2::new(enemy_transform.translation.x, enemy_transform.translation.z);
+                        let distance = hero_pos.distance(enemy_pos);
+                        
+                        status.push_str(&format!("Enemy Distance: {:.1} (Range: {:.1})\n", distance, range));
+                        status.push_str(&format!("Fire Rate: {} shots/sec\n", 1.0 / weapon.weapon_stats.fire_rate));
+                        
+                        // Check if turret is facing target
+                        let (_, rotation, translation) = turret_transform.to_scale_rotation_translation();
+                        let world_turret = Transform::from_translation(translation).with_rotation(rotation);
+                        let turret_pos = Vec2::new(translation.x, translation.z);
+                        let is_facing = is_turret_facing_target(
+                            &world_turret,
+                            turret_pos,
+                            enemy_pos,
+                            FIRING_ANGLE_TOLERANCE,
+                        );
+                        
+                        if distance > range {
+                            status.push_str("Firing Status: OUT OF RANGE\n");
+                        } else if !is_facing {
+                            status.push_str("Firing Status: ROTATING\n");
+                        } else {
+                            status.push_str("Firing Status: READY TO FIRE\n");
                         }
+                        status.push_str(&format!("Facing Target: {}\n", if is_facing { "YES" } else { "NO" }));
                     }
-                }
-                
-                if !found_turret {
+                } else {
                     status.push_str("Turret: Not found in hierarchy\n");
                 }
             } else {
@@ -653,4 +495,4 @@ fn debug_info_system(
             text.sections[0].value = status;
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,254 @@
+use bevy::prelude::*;
+use bevy::input::mouse::MouseWheel;
+use bevy::window::PrimaryWindow;
+use crate::components::Selected;
+use super::{cursor_to_ground_position, CAMERA_ANGLE_DEGREES, CAMERA_DISTANCE, GROUND_PLANE_HEIGHT};
+
+pub const ROTATE_LEFT_KEY: KeyCode = KeyCode::Comma;
+pub const ROTATE_RIGHT_KEY: KeyCode = KeyCode::Period;
+pub const FOLLOW_TOGGLE_KEY: KeyCode = KeyCode::F;
+
+/// RTS camera state and tuning. The camera orbits `focus` on the ground at a fixed
+/// `pitch`, rotated around Y by `yaw` in 90° steps.
+#[derive(Component, Debug, Clone)]
+pub struct CameraController {
+    pub focus: Vec3,
+    pub yaw: f32,   // degrees
+    pub pitch: f32, // degrees above the ground plane
+    pub distance: f32,
+    pub zoom: f32,  // orthographic scale
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    pub zoom_speed: f32, // fraction of the current zoom per scroll line
+    pub pan_speed: f32,  // screen widths per second
+    pub edge_scroll_margin: f32, // pixels
+    pub bounds: Rect, // allowed focus area on the X/Z plane
+    pub allow_rotation: bool,
+    pub follow_selected: bool,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            focus: Vec3::ZERO,
+            yaw: 0.0,
+            pitch: CAMERA_ANGLE_DEGREES,
+            distance: CAMERA_DISTANCE,
+            zoom: 0.02,
+            min_zoom: 0.01,
+            max_zoom: 0.05,
+            zoom_speed: 0.1,
+            pan_speed: 0.5,
+            edge_scroll_margin: 10.0,
+            bounds: Rect::new(-25.0, -25.0, 25.0, 25.0),
+            allow_rotation: true,
+            follow_selected: false,
+        }
+    }
+}
+
+impl CameraController {
+    pub fn camera_transform(&self) -> Transform {
+        calculate_camera_transform(self.focus, self.yaw, self.pitch, self.distance)
+    }
+
+    pub fn clamp_focus(&mut self) {
+        self.focus.x = self.focus.x.clamp(self.bounds.min.x, self.bounds.max.x);
+        self.focus.z = self.focus.z.clamp(self.bounds.min.y, self.bounds.max.y);
+    }
+}
+
+pub struct CameraControllerPlugin;
+
+impl Plugin for CameraControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            camera_pan_system,
+            camera_zoom_system,
+            camera_rotation_system,
+            camera_follow_system,
+            apply_camera_controller_system,
+        ).chain());
+    }
+}
+
+pub fn calculate_camera_transform(focus: Vec3, yaw_degrees: f32, pitch_degrees: f32, distance: f32) -> Transform {
+    let pitch = pitch_degrees.to_radians();
+    let offset = Quat::from_rotation_y(yaw_degrees.to_radians())
+        * Vec3::new(0.0, distance * pitch.sin(), distance * pitch.cos());
+
+    Transform::from_translation(focus + offset).looking_at(focus, Vec3::Y)
+}
+
+/// Converts a screen-space pan (x right, y up) into a ground-plane (X/Z) offset for a camera with the given yaw.
+pub fn screen_pan_to_world(pan: Vec2, yaw_degrees: f32) -> Vec2 {
+    // At yaw 0 the camera sits on +Z looking towards -Z, so screen-up is -Z and screen-right is +X
+    let world = Quat::from_rotation_y(yaw_degrees.to_radians()) * Vec3::new(pan.x, 0.0, -pan.y);
+    Vec2::new(world.x, world.z)
+}
+
+/// Returns the pan direction for a cursor near the window edges, in screen space (x right, y up).
+pub fn edge_scroll_direction(cursor_position: Vec2, window_size: Vec2, margin: f32) -> Vec2 {
+    let mut direction = Vec2::ZERO;
+    if cursor_position.x <= margin {
+        direction.x -= 1.0;
+    } else if cursor_position.x >= window_size.x - margin {
+        direction.x += 1.0;
+    }
+    // Window coordinates grow downwards
+    if cursor_position.y <= margin {
+        direction.y += 1.0;
+    } else if cursor_position.y >= window_size.y - margin {
+        direction.y -= 1.0;
+    }
+    direction
+}
+
+/// New focus after zooming so that `anchor` (the ground point under the cursor) stays under the cursor.
+/// Orthographic ground offsets scale linearly with the projection scale.
+pub fn zoom_focus_towards(focus: Vec3, anchor: Vec3, old_zoom: f32, new_zoom: f32) -> Vec3 {
+    anchor + (focus - anchor) * (new_zoom / old_zoom)
+}
+
+pub fn camera_pan_system(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut CameraController>,
+) {
+    let mut pan = Vec2::ZERO;
+    if keyboard_input.any_pressed([KeyCode::W, KeyCode::Up]) {
+        pan.y += 1.0;
+    }
+    if keyboard_input.any_pressed([KeyCode::S, KeyCode::Down]) {
+        pan.y -= 1.0;
+    }
+    if keyboard_input.any_pressed([KeyCode::D, KeyCode::Right]) {
+        pan.x += 1.0;
+    }
+    if keyboard_input.any_pressed([KeyCode::A, KeyCode::Left]) {
+        pan.x -= 1.0;
+    }
+
+    let window = windows.get_single().ok();
+
+    for mut controller in camera_query.iter_mut() {
+        let mut direction = pan;
+        if let Some(window) = window {
+            if let Some(cursor_position) = window.cursor_position() {
+                let window_size = Vec2::new(window.width(), window.height());
+                direction += edge_scroll_direction(cursor_position, window_size, controller.edge_scroll_margin);
+            }
+        }
+
+        if direction == Vec2::ZERO {
+            continue;
+        }
+
+        // Manual panning takes over from following
+        controller.follow_selected = false;
+
+        // Scale with zoom so a pan always covers the same share of the screen
+        let view_width = window.map(|window| window.width()).unwrap_or(1280.0) * controller.zoom;
+        let step = direction.normalize() * controller.pan_speed * view_width * time.delta_seconds();
+        let world_step = screen_pan_to_world(step, controller.yaw);
+
+        controller.focus.x += world_step.x;
+        controller.focus.z += world_step.y;
+        controller.clamp_focus();
+    }
+}
+
+pub fn camera_zoom_system(
+    mut scroll_events: EventReader<MouseWheel>,
+    keyboard_input: Res<Input<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut CameraController, &Camera, &GlobalTransform)>,
+) {
+    let mut scroll: f32 = scroll_events.read().map(|event| event.y).sum();
+    if keyboard_input.any_just_pressed([KeyCode::Equals, KeyCode::NumpadAdd]) {
+        scroll += 1.0;
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        scroll -= 1.0;
+    }
+    if scroll == 0.0 {
+        return;
+    }
+
+    let cursor_position = windows.get_single().ok().and_then(|window| window.cursor_position());
+
+    for (mut controller, camera, camera_transform) in camera_query.iter_mut() {
+        let old_zoom = controller.zoom;
+        let new_zoom = (old_zoom * (1.0 - scroll * controller.zoom_speed))
+            .clamp(controller.min_zoom, controller.max_zoom);
+        if new_zoom == old_zoom {
+            continue;
+        }
+
+        let anchor = cursor_position
+            .and_then(|cursor| cursor_to_ground_position(camera, camera_transform, cursor, GROUND_PLANE_HEIGHT));
+        if let Some(anchor) = anchor {
+            controller.focus = zoom_focus_towards(controller.focus, anchor, old_zoom, new_zoom);
+        }
+
+        controller.zoom = new_zoom;
+        controller.clamp_focus();
+    }
+}
+
+pub fn camera_rotation_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut camera_query: Query<&mut CameraController>,
+) {
+    let mut steps = 0.0;
+    if keyboard_input.just_pressed(ROTATE_LEFT_KEY) {
+        steps -= 1.0;
+    }
+    if keyboard_input.just_pressed(ROTATE_RIGHT_KEY) {
+        steps += 1.0;
+    }
+    if steps == 0.0 {
+        return;
+    }
+
+    for mut controller in camera_query.iter_mut() {
+        if controller.allow_rotation {
+            controller.yaw = (controller.yaw + steps * 90.0).rem_euclid(360.0);
+        }
+    }
+}
+
+pub fn camera_follow_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    selected_query: Query<&GlobalTransform, With<Selected>>,
+    mut camera_query: Query<&mut CameraController>,
+) {
+    let toggle = keyboard_input.just_pressed(FOLLOW_TOGGLE_KEY);
+    let target = selected_query.iter().next().map(|transform| transform.translation());
+
+    for mut controller in camera_query.iter_mut() {
+        if toggle {
+            controller.follow_selected = !controller.follow_selected;
+        }
+
+        if controller.follow_selected {
+            if let Some(target) = target {
+                controller.focus = Vec3::new(target.x, GROUND_PLANE_HEIGHT, target.z);
+                controller.clamp_focus();
+            }
+        }
+    }
+}
+
+/// Writes the controller state to the camera's transform and orthographic scale.
+pub fn apply_camera_controller_system(
+    mut camera_query: Query<(&CameraController, &mut Transform, &mut Projection), Changed<CameraController>>,
+) {
+    for (controller, mut transform, mut projection) in camera_query.iter_mut() {
+        *transform = controller.camera_transform();
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scale = controller.zoom;
+        }
+    }
+}
@@ -1,5 +1,11 @@
 use bevy::prelude::*;
 
+pub mod controller;
+pub mod shake;
+
+pub use controller::*;
+pub use shake::*;
+
 pub const CAMERA_ANGLE_DEGREES: f32 = 45.0;
 pub const CAMERA_HEIGHT: f32 = 20.0;
 pub const CAMERA_DISTANCE: f32 = 30.0;
@@ -13,6 +19,41 @@ pub fn create_orthographic_camera_transform(angle_degrees: f32) -> Transform {
         .looking_at(Vec3::ZERO, Vec3::Y)
 }
 
+/// Height of the ground plane that cursor picking projects onto.
+pub const GROUND_PLANE_HEIGHT: f32 = 0.0;
+
+/// Intersects a ray with the horizontal plane `y = plane_height`.
+/// Returns `None` when the ray is parallel to the plane or points away from it.
+pub fn ray_plane_intersection(ray_origin: Vec3, ray_direction: Vec3, plane_height: f32) -> Option<Vec3> {
+    if ray_direction.y.abs() <= 0.001 {
+        return None;
+    }
+    
+    let t = (plane_height - ray_origin.y) / ray_direction.y;
+    if t < 0.0 {
+        return None;
+    }
+    
+    let mut intersection = ray_origin + ray_direction * t;
+    intersection.y = plane_height;  // Snap away float error so callers can compare heights exactly
+    Some(intersection)
+}
+
+/// Projects a cursor position onto the ground through the live camera.
+/// This is the picking path used at runtime; it honours the camera's tilt, pan and zoom
+/// because it goes through the camera's own projection matrix.
+pub fn cursor_to_ground_position(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    cursor_position: Vec2,
+    ground_height: f32,
+) -> Option<Vec3> {
+    let ray = camera.viewport_to_world(camera_transform, cursor_position)?;
+    ray_plane_intersection(ray.origin, ray.direction, ground_height)
+}
+
+/// Pure-math equivalent of [`cursor_to_ground_position`] for an orthographic camera,
+/// usable without a render target (e.g. in tests).
 pub fn screen_to_world_position(
     screen_pos: Vec2,
     camera_transform: &Transform,
@@ -32,21 +73,12 @@ pub fn screen_to_world_position(
         0.0,
     );
     
-    let world_pos = camera_transform.compute_matrix() * camera_space.extend(1.0);
-    let world_pos = Vec3::new(world_pos.x, world_pos.y, world_pos.z);
-    
-    let ray_origin = camera_transform.translation;
-    let ray_direction = (world_pos - ray_origin).normalize();
-    
-    if ray_direction.y.abs() > 0.001 {
-        let t = -ray_origin.y / ray_direction.y;
-        if t > 0.0 {
-            let intersection = ray_origin + ray_direction * t;
-            return Some(intersection);
-        }
-    }
+    // Orthographic rays are all parallel to the camera's forward axis,
+    // only their origin moves across the view plane
+    let ray_origin = camera_transform.transform_point(camera_space);
+    let ray_direction = camera_transform.forward();
     
-    None
+    ray_plane_intersection(ray_origin, ray_direction, GROUND_PLANE_HEIGHT)
 }
 
 pub fn world_to_screen_position(
@@ -73,15 +105,25 @@ pub fn world_to_screen_position(
 
 pub fn setup_orthographic_camera(commands: &mut Commands) {
     // Simple top-down camera for debugging
-    commands.spawn(Camera3dBundle {
-        transform: Transform::from_xyz(0.0, 15.0, 0.1).looking_at(Vec3::ZERO, Vec3::Y),
-        projection: OrthographicProjection {
-            scale: 0.02,
-            ..default()
-        }.into(),
-        tonemapping: bevy::core_pipeline::tonemapping::Tonemapping::None,
+    let controller = CameraController {
+        pitch: 89.6,
+        distance: 15.0,
         ..default()
-    });
+    };
+    
+    commands.spawn((
+        Camera3dBundle {
+            transform: controller.camera_transform(),
+            projection: OrthographicProjection {
+                scale: controller.zoom,
+                ..default()
+            }.into(),
+            tonemapping: bevy::core_pipeline::tonemapping::Tonemapping::None,
+            ..default()
+        },
+        controller,
+        CameraShake::default(),
+    ));
     
     commands.spawn(DirectionalLightBundle {
         directional_light: DirectionalLight {
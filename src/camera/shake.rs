@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use super::controller::{camera_pan_system, apply_camera_controller_system, CameraController};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CameraShakeSource {
+    OwnMechHit,
+    Explosion,
+    HeavyWeaponFire,
+}
+
+/// Requests camera trauma from something that happened at `position`.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct CameraShakeEvent {
+    pub source: CameraShakeSource,
+    pub position: Vec3,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct CameraShakeSettings {
+    pub enabled: bool, // Accessibility: disables all shake when false
+    pub own_mech_hit_trauma: f32,
+    pub explosion_trauma: f32,
+    pub heavy_weapon_fire_trauma: f32,
+    pub falloff_radius: f32, // Explosions and weapon fire further than this from the view add no trauma
+    pub max_trauma: f32,
+}
+
+impl Default for CameraShakeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            own_mech_hit_trauma: 0.5,
+            explosion_trauma: 0.35,
+            heavy_weapon_fire_trauma: 0.15,
+            falloff_radius: 20.0,
+            max_trauma: 1.0,
+        }
+    }
+}
+
+/// Trauma-based shake: events add trauma, trauma decays over time and
+/// the shake intensity is trauma squared so small hits stay subtle.
+#[derive(Component, Debug, Clone)]
+pub struct CameraShake {
+    pub trauma: f32,
+    pub decay: f32,      // trauma lost per second
+    pub max_offset: f32, // world units at full trauma
+    pub max_roll: f32,   // degrees at full trauma
+    pub frequency: f32,
+    pub applied_offset: Vec3,
+    pub applied_roll: f32, // degrees
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.5,
+            max_offset: 0.5,
+            max_roll: 2.0,
+            frequency: 15.0,
+            applied_offset: Vec3::ZERO,
+            applied_roll: 0.0,
+        }
+    }
+}
+
+impl CameraShake {
+    pub fn add_trauma(&mut self, amount: f32, max_trauma: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, max_trauma.min(1.0));
+    }
+}
+
+pub struct CameraShakePlugin;
+
+impl Plugin for CameraShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CameraShakeEvent>()
+            .init_resource::<CameraShakeSettings>()
+            .add_systems(Update, (
+                camera_shake_restore_system.before(camera_pan_system),
+                (
+                    camera_trauma_system,
+                    camera_shake_system,
+                ).chain().after(apply_camera_controller_system),
+            ));
+    }
+}
+
+/// Trauma an event adds for a listener at `listener_position`.
+/// Hits on the player's own mech always register, other events fade out with distance.
+pub fn trauma_for_event(settings: &CameraShakeSettings, event: &CameraShakeEvent, listener_position: Vec3) -> f32 {
+    if !settings.enabled {
+        return 0.0;
+    }
+
+    let base_trauma = match event.source {
+        CameraShakeSource::OwnMechHit => return settings.own_mech_hit_trauma,
+        CameraShakeSource::Explosion => settings.explosion_trauma,
+        CameraShakeSource::HeavyWeaponFire => settings.heavy_weapon_fire_trauma,
+    };
+
+    let listener_2d = Vec2::new(listener_position.x, listener_position.z);
+    let event_2d = Vec2::new(event.position.x, event.position.z);
+    let falloff = 1.0 - listener_2d.distance(event_2d) / settings.falloff_radius;
+
+    base_trauma * falloff.clamp(0.0, 1.0)
+}
+
+/// Smooth pseudo-noise offset (x right, y up) and roll for the given trauma at `time`.
+pub fn calculate_shake_offset(shake: &CameraShake, time: f32) -> (Vec2, f32) {
+    let intensity = shake.trauma * shake.trauma;
+    let t = time * shake.frequency;
+
+    // Incommensurate frequencies so the axes never line up into a visible pattern
+    let x = (t * 1.0).sin() * 0.6 + (t * 2.3 + 1.7).sin() * 0.4;
+    let y = (t * 1.3 + 0.5).sin() * 0.6 + (t * 2.9 + 4.1).sin() * 0.4;
+    let roll = (t * 0.9 + 2.2).sin() * 0.7 + (t * 3.1 + 0.3).sin() * 0.3;
+
+    (
+        Vec2::new(x, y) * shake.max_offset * intensity,
+        roll * shake.max_roll * intensity,
+    )
+}
+
+/// Removes last frame's shake so the camera controller works on the unshaken transform.
+pub fn camera_shake_restore_system(
+    mut camera_query: Query<(&mut CameraShake, &mut Transform)>,
+) {
+    for (mut shake, mut transform) in camera_query.iter_mut() {
+        if shake.applied_offset == Vec3::ZERO && shake.applied_roll == 0.0 {
+            continue;
+        }
+
+        transform.translation -= shake.applied_offset;
+        transform.rotate_local_z(-shake.applied_roll.to_radians());
+        shake.applied_offset = Vec3::ZERO;
+        shake.applied_roll = 0.0;
+    }
+}
+
+pub fn camera_trauma_system(
+    mut shake_events: EventReader<CameraShakeEvent>,
+    settings: Res<CameraShakeSettings>,
+    mut camera_query: Query<(&mut CameraShake, &Transform, Option<&CameraController>)>,
+) {
+    for event in shake_events.read() {
+        for (mut shake, transform, controller) in camera_query.iter_mut() {
+            // Distance is measured from what the camera is looking at, not from the camera itself
+            let listener_position = controller
+                .map(|controller| controller.focus)
+                .unwrap_or(transform.translation);
+            let trauma = trauma_for_event(&settings, event, listener_position);
+            if trauma > 0.0 {
+                shake.add_trauma(trauma, settings.max_trauma);
+            }
+        }
+    }
+}
+
+pub fn camera_shake_system(
+    time: Res<Time>,
+    settings: Res<CameraShakeSettings>,
+    mut camera_query: Query<(&mut CameraShake, &mut Transform)>,
+) {
+    for (mut shake, mut transform) in camera_query.iter_mut() {
+        if !settings.enabled {
+            shake.trauma = 0.0;
+            continue;
+        }
+
+        if shake.trauma <= 0.0 {
+            continue;
+        }
+
+        let (offset, roll) = calculate_shake_offset(&shake, time.elapsed_seconds());
+        let world_offset = transform.right() * offset.x + transform.up() * offset.y;
+
+        transform.translation += world_offset;
+        transform.rotate_local_z(roll.to_radians());
+        shake.applied_offset = world_offset;
+        shake.applied_roll = roll;
+
+        shake.trauma = (shake.trauma - shake.decay * time.delta_seconds()).max(0.0);
+    }
+}
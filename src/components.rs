@@ -8,20 +8,67 @@ pub struct AttackTarget {
     pub entity: Entity,
 }
 
+/// Ground marker left where a move order was clicked, removed when `timer` runs out.
+#[derive(Component)]
+pub struct ClickMarker {
+    pub timer: Timer,
+}
+
 #[derive(Component)]
 pub struct TargetIndicator {
     pub target: Entity,
 }
 
+#[derive(Component)]
+pub struct HoverHighlight {
+    pub target: Entity,
+}
+
+#[derive(Component)]
+pub struct Selected;
+
 #[derive(Component)]
 pub struct Enemy;
 
+/// Solid obstacle that shelters whatever is behind it from explosions.
+#[derive(Component)]
+pub struct Wall;
+
+/// Map structure that takes damage like an enemy and collapses into `Rubble` at zero health.
+/// `half_extents` is its collider size; `stage` counts the damage stages it has shown so far.
+#[derive(Component, Debug, Clone)]
+pub struct Destructible {
+    pub half_extents: Vec3,
+    pub base_color: Color,
+    pub stage: u8,
+}
+
+/// Passable debris left where a destructible structure stood.
+#[derive(Component)]
+pub struct Rubble;
+
+/// Heightmap ground mesh and collider.
+#[derive(Component)]
+pub struct Terrain;
+
+/// Height of a unit's origin above the terrain. Units with it follow the ground as they move.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct GroundClearance(pub f32);
+
+/// How far a unit can see. Units without one reveal nothing for their side.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct VisionRange(pub f32);
+
 #[derive(Component)]
 pub struct Projectile {
     pub damage: f32,
     pub speed: f32,
 }
 
+/// Root of the mech that fired a projectile. Mechs cannot hit their own parts.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FiredBy(pub Entity);
+
 #[derive(Component)]
 pub struct Velocity {
     pub value: Vec2,
@@ -44,6 +91,34 @@ impl Health {
     }
 }
 
+/// Armor plate on a target, in millimetres. Kinetic shells that cannot get
+/// through it at their impact angle ricochet off instead of dealing damage.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Armor {
+    pub thickness: f32,
+}
+
+impl Armor {
+    pub fn new(thickness: f32) -> Self {
+        Self { thickness }
+    }
+}
+
+/// Sent whenever damage is applied to an entity's `Health`.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: f32,
+    pub position: Vec3,
+}
+
+/// Sent when an enemy's `Health` runs out, as it is despawned.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EnemyDestroyedEvent {
+    pub enemy: Entity,
+    pub position: Vec3,
+}
+
 #[derive(Component)]
 pub struct Rocket {
     pub initial_speed: f32,
@@ -70,39 +145,11 @@ pub struct ShellFragment {
     pub max_distance: f32,
     pub spawn_position: Vec2,
     pub fragment_index: u8,
+    pub struck_target: Option<Entity>, // What the parent shell hit, which fragments fly clear of
+    pub penetrations_left: u8,         // Targets it can pass through before it is spent
 }
 
 #[derive(Component)]
 pub struct HitFlash {
     pub timer: Timer,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum TankRotationState {
-    Idle,
-    Rotating,
-    Moving,
-}
-
-#[derive(Component)]
-pub struct TankMovement {
-    pub rotation_state: TankRotationState,
-    pub target_rotation: f32,
-    pub current_speed: f32,
-    pub acceleration: f32,
-    pub max_speed: f32,
-    pub rotation_speed: f32, // degrees per second
-}
-
-impl Default for TankMovement {
-    fn default() -> Self {
-        Self {
-            rotation_state: TankRotationState::Idle,
-            target_rotation: 0.0,
-            current_speed: 0.0,
-            acceleration: 3.0,
-            max_speed: 5.0,
-            rotation_speed: 90.0,
-        }
-    }
 }
\ No newline at end of file
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::RapierConfiguration;
+use crate::components::{Enemy, EnemyDestroyedEvent, Hero, Projectile, Rubble};
+use crate::map::{ground_position, spawn_hero_mech, spawn_map, MapDefinition, MapEntity, MapTag, MapTriggerEvent, SpawnSide};
+use crate::resources::{GameOutcome, GameState};
+use crate::systems::navigation::NavigationGrid;
+use crate::systems::projectile_pool::Recycle;
+use crate::systems::terrain::Heightmap;
+
+pub mod objectives;
+pub mod waves;
+
+pub use objectives::*;
+pub use waves::*;
+
+pub const START_KEY: KeyCode = KeyCode::Return;
+pub const PAUSE_KEY: KeyCode = KeyCode::Escape;
+pub const KILL_SCORE: u32 = 100;
+
+/// Top-level flow of the game. Gameplay systems only run while `Playing`.
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+/// Wave survival with a mission on top: hold out against `waves` and complete every one of
+/// `objectives` to win. Losing the hero or failing an objective loses the run.
+pub struct GameModePlugin {
+    pub waves: WaveDirector,
+    pub objectives: ObjectiveTracker,
+}
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<AppState>()
+            .init_resource::<GameState>()
+            .insert_resource(self.waves.clone())
+            .insert_resource(self.objectives.clone())
+            .add_event::<EnemyDestroyedEvent>()
+            .add_event::<MapTriggerEvent>()
+            .add_event::<ObjectiveCompletedEvent>()
+            .add_event::<ObjectiveFailedEvent>()
+            .add_systems(OnExit(AppState::GameOver), rebuild_map_system)
+            .add_systems(OnExit(AppState::MainMenu), start_run_system)
+            .add_systems(Update, (
+                game_state_input_system,
+                physics_pause_system.run_if(state_changed::<AppState>()),
+            ))
+            .add_systems(Update, (
+                game_clock_system,
+                kill_score_system,
+                objective_system,
+                game_over_system,
+                wave_director_system,
+            ).chain().run_if(in_state(AppState::Playing)));
+    }
+}
+
+/// Enter starts a run from the menu and returns to it after a game over; Escape pauses.
+pub fn game_state_input_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let next = match state.get() {
+        AppState::MainMenu if keyboard_input.just_pressed(START_KEY) => AppState::Playing,
+        AppState::Playing if keyboard_input.just_pressed(PAUSE_KEY) => AppState::Paused,
+        AppState::Paused if keyboard_input.just_pressed(PAUSE_KEY) => AppState::Playing,
+        AppState::GameOver if keyboard_input.just_pressed(START_KEY) => AppState::MainMenu,
+        _ => return,
+    };
+    next_state.set(next);
+}
+
+/// Resets score, clock, waves and objectives for a new run, clears out the last run's
+/// waves and brings the hero back if it was lost.
+#[allow(clippy::too_many_arguments)]
+pub fn start_run_system(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    mut director: ResMut<WaveDirector>,
+    mut objectives: ResMut<ObjectiveTracker>,
+    wave_enemies: Query<Entity, With<WaveEnemy>>,
+    hero_query: Query<(), With<Hero>>,
+    map: Option<Res<MapDefinition>>,
+    heightmap: Option<Res<Heightmap>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    *game_state = GameState::default();
+    director.reset();
+    objectives.reset();
+    for enemy in wave_enemies.iter() {
+        commands.entity(enemy).despawn_recursive();
+    }
+
+    if let (true, Some(map), Some(heightmap)) = (hero_query.is_empty(), map, heightmap) {
+        for spawn in map.spawns.iter().filter(|spawn| spawn.side == SpawnSide::Player) {
+            let position = ground_position(&heightmap, spawn.position, 0.0);
+            let hero = spawn_hero_mech(&mut commands, &mut meshes, &mut materials, position, spawn.facing);
+            commands.entity(hero).insert(MapEntity);
+            if let Some(tag) = &spawn.tag {
+                commands.entity(hero).insert(MapTag(tag.clone()));
+            }
+        }
+    }
+}
+
+/// What a finished run leaves on the map: everything the map spawned, and the rubble of it.
+type MapLeftovers = Or<(With<MapEntity>, With<Rubble>)>;
+
+/// Clears away a finished run's map, rubble and shells still in flight, and builds the map
+/// afresh. The next run starts with its triggers armed, tagged targets standing and heroes
+/// undamaged.
+#[allow(clippy::too_many_arguments)]
+pub fn rebuild_map_system(
+    mut commands: Commands,
+    map_entities: Query<Entity, MapLeftovers>,
+    projectiles: Query<Entity, With<Projectile>>,
+    map: Option<Res<MapDefinition>>,
+    heightmap: Option<Res<Heightmap>>,
+    mut navigation: Option<ResMut<NavigationGrid>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for projectile in projectiles.iter() {
+        commands.add(Recycle(projectile));
+    }
+    let (Some(map), Some(heightmap)) = (map, heightmap) else {
+        return;
+    };
+    for entity in map_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    // Rubble left rough ground behind; the rebuilt structures mark their own footprints
+    if let Some(navigation) = navigation.as_deref_mut() {
+        *navigation = NavigationGrid::new(map.size(), navigation.cell_size());
+    }
+    spawn_map(&mut commands, &mut meshes, &mut materials, &heightmap, navigation.as_deref_mut(), &map);
+}
+
+/// Freezes the physics simulation outside of play.
+pub fn physics_pause_system(
+    state: Res<State<AppState>>,
+    rapier_config: Option<ResMut<RapierConfiguration>>,
+) {
+    if let Some(mut rapier_config) = rapier_config {
+        rapier_config.physics_pipeline_active = *state.get() == AppState::Playing;
+    }
+}
+
+pub fn game_clock_system(time: Res<Time>, mut game_state: ResMut<GameState>) {
+    game_state.game_time += time.delta_seconds();
+}
+
+pub fn kill_score_system(mut destroyed_events: EventReader<EnemyDestroyedEvent>, mut game_state: ResMut<GameState>) {
+    game_state.score += KILL_SCORE * destroyed_events.read().count() as u32;
+}
+
+/// Ends the run in defeat once no hero is left or an objective fails, or in victory once
+/// the final wave is in, every enemy is destroyed and the mission is complete.
+pub fn game_over_system(
+    hero_query: Query<(), With<Hero>>,
+    enemy_query: Query<(), With<Enemy>>,
+    director: Res<WaveDirector>,
+    objectives: Res<ObjectiveTracker>,
+    mut game_state: ResMut<GameState>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let outcome = if hero_query.is_empty() || objectives.has_failed() {
+        GameOutcome::Defeat
+    } else if director.is_finished() && enemy_query.is_empty() && objectives.is_complete() {
+        GameOutcome::Victory
+    } else {
+        return;
+    };
+    game_state.outcome = Some(outcome);
+    next_state.set(AppState::GameOver);
+    info!("Game over: {:?} with {} points after {:.0}s", outcome, game_state.score, game_state.game_time);
+}
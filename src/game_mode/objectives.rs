@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use crate::components::{Enemy, Health, Hero};
+use crate::map::{MapTag, MapTriggerEvent, ObjectiveDefinition, ObjectiveKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectiveStatus {
+    Pending, // Waiting on the objectives in its `after` list
+    Active,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct Objective {
+    pub definition: ObjectiveDefinition,
+    pub status: ObjectiveStatus,
+    pub elapsed: f32, // Seconds spent active, for timed objectives
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ObjectiveCompletedEvent {
+    pub id: String,
+}
+
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ObjectiveFailedEvent {
+    pub id: String,
+}
+
+/// The mission: every objective of the map and how far along each is. The mission is
+/// complete once all objectives are, and failed as soon as any one fails. Triggers are
+/// remembered once fired, so one reached before its objective starts still counts.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ObjectiveTracker {
+    objectives: Vec<Objective>,
+    fired_triggers: HashSet<String>,
+}
+
+impl ObjectiveTracker {
+    pub fn new(definitions: Vec<ObjectiveDefinition>) -> Self {
+        let mut tracker = Self {
+            objectives: definitions.into_iter().map(|definition| Objective {
+                definition,
+                status: ObjectiveStatus::Pending,
+                elapsed: 0.0,
+            }).collect(),
+            fired_triggers: HashSet::new(),
+        };
+        tracker.activate_ready();
+        tracker
+    }
+
+    pub fn objectives(&self) -> &[Objective] {
+        &self.objectives
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Objective> {
+        self.objectives.iter().find(|objective| objective.definition.id == id)
+    }
+
+    pub fn status(&self, id: &str) -> Option<ObjectiveStatus> {
+        self.get(id).map(|objective| objective.status)
+    }
+
+    pub fn active(&self) -> impl Iterator<Item = &Objective> {
+        self.objectives.iter().filter(|objective| objective.status == ObjectiveStatus::Active)
+    }
+
+    /// Every objective is completed. A map without objectives has nothing left to do.
+    pub fn is_complete(&self) -> bool {
+        self.objectives.iter().all(|objective| objective.status == ObjectiveStatus::Completed)
+    }
+
+    pub fn has_failed(&self) -> bool {
+        self.objectives.iter().any(|objective| objective.status == ObjectiveStatus::Failed)
+    }
+
+    /// Marks an active objective completed and starts whatever was waiting on it.
+    /// Returns whether the objective was active.
+    pub fn complete(&mut self, id: &str) -> bool {
+        let changed = self.finish(id, ObjectiveStatus::Completed);
+        if changed {
+            self.activate_ready();
+        }
+        changed
+    }
+
+    /// Marks an active objective failed. Returns whether the objective was active.
+    pub fn fail(&mut self, id: &str) -> bool {
+        self.finish(id, ObjectiveStatus::Failed)
+    }
+
+    /// Puts every objective back to where the mission starts.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.objectives.drain(..).map(|objective| objective.definition).collect());
+    }
+
+    fn finish(&mut self, id: &str, status: ObjectiveStatus) -> bool {
+        match self.objectives.iter_mut().find(|objective| objective.definition.id == id) {
+            Some(objective) if objective.status == ObjectiveStatus::Active => {
+                objective.status = status;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn activate_ready(&mut self) {
+        let completed: HashSet<String> = self.objectives.iter()
+            .filter(|objective| objective.status == ObjectiveStatus::Completed)
+            .map(|objective| objective.definition.id.clone())
+            .collect();
+        for objective in self.objectives.iter_mut().filter(|objective| objective.status == ObjectiveStatus::Pending) {
+            if objective.definition.after.iter().all(|id| completed.contains(id)) {
+                objective.status = ObjectiveStatus::Active;
+            }
+        }
+    }
+}
+
+fn ground(translation: Vec3) -> Vec2 {
+    Vec2::new(translation.x, translation.z)
+}
+
+/// Checks each active objective against the world and reports the ones that
+/// complete or fail this frame.
+#[allow(clippy::too_many_arguments)]
+pub fn objective_system(
+    time: Res<Time>,
+    mut tracker: ResMut<ObjectiveTracker>,
+    mut trigger_events: EventReader<MapTriggerEvent>,
+    hero_query: Query<&Transform, With<Hero>>,
+    enemy_query: Query<(), With<Enemy>>,
+    tagged_query: Query<(&MapTag, &Transform, Option<&Health>)>,
+    mut completed_events: EventWriter<ObjectiveCompletedEvent>,
+    mut failed_events: EventWriter<ObjectiveFailedEvent>,
+) {
+    let tracker = &mut *tracker;
+    tracker.fired_triggers.extend(trigger_events.read().map(|event| event.id.clone()));
+    let delta = time.delta_seconds();
+    // Tagged entities still standing; anything at zero health is as good as destroyed
+    let find_tagged = |tag: &str| {
+        tagged_query.iter()
+            .find(|(map_tag, _, health)| map_tag.0 == tag && health.is_none_or(|health| health.current > 0.0))
+            .map(|(_, transform, _)| ground(transform.translation))
+    };
+    let hero_within = |center: Vec2, radius: f32| {
+        hero_query.iter().any(|transform| ground(transform.translation).distance(center) <= radius)
+    };
+
+    let mut outcomes = Vec::new();
+    for objective in tracker.objectives.iter_mut().filter(|objective| objective.status == ObjectiveStatus::Active) {
+        objective.elapsed += delta;
+        let outcome = match &objective.definition.kind {
+            ObjectiveKind::DestroyAllEnemies => enemy_query.is_empty().then_some(ObjectiveStatus::Completed),
+            ObjectiveKind::DestroyTarget { tag } => find_tagged(tag).is_none().then_some(ObjectiveStatus::Completed),
+            ObjectiveKind::Survive { seconds } => (objective.elapsed >= *seconds).then_some(ObjectiveStatus::Completed),
+            ObjectiveKind::ReachZone { center, radius } => {
+                hero_within(Vec2::new(center.0, center.1), *radius).then_some(ObjectiveStatus::Completed)
+            }
+            ObjectiveKind::ReachTrigger { trigger } => {
+                tracker.fired_triggers.contains(trigger).then_some(ObjectiveStatus::Completed)
+            }
+            ObjectiveKind::Defend { tag, seconds } => match find_tagged(tag) {
+                None => Some(ObjectiveStatus::Failed),
+                Some(_) => (objective.elapsed >= *seconds).then_some(ObjectiveStatus::Completed),
+            },
+            ObjectiveKind::Escort { tag, center, radius } => match find_tagged(tag) {
+                None => Some(ObjectiveStatus::Failed),
+                Some(position) => {
+                    (position.distance(Vec2::new(center.0, center.1)) <= *radius).then_some(ObjectiveStatus::Completed)
+                }
+            },
+        };
+        if let Some(outcome) = outcome {
+            outcomes.push((objective.definition.id.clone(), outcome));
+        }
+    }
+
+    for (id, outcome) in outcomes {
+        if outcome == ObjectiveStatus::Completed && tracker.complete(&id) {
+            info!("Objective completed: {}", id);
+            completed_events.send(ObjectiveCompletedEvent { id });
+        } else if outcome == ObjectiveStatus::Failed && tracker.fail(&id) {
+            info!("Objective failed: {}", id);
+            failed_events.send(ObjectiveFailedEvent { id });
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+use crate::components::Health;
+use crate::map::spawn_enemy_target;
+use crate::systems::terrain::Heightmap;
+
+pub const WAVE_COUNT: usize = 5;
+/// Seconds before the first wave arrives, then between waves.
+pub const FIRST_WAVE_DELAY: f32 = 5.0;
+pub const WAVE_INTERVAL: f32 = 30.0;
+pub const BASE_WAVE_SIZE: u32 = 2;
+/// Extra enemies each later wave brings.
+pub const WAVE_SIZE_GROWTH: u32 = 1;
+pub const BASE_ENEMY_HEALTH: f32 = 100.0;
+/// Extra health per wave, as a fraction of the base.
+pub const WAVE_HEALTH_GROWTH: f32 = 0.25;
+/// Radius of the ring a wave's enemies are spread over around their spawn point.
+const WAVE_SPAWN_SPREAD: f32 = 2.5;
+/// Enemy centres sit this far above the ground.
+const ENEMY_CLEARANCE: f32 = 0.75;
+
+/// Enemy brought in by the wave director, cleared away when a new run starts.
+#[derive(Component)]
+pub struct WaveEnemy;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveDefinition {
+    pub delay: f32, // Seconds after the previous wave, or after the start for the first
+    pub enemies: u32,
+    pub enemy_health: f32,
+}
+
+/// Schedule of enemy waves and the spawn points they come in from.
+#[derive(Resource, Debug, Clone)]
+pub struct WaveDirector {
+    waves: Vec<WaveDefinition>,
+    spawn_points: Vec<Vec2>,
+    spawned: usize,
+    countdown: f32,
+}
+
+impl WaveDirector {
+    pub fn new(waves: Vec<WaveDefinition>, spawn_points: Vec<Vec2>) -> Self {
+        let countdown = waves.first().map_or(0.0, |wave| wave.delay);
+        Self {
+            waves,
+            spawn_points,
+            spawned: 0,
+            countdown,
+        }
+    }
+
+    /// `count` waves, each larger and tougher than the one before.
+    pub fn escalating(count: usize, spawn_points: Vec<Vec2>) -> Self {
+        let waves = (0..count).map(|index| WaveDefinition {
+            delay: if index == 0 { FIRST_WAVE_DELAY } else { WAVE_INTERVAL },
+            enemies: BASE_WAVE_SIZE + WAVE_SIZE_GROWTH * index as u32,
+            enemy_health: BASE_ENEMY_HEALTH * (1.0 + WAVE_HEALTH_GROWTH * index as f32),
+        }).collect();
+        Self::new(waves, spawn_points)
+    }
+
+    pub fn waves(&self) -> &[WaveDefinition] {
+        &self.waves
+    }
+
+    pub fn waves_spawned(&self) -> usize {
+        self.spawned
+    }
+
+    /// Every wave has been sent in.
+    pub fn is_finished(&self) -> bool {
+        self.spawned >= self.waves.len()
+    }
+
+    pub fn time_to_next_wave(&self) -> Option<f32> {
+        (!self.is_finished()).then_some(self.countdown.max(0.0))
+    }
+
+    /// Rewinds the schedule for a new run.
+    pub fn reset(&mut self) {
+        *self = Self::new(std::mem::take(&mut self.waves), std::mem::take(&mut self.spawn_points));
+    }
+
+    /// Advances the schedule by `delta` seconds and returns the index of the wave due now, if any.
+    pub fn tick(&mut self, delta: f32) -> Option<usize> {
+        if self.is_finished() {
+            return None;
+        }
+        self.countdown -= delta;
+        if self.countdown > 0.0 {
+            return None;
+        }
+        let wave = self.spawned;
+        self.spawned += 1;
+        // Carry the overshoot so the schedule doesn't drift with the frame rate
+        self.countdown += self.waves.get(self.spawned).map_or(0.0, |next| next.delay);
+        Some(wave)
+    }
+
+    /// Where the enemies of `wave` appear: dealt out to the spawn points in turn and
+    /// spread on a ring around each so they don't overlap.
+    pub fn spawn_positions(&self, wave: usize) -> Vec<Vec2> {
+        let Some(definition) = self.waves.get(wave) else {
+            return Vec::new();
+        };
+        if self.spawn_points.is_empty() {
+            return Vec::new();
+        }
+        let enemies = definition.enemies as usize;
+        (0..enemies).map(|index| {
+            let point = self.spawn_points[index % self.spawn_points.len()];
+            point + Vec2::from_angle(TAU * index as f32 / enemies as f32) * WAVE_SPAWN_SPREAD
+        }).collect()
+    }
+}
+
+/// Sends in each wave when it is due.
+pub fn wave_director_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut director: ResMut<WaveDirector>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    heightmap: Option<Res<Heightmap>>,
+) {
+    let Some(wave) = director.tick(time.delta_seconds()) else {
+        return;
+    };
+    let positions = director.spawn_positions(wave);
+    if positions.is_empty() {
+        warn!("Wave {} has nowhere to spawn; the map needs an enemy spawn point", wave + 1);
+    }
+
+    let health = director.waves()[wave].enemy_health;
+    for position in &positions {
+        let ground = heightmap.as_deref().map_or(0.0, |heightmap| heightmap.height_at(*position));
+        let enemy = spawn_enemy_target(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            Vec3::new(position.x, ground + ENEMY_CLEARANCE, position.y),
+        );
+        commands.entity(enemy).insert((Health::new(health), WaveEnemy));
+    }
+    info!("Wave {} of {}: {} enemies", wave + 1, director.waves().len(), positions.len());
+}
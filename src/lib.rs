@@ -1,6 +1,9 @@
 pub mod camera;
 pub mod components;
+pub mod game_mode;
+pub mod map;
 pub mod mech;
 pub mod rendering;
 pub mod resources;
-pub mod systems;
\ No newline at end of file
+pub mod systems;
+pub mod ui;
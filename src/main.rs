@@ -3,17 +3,34 @@ use bevy_rapier3d::prelude::*;
 
 mod camera;
 mod components;
+mod game_mode;
+mod map;
 mod mech;
 mod rendering;
 mod resources;
 mod systems;
+mod ui;
 
-use components::*;
-use mech::*;
+use game_mode::AppState;
 use resources::*;
 use systems::*;
 
 fn main() {
+    // Designers point the game at a map with `--map <path>` to iterate without recompiling
+    let map_path = map::map_path_from_args(std::env::args().skip(1));
+    let map = match map::MapDefinition::load(&map_path) {
+        Ok(map) => map,
+        Err(error) => {
+            eprintln!("Failed to load map {}: {}", map_path, error);
+            std::process::exit(1);
+        }
+    };
+    let waves = game_mode::WaveDirector::escalating(
+        game_mode::WAVE_COUNT,
+        map.spawn_positions(map::SpawnSide::Enemy),
+    );
+    let objectives = game_mode::ObjectiveTracker::new(map.objectives.clone());
+
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
@@ -24,21 +41,58 @@ fn main() {
             ..default()
         }))
         .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
+        .add_plugins(camera::CameraControllerPlugin)
+        .add_plugins(camera::CameraShakePlugin)
+        .add_plugins(ui::MinimapPlugin)
+        .add_plugins(ui::HealthBarPlugin)
+        .add_plugins(ui::HeatGaugePlugin)
+        .add_plugins(ui::UnitInfoPanelPlugin)
+        .add_plugins(ui::GameHudPlugin)
+        .add_plugins(map::MapPlugin { map })
+        .add_plugins(game_mode::GameModePlugin { waves, objectives })
         // .add_plugins(RapierDebugRenderPlugin::default())
-        .init_resource::<GameState>()
         .init_resource::<MouseWorldPosition>()
-        .add_systems(Startup, setup)
+        .init_resource::<ControlScheme>()
+        .init_resource::<PickingState>()
+        .init_resource::<ProjectileAssets>()
+        .init_resource::<ProjectilePool>()
+        .init_resource::<RubbleAssets>()
+        .init_resource::<SelectionRingAssets>()
+        .init_resource::<SpatialIndex>()
+        .add_event::<PartDestroyedEvent>()
+        .add_event::<ExplosionEvent>()
+        .add_systems(Startup, (setup, spawn_fog_overlay_system))
+        .add_systems(PreUpdate, spatial_index_system)
+        .add_systems(Update, (
+            cursor_ray_system,
+            entity_picking_system,
+            hover_highlight_system,
+            entity_inspect_system,
+            ui::pointer_over_ui_system,
+        ).chain().before(control_scheme_toggle_system))
+        .add_systems(Update, (
+            control_scheme_toggle_system,
+            (
+                click_to_move_system,
+                enemy_selection_system,
+            ).run_if(resource_equals(ControlScheme::Rts)),
+            (
+                gamepad_drive_system,
+                gamepad_turret_aim_system.after(upper_body_control_system),
+                gamepad_fire_system,
+            ).run_if(resource_equals(ControlScheme::Gamepad)),
+        ).after(mouse_position_system).run_if(in_state(AppState::Playing)))
         .add_systems(Update, (
             mouse_position_system,
-            click_to_move_system,
-            enemy_selection_system,
             update_target_indicator_system,
             attack_move_system,
             propagate_attack_target_system,  // Propagate AttackTarget down hierarchy
-            turret_control_system,
-            // spawn_projectile_system, // Disabled - using auto_fire_system instead
-            auto_fire_system,
-            tank_movement_system,
+            upper_body_control_system,
+            weapon_elevation_system.after(upper_body_control_system),
+            traverse_hull_to_target_system,
+            weapon_cooldown_system.before(weapon_control_system),
+            weapon_control_system,
+            mech_movement_system,
             movement_system,
             rocket_acceleration_system,
             tank_shell_movement_system,  // Update tank shell positions
@@ -48,109 +102,50 @@ fn main() {
             hit_flash_system,
             fragment_lifetime_system,
             fragment_visual_fade_system,
-        ))
+            click_marker_lifetime_system,
+        ).run_if(in_state(AppState::Playing)))
+        .add_systems(Update, (
+            weapon_reload_system,
+            manual_reload_system,
+            cycle_ammo_type_system,
+        ).before(weapon_control_system).run_if(in_state(AppState::Playing)))
+        .add_systems(Update, (
+            mech_part_collision_system,
+            fragment_collision_system,
+            explosion_system,
+            mech_part_destruction_system,
+        ).chain().after(collision_detection_system).run_if(in_state(AppState::Playing)))
+        .add_systems(Update, (
+            structure_damage_stage_system,
+            structure_destruction_system,
+        ).chain().after(explosion_system).run_if(in_state(AppState::Playing)))
+        .add_systems(Update, (
+            sprint_input_system,
+            heat_system,
+        ).chain().before(weapon_control_system).before(mech_movement_system).run_if(in_state(AppState::Playing)))
+        .add_systems(Update, (
+            weapon_range_ring_system,
+            firing_arc_system,
+        ).run_if(in_state(AppState::Playing)))
+        .add_systems(Update, terrain_follow_system
+            .after(mech_movement_system)
+            .after(movement_system)
+            .run_if(in_state(AppState::Playing)))
+        .add_systems(Update, (
+            fog_of_war_system
+                .before(enemy_selection_system)
+                .before(weapon_control_system),
+            enemy_fog_visibility_system,
+            fog_overlay_system,
+        ).chain().run_if(in_state(AppState::Playing)))
         .run();
 }
 
-use crate::mech::{MechLowerPart, MechUpperPart, TurretRotation, TurretCannon, CannonBarrel};
-
 fn setup(
     mut commands: Commands,
     mut rapier_config: ResMut<RapierConfiguration>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     rapier_config.gravity = Vec3::ZERO;
     
     camera::setup_orthographic_camera(&mut commands);
-    
-    // Add a ground plane for reference
-    commands.spawn(PbrBundle {
-        mesh: meshes.add(shape::Plane::from_size(50.0).into()),
-        material: materials.add(Color::rgb(0.1, 0.2, 0.1).into()),
-        transform: Transform::from_xyz(0.0, -0.1, 0.0),
-        ..default()
-    });
-    
-    // Spawn hero mech with 3D shapes
-    let hero_entity = commands.spawn((
-        Hero,
-        TankMovement::default(),
-        SpatialBundle {
-            transform: Transform::from_xyz(-4.0, 0.0, 0.0),
-            ..default()
-        },
-    )).id();
-    
-    // Tank base (box shape)
-    let tank_base = commands.spawn((
-        MechLowerPart,
-        PbrBundle {
-            mesh: meshes.add(Mesh::from(shape::Box::new(1.5, 0.5, 2.0))),
-            material: materials.add(Color::rgb(0.3, 0.3, 0.3).into()),
-            transform: Transform::from_xyz(0.0, 0.25, 0.0),
-            ..default()
-        },
-        // Add physics components for the tank
-        RigidBody::Dynamic,
-        Collider::cuboid(0.75, 0.25, 1.0), // Half of box size
-        LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z, // Only allow Y rotation
-    )).id();
-    
-    // Turret base (cylinder)
-    let turret_base = commands.spawn((
-        MechUpperPart,
-        TurretRotation {
-            target_angle: 0.0,
-            current_angle: 0.0,
-        },
-        TurretCannon::default(),
-        PbrBundle {
-            mesh: meshes.add(Mesh::from(shape::Cylinder {
-                radius: 0.5,
-                height: 0.4,
-                resolution: 16,
-                segments: 1,
-            })),
-            material: materials.add(Color::rgb(0.2, 0.6, 1.0).into()),
-            transform: Transform::from_xyz(0.0, 0.5, 0.0),
-            ..default()
-        },
-    )).id();
-    
-    // Cannon barrel (box)
-    let cannon = commands.spawn((
-        CannonBarrel,
-        PbrBundle {
-            mesh: meshes.add(Mesh::from(shape::Box::new(0.2, 0.2, 1.0))),
-            material: materials.add(Color::rgb(0.6, 0.6, 0.6).into()),
-            transform: Transform::from_xyz(0.0, 0.0, 0.6),
-            ..default()
-        },
-    )).id();
-    
-    // Set up hierarchy
-    commands.entity(hero_entity).push_children(&[tank_base]);
-    commands.entity(tank_base).push_children(&[turret_base]);
-    commands.entity(turret_base).push_children(&[cannon]);
-    
-    // Spawn enemy
-    commands.spawn((
-        Enemy,
-        Health::new(100.0),
-        PbrBundle {
-            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.5 })),
-            material: materials.add(Color::rgb(1.0, 0.0, 0.0).into()),
-            transform: Transform::from_xyz(4.0, 0.75, 0.0),  // Positioned at proper height
-            ..default()
-        },
-        RigidBody::Dynamic,
-        Collider::cuboid(0.75, 0.75, 0.75),  // 3D cube collider
-        ColliderMassProperties::Density(5.0),  // Heavy enemy
-        LockedAxes::ROTATION_LOCKED | LockedAxes::TRANSLATION_LOCKED_Y,  // Keep upright and on ground
-        Damping { linear_damping: 2.0, angular_damping: 1.0 },  // Quick stop after impact
-        ExternalImpulse::default(),
-    ));
-    
-    info!("Spawned 3D mech and enemy");
 }
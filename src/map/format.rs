@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fmt;
+use crate::systems::terrain::Heightmap;
+
+pub const DEFAULT_MAP_PATH: &str = "assets/maps/skirmish.ron";
+
+/// Everything placed in the world at startup, read from a RON map file.
+/// Positions are `(x, z)` on the ground; heights come from the terrain.
+#[derive(Resource, Deserialize, Debug, Clone, PartialEq)]
+pub struct MapDefinition {
+    pub name: String,
+    pub size: (f32, f32),
+    #[serde(default)]
+    pub terrain: TerrainDefinition,
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleDefinition>,
+    pub spawns: Vec<SpawnPoint>,
+    #[serde(default)]
+    pub objectives: Vec<ObjectiveDefinition>,
+    #[serde(default)]
+    pub triggers: Vec<TriggerDefinition>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum TerrainDefinition {
+    #[default]
+    Flat,
+    /// Grayscale PNG, black at zero and white at `max_height`.
+    Heightmap { path: String, max_height: f32 },
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObstacleKind {
+    Wall,
+    /// Loose box that shots and blasts can shove around.
+    Crate,
+    Building,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ObstacleDefinition {
+    pub kind: ObstacleKind,
+    pub position: (f32, f32),
+    pub size: (f32, f32, f32), // Width, height, depth
+    #[serde(default)]
+    pub rotation: f32, // Degrees about Y
+    /// Makes the obstacle destructible with this much health.
+    #[serde(default)]
+    pub health: Option<f32>,
+    /// Name objectives refer to this obstacle by.
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnSide {
+    Player,
+    Enemy,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SpawnPoint {
+    pub side: SpawnSide,
+    pub position: (f32, f32),
+    #[serde(default)]
+    pub facing: f32, // Degrees about Y, 0 faces +Z
+    /// Name objectives refer to this unit by.
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+/// What an objective asks of the player. `tag` names a spawn or obstacle in the same map.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum ObjectiveKind {
+    /// No enemy left on the field.
+    DestroyAllEnemies,
+    DestroyTarget { tag: String },
+    Survive { seconds: f32 },
+    /// A hero gets within `radius` of `center`.
+    ReachZone { center: (f32, f32), radius: f32 },
+    /// A hero enters the trigger volume with this id.
+    ReachTrigger { trigger: String },
+    /// The tagged entity lasts `seconds`; fails if it is destroyed first.
+    Defend { tag: String, seconds: f32 },
+    /// The tagged entity gets within `radius` of `center`; fails if it is destroyed first.
+    Escort { tag: String, center: (f32, f32), radius: f32 },
+}
+
+/// One step of the map's mission. Objectives start active unless they name others in
+/// `after`, in which case they activate once all of those are completed.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ObjectiveDefinition {
+    pub id: String,
+    pub description: String,
+    pub kind: ObjectiveKind,
+    #[serde(default)]
+    pub after: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum TriggerAction {
+    SpawnEnemy { position: (f32, f32) },
+    Message(String),
+}
+
+/// Circle on the ground that runs `actions` when a hero enters it.
+/// Fires once unless `repeat` is set, in which case it fires on every entry.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct TriggerDefinition {
+    pub id: String,
+    pub center: (f32, f32),
+    pub radius: f32,
+    #[serde(default)]
+    pub repeat: bool,
+    pub actions: Vec<TriggerAction>,
+}
+
+#[derive(Debug)]
+pub enum MapLoadError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+}
+
+impl fmt::Display for MapLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapLoadError::Io(error) => write!(f, "could not read map: {}", error),
+            MapLoadError::Parse(error) => write!(f, "invalid map: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for MapLoadError {}
+
+impl MapDefinition {
+    /// Parses a map. Objectives that refer to tags, triggers or objectives the map
+    /// lacks are reported by [`MapDefinition::unresolved_references`].
+    pub fn parse(source: &str) -> Result<Self, MapLoadError> {
+        ron::from_str(source).map_err(MapLoadError::Parse)
+    }
+
+    pub fn load(path: &str) -> Result<Self, MapLoadError> {
+        let source = std::fs::read_to_string(path).map_err(MapLoadError::Io)?;
+        Self::parse(&source)
+    }
+
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.size.0, self.size.1)
+    }
+
+    /// The map's extent on the X/Z plane, centred on the origin.
+    pub fn bounds(&self) -> Rect {
+        Rect::from_center_size(Vec2::ZERO, self.size())
+    }
+
+    /// Objectives naming a tag no spawn or obstacle carries, or a trigger or objective the
+    /// map doesn't define. A missing target counts as destroyed, so those objectives complete
+    /// or fail at once, while a missing trigger never fires and a missing objective never
+    /// lets the ones after it start.
+    pub fn unresolved_references(&self) -> Vec<String> {
+        let tags: Vec<&String> = self.spawns.iter().filter_map(|spawn| spawn.tag.as_ref())
+            .chain(self.obstacles.iter().filter_map(|obstacle| obstacle.tag.as_ref()))
+            .collect();
+        let mut problems = Vec::new();
+        for objective in &self.objectives {
+            match &objective.kind {
+                ObjectiveKind::DestroyTarget { tag } | ObjectiveKind::Defend { tag, .. } | ObjectiveKind::Escort { tag, .. } => {
+                    if !tags.contains(&tag) {
+                        problems.push(format!("objective '{}' refers to unknown tag '{}'", objective.id, tag));
+                    }
+                }
+                ObjectiveKind::ReachTrigger { trigger } => {
+                    if !self.triggers.iter().any(|definition| &definition.id == trigger) {
+                        problems.push(format!("objective '{}' waits on unknown trigger '{}'", objective.id, trigger));
+                    }
+                }
+                ObjectiveKind::DestroyAllEnemies | ObjectiveKind::Survive { .. } | ObjectiveKind::ReachZone { .. } => {}
+            }
+            for id in objective.after.iter().filter(|id| !self.objectives.iter().any(|other| &other.id == *id)) {
+                problems.push(format!("objective '{}' waits on unknown objective '{}'", objective.id, id));
+            }
+        }
+        problems
+    }
+
+    /// Ground positions of every spawn point for `side`.
+    pub fn spawn_positions(&self, side: SpawnSide) -> Vec<Vec2> {
+        self.spawns.iter()
+            .filter(|spawn| spawn.side == side)
+            .map(|spawn| Vec2::new(spawn.position.0, spawn.position.1))
+            .collect()
+    }
+
+    pub fn heightmap(&self) -> Heightmap {
+        match &self.terrain {
+            TerrainDefinition::Flat => Heightmap::flat(self.size()),
+            TerrainDefinition::Heightmap { path, max_height } => Heightmap::load(path, self.size(), *max_height),
+        }
+    }
+}
+
+/// Map file named by `--map <path>` or `--map=<path>`, or the default skirmish map.
+pub fn map_path_from_args(args: impl IntoIterator<Item = String>) -> String {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--map" {
+            if let Some(path) = args.next() {
+                return path;
+            }
+        } else if let Some(path) = arg.strip_prefix("--map=") {
+            return path.to_string();
+        }
+    }
+    DEFAULT_MAP_PATH.to_string()
+}
@@ -0,0 +1,359 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use crate::camera::CameraController;
+use crate::components::{Armor, Destructible, Enemy, GroundClearance, Health, Hero, VisionRange, Wall};
+use crate::mech::*;
+use crate::systems::armor::{ENEMY_ARMOR, LOWER_BODY_ARMOR, UPPER_BODY_ARMOR, WEAPON_ARMOR};
+use crate::systems::fog_of_war::{ENEMY_VISION_RANGE, HERO_VISION_RANGE};
+use crate::systems::navigation::{NavCell, NavigationGrid};
+use crate::systems::terrain::{spawn_terrain, Heightmap, UNIT_COLLISION_GROUPS};
+use crate::ui::minimap::Minimap;
+use super::format::*;
+
+/// Sent when a hero enters a map trigger, after its actions have run.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct MapTriggerEvent {
+    pub id: String,
+}
+
+/// Runtime state of a map trigger. `occupied` tracks whether a hero was inside last frame.
+#[derive(Component, Debug)]
+pub struct TriggerVolume {
+    pub definition: TriggerDefinition,
+    pub occupied: bool,
+    pub fired: bool,
+}
+
+/// Marks everything spawned from the map, so a new run can clear it away and rebuild.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapEntity;
+
+/// Name given to a spawned unit or obstacle by the map, for objectives to refer to.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct MapTag(pub String);
+
+/// Point `clearance` above the terrain at map position `(x, z)`.
+pub fn ground_position(heightmap: &Heightmap, (x, z): (f32, f32), clearance: f32) -> Vec3 {
+    Vec3::new(x, heightmap.height_at(Vec2::new(x, z)) + clearance, z)
+}
+
+/// Spawns the hero mech: the root drives, the upper body aims and the weapon fires.
+pub fn spawn_hero_mech(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    position: Vec3,
+    facing: f32,
+) -> Entity {
+    let lower_body = create_tank_treads_lower();
+    let mut upper_body = create_turret_upper();
+    let cannon = CannonWeapon::new(1.5, 25.0, 10.0, 15.0, 0.5);
+    let hardpoint = upper_body.hardpoints[0].clone();
+
+    let hero_entity = commands.spawn((
+        Hero,
+        Mech::new("Hero"),
+        MechMovement::default(),
+        Heat::default(),
+        lower_body, // Drive stats live on the root, which is what mech_movement_system moves
+        GroundClearance(0.0),
+        VisionRange(HERO_VISION_RANGE),
+        SpatialBundle {
+            transform: Transform::from_translation(position)
+                .with_rotation(Quat::from_rotation_y(facing.to_radians())),
+            ..default()
+        },
+    )).id();
+
+    // Tank base (box shape)
+    let tank_base = commands.spawn((
+        MechLowerPart,
+        PartHealth::lower(),
+        Armor::new(LOWER_BODY_ARMOR),
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(1.5, 0.5, 2.0))),
+            material: materials.add(Color::rgb(0.3, 0.3, 0.3).into()),
+            transform: Transform::from_xyz(0.0, 0.25, 0.0),
+            ..default()
+        },
+        // Add physics components for the tank
+        RigidBody::Dynamic,
+        Collider::cuboid(0.75, 0.25, 1.0), // Half of box size
+        UNIT_COLLISION_GROUPS,
+        LockedAxes::ROTATION_LOCKED_X | LockedAxes::ROTATION_LOCKED_Z, // Only allow Y rotation
+    )).id();
+
+    // Cannon barrel (box) mounted on the main hardpoint
+    let weapon = commands.spawn((
+        CannonBarrel,
+        MechWeapon {
+            weapon_stats: cannon.weapon_stats.clone(),
+            hardpoint_id: hardpoint.id,
+            last_fire_time: cannon.weapon_stats.fire_rate, // Ready to fire
+        },
+        WeaponAmmo::from_stats(&cannon.weapon_stats),
+        cannon,
+        PartHealth::weapon(),
+        Armor::new(WEAPON_ARMOR),
+        // Upper and weapon hitboxes are sensors so they register hits without
+        // shoving the shells they fire
+        Collider::cuboid(0.1, 0.1, 0.5),
+        Sensor,
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(0.2, 0.2, 1.0))),
+            material: materials.add(Color::rgb(0.6, 0.6, 0.6).into()),
+            transform: Transform::from_translation(hardpoint.offset + Vec3::new(0.0, 0.0, 0.1)),
+            ..default()
+        },
+    )).id();
+
+    upper_body.hardpoints[0].occupied_by = Some(weapon);
+
+    // Turret base (cylinder)
+    let turret_base = commands.spawn((
+        MechUpperPart,
+        upper_body,
+        PartHealth::upper(),
+        Armor::new(UPPER_BODY_ARMOR),
+        Collider::cylinder(0.2, 0.5),
+        Sensor,
+        MechRotation {
+            target_angle: 0.0,
+            current_angle: 0.0,
+        },
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cylinder {
+                radius: 0.5,
+                height: 0.4,
+                resolution: 16,
+                segments: 1,
+            })),
+            material: materials.add(Color::rgb(0.2, 0.6, 1.0).into()),
+            transform: Transform::from_xyz(0.0, 0.75, 0.0),
+            ..default()
+        },
+    )).id();
+
+    // Set up hierarchy: lower and upper both hang off the root so the upper turns independently
+    commands.entity(hero_entity).push_children(&[tank_base, turret_base]);
+    commands.entity(turret_base).push_children(&[weapon]);
+    commands.entity(hero_entity).insert(MechHierarchy {
+        lower: Some(tank_base),
+        upper: Some(turret_base),
+        weapons: vec![weapon],
+    });
+    hero_entity
+}
+
+/// Spawns the stationary enemy target block. `position` is its centre, half its height above the ground.
+pub fn spawn_enemy_target(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    position: Vec3,
+) -> Entity {
+    commands.spawn((
+        Enemy,
+        Health::new(100.0),
+        Armor::new(ENEMY_ARMOR),
+        GroundClearance(0.75),
+        VisionRange(ENEMY_VISION_RANGE),
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.5 })),
+            material: materials.add(Color::rgb(1.0, 0.0, 0.0).into()),
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        RigidBody::Dynamic,
+        Collider::cuboid(0.75, 0.75, 0.75),  // 3D cube collider
+        UNIT_COLLISION_GROUPS,
+        ColliderMassProperties::Density(5.0),  // Heavy enemy
+        LockedAxes::ROTATION_LOCKED | LockedAxes::TRANSLATION_LOCKED_Y,  // Keep upright and on ground
+        Damping { linear_damping: 2.0, angular_damping: 1.0 },  // Quick stop after impact
+        ExternalImpulse::default(),
+    )).id()
+}
+
+/// Spawns a map obstacle. Walls and buildings are fixed and block `navigation`; crates are
+/// heavy loose boxes. Any of them with `health` set takes damage and collapses into rubble.
+pub fn spawn_obstacle(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    heightmap: &Heightmap,
+    navigation: Option<&mut NavigationGrid>,
+    obstacle: &ObstacleDefinition,
+) -> Entity {
+    let (width, height, depth) = obstacle.size;
+    let half_extents = Vec3::new(width, height, depth) / 2.0;
+    let color = match obstacle.kind {
+        ObstacleKind::Wall => Color::rgb(0.4, 0.35, 0.3),
+        ObstacleKind::Crate => Color::rgb(0.55, 0.4, 0.2),
+        ObstacleKind::Building => Color::rgb(0.5, 0.5, 0.55),
+    };
+    let transform = Transform::from_translation(ground_position(heightmap, obstacle.position, half_extents.y))
+        .with_rotation(Quat::from_rotation_y(obstacle.rotation.to_radians()));
+
+    let mut entity = commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(width, height, depth))),
+            material: materials.add(color.into()),
+            transform,
+            ..default()
+        },
+        Collider::cuboid(half_extents.x, half_extents.y, half_extents.z),
+    ));
+
+    match obstacle.kind {
+        // Solid blocks that shelter whatever is behind them from blasts
+        ObstacleKind::Wall | ObstacleKind::Building => {
+            entity.insert((Wall, RigidBody::Fixed));
+            if let Some(navigation) = navigation {
+                navigation.mark_footprint(&transform, half_extents.xz(), NavCell::Blocked);
+            }
+        }
+        ObstacleKind::Crate => {
+            entity.insert((
+                RigidBody::Dynamic,
+                UNIT_COLLISION_GROUPS,
+                ColliderMassProperties::Density(3.0),
+                LockedAxes::ROTATION_LOCKED | LockedAxes::TRANSLATION_LOCKED_Y,
+                Damping { linear_damping: 2.0, angular_damping: 1.0 },
+                GroundClearance(half_extents.y),
+            ));
+        }
+    }
+
+    if let Some(health) = obstacle.health {
+        entity.insert((
+            Health::new(health),
+            Destructible {
+                half_extents,
+                base_color: color,
+                stage: 0,
+            },
+            ExternalImpulse::default(),
+        ));
+    }
+    entity.id()
+}
+
+/// Instantiates a map: terrain, obstacles, units at their spawn points and trigger volumes,
+/// each marked as a `MapEntity`.
+pub fn spawn_map(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    heightmap: &Heightmap,
+    mut navigation: Option<&mut NavigationGrid>,
+    map: &MapDefinition,
+) {
+    let terrain = spawn_terrain(commands, meshes, materials, heightmap);
+    commands.entity(terrain).insert(MapEntity);
+
+    for obstacle in &map.obstacles {
+        let entity = spawn_obstacle(commands, meshes, materials, heightmap, navigation.as_deref_mut(), obstacle);
+        commands.entity(entity).insert(MapEntity);
+        if let Some(tag) = &obstacle.tag {
+            commands.entity(entity).insert(MapTag(tag.clone()));
+        }
+    }
+
+    for spawn in &map.spawns {
+        let entity = match spawn.side {
+            SpawnSide::Player => {
+                spawn_hero_mech(commands, meshes, materials, ground_position(heightmap, spawn.position, 0.0), spawn.facing)
+            }
+            SpawnSide::Enemy => {
+                spawn_enemy_target(commands, meshes, materials, ground_position(heightmap, spawn.position, 0.75))
+            }
+        };
+        commands.entity(entity).insert(MapEntity);
+        if let Some(tag) = &spawn.tag {
+            commands.entity(entity).insert(MapTag(tag.clone()));
+        }
+    }
+
+    for trigger in &map.triggers {
+        commands.spawn((
+            MapEntity,
+            TriggerVolume {
+                definition: trigger.clone(),
+                occupied: false,
+                fired: false,
+            },
+            TransformBundle::from_transform(Transform::from_translation(ground_position(heightmap, trigger.center, 0.0))),
+        ));
+    }
+
+    info!("Loaded map '{}': {} obstacles, {} spawns, {} objectives, {} triggers",
+          map.name, map.obstacles.len(), map.spawns.len(), map.objectives.len(), map.triggers.len());
+}
+
+pub fn spawn_map_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    heightmap: Res<Heightmap>,
+    mut navigation: Option<ResMut<NavigationGrid>>,
+    map: Res<MapDefinition>,
+) {
+    spawn_map(&mut commands, &mut meshes, &mut materials, &heightmap, navigation.as_deref_mut(), &map);
+}
+
+/// Warns about objectives the map can't satisfy. Runs at startup, once logging is set up.
+pub fn report_unresolved_references_system(map: Res<MapDefinition>) {
+    for problem in map.unresolved_references() {
+        warn!("Map '{}': {}", map.name, problem);
+    }
+}
+
+/// Fits camera panning and the minimap view to the map as they are created.
+pub fn map_bounds_system(
+    map: Res<MapDefinition>,
+    mut cameras: Query<&mut CameraController, Added<CameraController>>,
+    mut minimaps: Query<&mut Minimap, Added<Minimap>>,
+) {
+    for mut controller in cameras.iter_mut() {
+        controller.bounds = map.bounds();
+        controller.clamp_focus();
+    }
+    for mut minimap in minimaps.iter_mut() {
+        minimap.world_bounds = map.bounds();
+    }
+}
+
+/// Runs a trigger's actions when a hero steps into it.
+pub fn trigger_volume_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    heightmap: Res<Heightmap>,
+    hero_query: Query<&Transform, With<Hero>>,
+    mut trigger_query: Query<(&Transform, &mut TriggerVolume), Without<Hero>>,
+    mut trigger_events: EventWriter<MapTriggerEvent>,
+) {
+    for (transform, mut volume) in trigger_query.iter_mut() {
+        let center = Vec2::new(transform.translation.x, transform.translation.z);
+        let occupied = hero_query.iter().any(|hero| {
+            Vec2::new(hero.translation.x, hero.translation.z).distance(center) <= volume.definition.radius
+        });
+        let entered = occupied && !volume.occupied;
+        volume.occupied = occupied;
+        if !entered || (volume.fired && !volume.definition.repeat) {
+            continue;
+        }
+
+        volume.fired = true;
+        for action in &volume.definition.actions {
+            match action {
+                TriggerAction::SpawnEnemy { position } => {
+                    let enemy = spawn_enemy_target(&mut commands, &mut meshes, &mut materials, ground_position(&heightmap, *position, 0.75));
+                    commands.entity(enemy).insert(MapEntity);
+                }
+                TriggerAction::Message(text) => info!("{}", text),
+            }
+        }
+        trigger_events.send(MapTriggerEvent { id: volume.definition.id.clone() });
+    }
+}
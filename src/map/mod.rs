@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+use crate::systems::fog_of_war::{FogOfWar, FOG_CELL_SIZE};
+use crate::systems::navigation::{NavigationGrid, NAV_CELL_SIZE};
+
+pub mod format;
+pub mod loader;
+
+pub use format::*;
+pub use loader::*;
+
+/// Builds the world from a map definition: terrain, obstacles, spawns and triggers,
+/// plus the navigation grid the obstacles are marked on and each side's fog of war.
+/// The camera and minimap are fitted to the map's size.
+pub struct MapPlugin {
+    pub map: MapDefinition,
+}
+
+impl Plugin for MapPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.map.heightmap())
+            .insert_resource(NavigationGrid::new(self.map.size(), NAV_CELL_SIZE))
+            .insert_resource(FogOfWar::new(self.map.size(), FOG_CELL_SIZE))
+            .insert_resource(self.map.clone())
+            .add_event::<MapTriggerEvent>()
+            .add_systems(Startup, (spawn_map_system, report_unresolved_references_system))
+            .add_systems(Update, (map_bounds_system, trigger_volume_system));
+    }
+}
@@ -10,6 +10,7 @@ pub struct MechLowerBody {
 pub struct MechUpperBody {
     pub rotation_capability: RotationCapability,
     pub hardpoints: Vec<Hardpoint>,
+    pub cooling_rating: f32, // Heat dissipated per second
 }
 
 #[derive(Component, Debug)]
@@ -37,6 +38,7 @@ pub struct MechMovement {
     pub movement_state: MechMovementState,
     pub target_rotation: f32,
     pub current_speed: f32,
+    pub sprinting: bool,
 }
 
 impl Default for MechMovement {
@@ -45,6 +47,7 @@ impl Default for MechMovement {
             movement_state: MechMovementState::Idle,
             target_rotation: 0.0,
             current_speed: 0.0,
+            sprinting: false,
         }
     }
 }
@@ -76,4 +79,46 @@ impl MechHierarchy {
     pub fn is_complete(&self) -> bool {
         self.has_lower() && self.has_upper()
     }
-}
\ No newline at end of file
+}
+
+pub const LOWER_BODY_HIT_POINTS: f32 = 120.0;
+pub const UPPER_BODY_HIT_POINTS: f32 = 100.0;
+pub const WEAPON_HIT_POINTS: f32 = 50.0;
+
+/// Where a part sits in a `MechHierarchy`, which decides what losing it costs the mech.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MechPartSlot {
+    Lower,  // Destroyed: the mech is immobilised
+    Upper,  // Destroyed: the mech is killed
+    Weapon, // Destroyed: its hardpoint is freed
+}
+
+/// Hit points of a single mech part. Hits are routed to the part actually struck.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct PartHealth {
+    pub slot: MechPartSlot,
+    pub current: f32,
+    pub max: f32,
+}
+
+impl PartHealth {
+    pub fn new(slot: MechPartSlot, max: f32) -> Self {
+        Self { slot, current: max, max }
+    }
+
+    pub fn lower() -> Self {
+        Self::new(MechPartSlot::Lower, LOWER_BODY_HIT_POINTS)
+    }
+
+    pub fn upper() -> Self {
+        Self::new(MechPartSlot::Upper, UPPER_BODY_HIT_POINTS)
+    }
+
+    pub fn weapon() -> Self {
+        Self::new(MechPartSlot::Weapon, WEAPON_HIT_POINTS)
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        self.current <= 0.0
+    }
+}
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+
+/// Fraction of capacity at which shots start to scatter.
+pub const HOT_FRACTION: f32 = 0.5;
+/// Fraction of capacity at which weapons are locked out.
+pub const LOCKOUT_FRACTION: f32 = 0.8;
+/// A shut down mech restarts once it has cooled to this fraction of capacity.
+pub const RESTART_FRACTION: f32 = 0.3;
+/// Largest shot deviation in degrees, reached just below lockout.
+pub const MAX_HEAT_SPREAD: f32 = 8.0;
+
+/// Heat stages in increasing severity. Each stage keeps the penalties of the ones below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HeatLevel {
+    Normal,
+    Hot,      // Reduced accuracy
+    Lockout,  // Weapons cannot fire
+    Shutdown, // Mech cannot move or fire until it cools down
+}
+
+impl HeatLevel {
+    pub fn label(self) -> &'static str {
+        match self {
+            HeatLevel::Normal => "NORMAL",
+            HeatLevel::Hot => "HOT",
+            HeatLevel::Lockout => "WEAPONS LOCKED",
+            HeatLevel::Shutdown => "SHUTDOWN",
+        }
+    }
+}
+
+/// Heat built up by a mech. Weapon shots and sprinting add heat,
+/// the upper body's `cooling_rating` removes it.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Heat {
+    pub current: f32,
+    pub capacity: f32,
+    pub shutdown: bool, // Latched on reaching capacity until cooled to RESTART_FRACTION
+}
+
+impl Default for Heat {
+    fn default() -> Self {
+        Self::new(100.0)
+    }
+}
+
+impl Heat {
+    pub fn new(capacity: f32) -> Self {
+        Self {
+            current: 0.0,
+            capacity,
+            shutdown: false,
+        }
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.capacity <= 0.0 {
+            return 0.0;
+        }
+        (self.current / self.capacity).clamp(0.0, 1.0)
+    }
+
+    pub fn add(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.capacity);
+        if self.current >= self.capacity {
+            self.shutdown = true;
+        }
+    }
+
+    pub fn dissipate(&mut self, amount: f32) {
+        self.current = (self.current - amount).max(0.0);
+        if self.shutdown && self.fraction() <= RESTART_FRACTION {
+            self.shutdown = false;
+        }
+    }
+
+    pub fn level(&self) -> HeatLevel {
+        let fraction = self.fraction();
+        if self.shutdown {
+            HeatLevel::Shutdown
+        } else if fraction >= LOCKOUT_FRACTION {
+            HeatLevel::Lockout
+        } else if fraction >= HOT_FRACTION {
+            HeatLevel::Hot
+        } else {
+            HeatLevel::Normal
+        }
+    }
+
+    pub fn can_fire(&self) -> bool {
+        self.level() < HeatLevel::Lockout
+    }
+
+    pub fn can_move(&self) -> bool {
+        !self.shutdown
+    }
+
+    /// Maximum shot deviation in degrees, growing from 0 at `HOT_FRACTION` to
+    /// `MAX_HEAT_SPREAD` at `LOCKOUT_FRACTION`.
+    pub fn accuracy_spread(&self) -> f32 {
+        let overheat = (self.fraction() - HOT_FRACTION) / (LOCKOUT_FRACTION - HOT_FRACTION);
+        overheat.clamp(0.0, 1.0) * MAX_HEAT_SPREAD
+    }
+}
@@ -2,6 +2,9 @@ use bevy::prelude::*;
 use super::traits::*;
 use super::components::*;
 
+pub const TANK_TREADS_CLIMB_SLOPE: f32 = 15.0;
+pub const TANK_TREADS_MAX_SLOPE: f32 = 30.0;
+
 #[derive(Component, Debug, Clone)]
 pub struct TankTreadsLower {
     pub movement_stats: MovementStats,
@@ -14,6 +17,8 @@ impl Default for TankTreadsLower {
                 max_speed: 5.0,
                 turn_rate: 90.0,
                 acceleration: 3.0,
+                climb_slope: TANK_TREADS_CLIMB_SLOPE,
+                max_slope: TANK_TREADS_MAX_SLOPE,
             },
         }
     }
@@ -26,6 +31,8 @@ impl TankTreadsLower {
                 max_speed,
                 turn_rate,
                 acceleration,
+                climb_slope: TANK_TREADS_CLIMB_SLOPE,
+                max_slope: TANK_TREADS_MAX_SLOPE,
             },
         }
     }
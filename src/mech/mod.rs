@@ -5,12 +5,14 @@ pub mod components;
 pub mod lower_bodies;
 pub mod upper_bodies;
 pub mod weapons;
+pub mod heat;
 
 pub use traits::*;
 pub use components::*;
 pub use lower_bodies::*;
 pub use upper_bodies::*;
 pub use weapons::*;
+pub use heat::*;
 
 #[derive(Component, Debug)]
 pub struct Mech {
@@ -126,10 +128,4 @@ pub struct MechLowerPart;
 pub struct MechUpperPart;
 
 #[derive(Component)]
-pub struct CannonBarrel;
-
-#[derive(Component)]
-pub struct TurretRotation {
-    pub target_angle: f32,
-    pub current_angle: f32,
-}
\ No newline at end of file
+pub struct CannonBarrel;
\ No newline at end of file
@@ -5,6 +5,8 @@ pub struct MovementStats {
     pub max_speed: f32,
     pub turn_rate: f32,
     pub acceleration: f32,
+    pub climb_slope: f32, // Steepest incline taken at full speed, in degrees
+    pub max_slope: f32,   // Inclines from here up are impassable, in degrees
 }
 
 #[derive(Debug, Clone)]
@@ -13,12 +15,69 @@ pub struct WeaponStats {
     pub damage: f32,
     pub range: f32,
     pub projectile_speed: f32,
+    pub magazine_size: u32,
+    pub reserve_ammo: u32, // Rounds carried outside the magazine at spawn
+    pub reload_time: f32,  // Seconds to swap a magazine
+    pub heat_per_shot: f32,
 }
 
 #[derive(Debug, Clone)]
 pub struct RotationCapability {
     pub can_rotate: bool,
     pub rotation_speed: f32,
+    pub limits: TurretLimits,
+}
+
+/// Traverse and elevation limits of a turret, in degrees.
+/// Traverse is measured from the hull's forward direction; negative elevation is depression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TurretLimits {
+    pub traverse: Option<f32>, // Max yaw either side of hull forward, None = full 360°
+    pub min_elevation: f32,
+    pub max_elevation: f32,
+}
+
+impl Default for TurretLimits {
+    fn default() -> Self {
+        Self {
+            traverse: None,
+            min_elevation: -10.0,
+            max_elevation: 45.0,
+        }
+    }
+}
+
+impl TurretLimits {
+    pub fn casemate(traverse: f32) -> Self {
+        Self {
+            traverse: Some(traverse),
+            ..default()
+        }
+    }
+
+    /// Whether a signed local yaw (-180..180) is inside the traverse arc.
+    pub fn is_within_traverse(&self, signed_local_angle: f32) -> bool {
+        match self.traverse {
+            Some(traverse) => signed_local_angle.abs() <= traverse,
+            None => true,
+        }
+    }
+
+    /// Clamps a signed local yaw (-180..180) into the traverse arc.
+    pub fn clamp_traverse(&self, signed_local_angle: f32) -> f32 {
+        match self.traverse {
+            Some(traverse) => signed_local_angle.clamp(-traverse, traverse),
+            None => signed_local_angle,
+        }
+    }
+
+    pub fn is_within_elevation(&self, elevation: f32) -> bool {
+        elevation >= self.min_elevation && elevation <= self.max_elevation
+    }
+
+    pub fn clamp_elevation(&self, elevation: f32) -> f32 {
+        elevation.clamp(self.min_elevation, self.max_elevation)
+    }
 }
 
 #[derive(Debug, Clone)]
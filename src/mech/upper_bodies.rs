@@ -6,6 +6,7 @@ use super::components::*;
 pub struct TurretUpper {
     pub rotation_capability: RotationCapability,
     pub hardpoints: Vec<Hardpoint>,
+    pub cooling_rating: f32,
 }
 
 impl Default for TurretUpper {
@@ -14,10 +15,12 @@ impl Default for TurretUpper {
             rotation_capability: RotationCapability {
                 can_rotate: true,
                 rotation_speed: 120.0,
+                limits: TurretLimits::default(),
             },
             hardpoints: vec![
                 Hardpoint::new("main".to_string(), Vec3::new(0.0, 0.0, 0.5)),
             ],
+            cooling_rating: 5.0,
         }
     }
 }
@@ -28,10 +31,27 @@ impl TurretUpper {
             rotation_capability: RotationCapability {
                 can_rotate: true,
                 rotation_speed,
+                limits: TurretLimits::default(),
             },
             hardpoints: vec![
                 Hardpoint::new("main".to_string(), Vec3::new(0.0, 0.0, 0.5)),
             ],
+            cooling_rating: 5.0,
+        }
+    }
+
+    /// Hull-mounted upper that can only aim `traverse` degrees either side of the chassis.
+    pub fn casemate(rotation_speed: f32, traverse: f32) -> Self {
+        Self {
+            rotation_capability: RotationCapability {
+                can_rotate: true,
+                rotation_speed,
+                limits: TurretLimits::casemate(traverse),
+            },
+            hardpoints: vec![
+                Hardpoint::new("main".to_string(), Vec3::new(0.0, 0.0, 0.5)),
+            ],
+            cooling_rating: 8.0,
         }
     }
 
@@ -40,11 +60,13 @@ impl TurretUpper {
             rotation_capability: RotationCapability {
                 can_rotate: true,
                 rotation_speed,
+                limits: TurretLimits::default(),
             },
             hardpoints: vec![
                 Hardpoint::new("left".to_string(), Vec3::new(-0.3, 0.0, 0.5)),
                 Hardpoint::new("right".to_string(), Vec3::new(0.3, 0.0, 0.5)),
             ],
+            cooling_rating: 6.0,
         }
     }
 }
@@ -54,6 +76,7 @@ pub fn create_turret_upper() -> MechUpperBody {
     MechUpperBody {
         rotation_capability: turret.rotation_capability,
         hardpoints: turret.hardpoints,
+        cooling_rating: turret.cooling_rating,
     }
 }
 
@@ -62,5 +85,15 @@ pub fn create_dual_turret_upper() -> MechUpperBody {
     MechUpperBody {
         rotation_capability: turret.rotation_capability,
         hardpoints: turret.hardpoints,
+        cooling_rating: turret.cooling_rating,
+    }
+}
+
+pub fn create_casemate_upper() -> MechUpperBody {
+    let casemate = TurretUpper::casemate(60.0, 30.0);
+    MechUpperBody {
+        rotation_capability: casemate.rotation_capability,
+        hardpoints: casemate.hardpoints,
+        cooling_rating: casemate.cooling_rating,
     }
-}
\ No newline at end of file
+}
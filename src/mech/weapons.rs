@@ -16,6 +16,10 @@ impl Default for CannonWeapon {
                 damage: 25.0,
                 range: 15.0,
                 projectile_speed: 15.0,
+                magazine_size: 5,
+                reserve_ammo: 30,
+                reload_time: 3.0,
+                heat_per_shot: 12.0,
             },
             barrel_length: 0.5,
         }
@@ -30,6 +34,10 @@ impl CannonWeapon {
                 damage,
                 range,
                 projectile_speed,
+                magazine_size: 5,
+                reserve_ammo: 30,
+                reload_time: 3.0,
+                heat_per_shot: 12.0,
             },
             barrel_length,
         }
@@ -42,6 +50,10 @@ impl CannonWeapon {
                 damage: 40.0,
                 range: 20.0,
                 projectile_speed: 12.0,
+                magazine_size: 3,
+                reserve_ammo: 18,
+                reload_time: 4.0,
+                heat_per_shot: 20.0,
             },
             barrel_length: 0.7,
         }
@@ -54,6 +66,10 @@ impl CannonWeapon {
                 damage: 15.0,
                 range: 12.0,
                 projectile_speed: 18.0,
+                magazine_size: 8,
+                reserve_ammo: 48,
+                reload_time: 2.0,
+                heat_per_shot: 6.0,
             },
             barrel_length: 0.4,
         }
@@ -67,4 +83,155 @@ pub fn create_cannon_weapon(hardpoint_id: String) -> MechWeapon {
         hardpoint_id,
         last_fire_time: 0.0,
     }
-}
\ No newline at end of file
+}
+
+/// Shell loaded into a cannon. Selectable per weapon through `WeaponAmmo`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmmoType {
+    ArmorPiercing,
+    HighExplosive,
+    #[default]
+    Fragmentation, // Breaks into `ShellFragment`s on impact
+}
+
+impl AmmoType {
+    pub const ALL: [AmmoType; 3] = [AmmoType::ArmorPiercing, AmmoType::HighExplosive, AmmoType::Fragmentation];
+
+    pub fn next(self) -> Self {
+        match self {
+            AmmoType::ArmorPiercing => AmmoType::HighExplosive,
+            AmmoType::HighExplosive => AmmoType::Fragmentation,
+            AmmoType::Fragmentation => AmmoType::ArmorPiercing,
+        }
+    }
+
+    pub fn damage_multiplier(self) -> f32 {
+        match self {
+            AmmoType::ArmorPiercing => 1.4,
+            AmmoType::HighExplosive => 1.2,
+            AmmoType::Fragmentation => 1.0,
+        }
+    }
+
+    pub fn speed_multiplier(self) -> f32 {
+        match self {
+            AmmoType::ArmorPiercing => 1.3,
+            AmmoType::HighExplosive => 0.8,
+            AmmoType::Fragmentation => 1.0,
+        }
+    }
+
+    /// Armor, in millimetres, the shell can defeat head-on at muzzle velocity.
+    pub fn penetration(self) -> f32 {
+        match self {
+            AmmoType::ArmorPiercing => 120.0,
+            AmmoType::HighExplosive => 40.0,
+            AmmoType::Fragmentation => 60.0,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AmmoType::ArmorPiercing => "AP",
+            AmmoType::HighExplosive => "HE",
+            AmmoType::Fragmentation => "FRAG",
+        }
+    }
+}
+
+/// Per-weapon ammunition state. Weapons without it fire without limit.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct WeaponAmmo {
+    pub magazine: u32,
+    pub magazine_size: u32,
+    pub reserve: u32,
+    pub reload_time: f32,
+    pub reload_remaining: Option<f32>, // Some while a magazine swap is in progress
+    pub ammo_type: AmmoType,
+}
+
+impl WeaponAmmo {
+    pub fn new(magazine_size: u32, reserve: u32, reload_time: f32) -> Self {
+        Self {
+            magazine: magazine_size,
+            magazine_size,
+            reserve,
+            reload_time,
+            reload_remaining: None,
+            ammo_type: AmmoType::default(),
+        }
+    }
+
+    /// A full magazine plus the weapon's reserve.
+    pub fn from_stats(stats: &WeaponStats) -> Self {
+        Self::new(stats.magazine_size, stats.reserve_ammo, stats.reload_time)
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.reload_remaining.is_some()
+    }
+
+    pub fn can_fire(&self) -> bool {
+        !self.is_reloading() && self.magazine > 0
+    }
+
+    /// Spends one round, starting a reload when the magazine runs dry.
+    /// Returns false if the weapon could not fire.
+    pub fn consume(&mut self) -> bool {
+        if !self.can_fire() {
+            return false;
+        }
+        self.magazine -= 1;
+        if self.magazine == 0 {
+            self.start_reload();
+        }
+        true
+    }
+
+    /// Begins swapping the magazine. Does nothing if already reloading,
+    /// the magazine is full or there is nothing left in reserve.
+    pub fn start_reload(&mut self) -> bool {
+        if self.is_reloading() || self.magazine >= self.magazine_size || self.reserve == 0 {
+            return false;
+        }
+        self.reload_remaining = Some(self.reload_time);
+        true
+    }
+
+    /// Advances a reload in progress, topping the magazine up from reserve when it completes.
+    pub fn tick_reload(&mut self, delta_time: f32) {
+        let Some(remaining) = self.reload_remaining else {
+            return;
+        };
+        let remaining = remaining - delta_time;
+        if remaining > 0.0 {
+            self.reload_remaining = Some(remaining);
+            return;
+        }
+
+        let loaded = (self.magazine_size - self.magazine).min(self.reserve);
+        self.magazine += loaded;
+        self.reserve -= loaded;
+        self.reload_remaining = None;
+    }
+
+    /// 0 at the start of a reload, 1 once complete (or when not reloading).
+    pub fn reload_progress(&self) -> f32 {
+        match self.reload_remaining {
+            Some(remaining) if self.reload_time > 0.0 => (1.0 - remaining / self.reload_time).clamp(0.0, 1.0),
+            _ => 1.0,
+        }
+    }
+
+    /// Switches to another shell type. The loaded magazine is swapped, so this forces a reload.
+    pub fn select_ammo_type(&mut self, ammo_type: AmmoType) {
+        if ammo_type == self.ammo_type {
+            return;
+        }
+        self.ammo_type = ammo_type;
+        self.reserve += self.magazine;
+        self.magazine = 0;
+        self.reload_remaining = None;
+        self.start_reload();
+    }
+}
@@ -32,7 +32,7 @@ pub fn create_sprite_mesh(size: Vec2) -> Mesh {
     mesh
 }
 
-pub fn create_sprite_material(color: Color, materials: &mut ResMut<Assets<StandardMaterial>>) -> Handle<StandardMaterial> {
+pub fn create_sprite_material(color: Color, materials: &mut Assets<StandardMaterial>) -> Handle<StandardMaterial> {
     materials.add(StandardMaterial {
         base_color: color,
         unlit: true,
@@ -1,9 +1,11 @@
 use bevy::prelude::*;
 
+/// Progress of the current run. `game_time` only advances while playing.
 #[derive(Resource)]
 pub struct GameState {
     pub score: u32,
     pub game_time: f32,
+    pub outcome: Option<GameOutcome>,
 }
 
 impl Default for GameState {
@@ -11,10 +13,17 @@ impl Default for GameState {
         Self {
             score: 0,
             game_time: 0.0,
+            outcome: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameOutcome {
+    Victory,
+    Defeat,
+}
+
 #[derive(Resource)]
 pub struct MouseWorldPosition {
     pub position: Vec2,
@@ -26,4 +35,26 @@ impl Default for MouseWorldPosition {
             position: Vec2::ZERO,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Which input scheme drives the hero. RTS is click-to-move with the mouse,
+/// Gamepad is direct driving with the sticks and triggers.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ControlScheme {
+    #[default]
+    Rts,
+    Gamepad,
+}
+
+
+/// Result of ray-casting from the camera through the cursor.
+/// `hovered` is the root entity of the first collider the ray hits.
+/// `pointer_over_ui` is set while the cursor is over a UI panel such as the minimap.
+#[derive(Resource, Default)]
+pub struct PickingState {
+    pub ray: Option<Ray>,
+    pub hovered: Option<Entity>,
+    pub hit_point: Option<Vec3>,
+    pub inspected: Option<Entity>,
+    pub pointer_over_ui: bool,
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use crate::components::Hero;
+use crate::mech::{AmmoType, MechHierarchy, WeaponAmmo};
+use crate::systems::gamepad_control::active_gamepad;
+use crate::systems::picking::find_descendants;
+
+pub const RELOAD_KEY: KeyCode = KeyCode::R;
+pub const CYCLE_AMMO_KEY: KeyCode = KeyCode::T;
+pub const RELOAD_BUTTON: GamepadButtonType = GamepadButtonType::West;
+pub const CYCLE_AMMO_BUTTON: GamepadButtonType = GamepadButtonType::North;
+
+/// Spends a round from a weapon's optional ammo state and returns the shell to fire,
+/// or `None` while it is reloading or empty. Weapons without ammo fire default shells forever.
+pub fn take_round(ammo: Option<Mut<WeaponAmmo>>) -> Option<AmmoType> {
+    match ammo {
+        Some(mut ammo) => ammo.consume().then_some(ammo.ammo_type),
+        None => Some(AmmoType::default()),
+    }
+}
+
+/// The hero's primary weapon: the first weapon listed in its `MechHierarchy`,
+/// or else the first weapon with ammo found below it.
+fn primary_weapon(
+    hero: Entity,
+    hierarchy: Option<&MechHierarchy>,
+    children_query: &Query<&Children>,
+    ammo_query: &Query<&mut WeaponAmmo>,
+) -> Option<Entity> {
+    hierarchy
+        .and_then(|hierarchy| hierarchy.weapons.first().copied())
+        .or_else(|| find_descendants(hero, children_query).into_iter().find(|part| ammo_query.contains(*part)))
+}
+
+fn gamepad_just_pressed(gamepads: &Gamepads, buttons: &Input<GamepadButton>, button: GamepadButtonType) -> bool {
+    active_gamepad(gamepads)
+        .map(|gamepad| buttons.just_pressed(GamepadButton::new(gamepad, button)))
+        .unwrap_or(false)
+}
+
+/// Advances magazine swaps in progress.
+pub fn weapon_reload_system(time: Res<Time>, mut ammo_query: Query<&mut WeaponAmmo>) {
+    for mut ammo in ammo_query.iter_mut() {
+        if ammo.is_reloading() {
+            ammo.tick_reload(time.delta_seconds());
+        }
+    }
+}
+
+/// Reloads the hero's primary weapon if its magazine has room.
+pub fn manual_reload_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    hero_query: Query<(Entity, Option<&MechHierarchy>), With<Hero>>,
+    children_query: Query<&Children>,
+    mut ammo_query: Query<&mut WeaponAmmo>,
+) {
+    if !keyboard_input.just_pressed(RELOAD_KEY) && !gamepad_just_pressed(&gamepads, &gamepad_buttons, RELOAD_BUTTON) {
+        return;
+    }
+
+    for (hero, hierarchy) in hero_query.iter() {
+        let Some(weapon) = primary_weapon(hero, hierarchy, &children_query, &ammo_query) else {
+            continue;
+        };
+        if let Ok(mut ammo) = ammo_query.get_mut(weapon) {
+            ammo.start_reload();
+        }
+    }
+}
+
+/// Switches the hero's primary weapon to its next shell type.
+pub fn cycle_ammo_type_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    hero_query: Query<(Entity, Option<&MechHierarchy>), With<Hero>>,
+    children_query: Query<&Children>,
+    mut ammo_query: Query<&mut WeaponAmmo>,
+) {
+    if !keyboard_input.just_pressed(CYCLE_AMMO_KEY) && !gamepad_just_pressed(&gamepads, &gamepad_buttons, CYCLE_AMMO_BUTTON) {
+        return;
+    }
+
+    for (hero, hierarchy) in hero_query.iter() {
+        let Some(weapon) = primary_weapon(hero, hierarchy, &children_query, &ammo_query) else {
+            continue;
+        };
+        if let Ok(mut ammo) = ammo_query.get_mut(weapon) {
+            let next = ammo.ammo_type.next();
+            ammo.select_ammo_type(next);
+            info!("Weapon switched to {} rounds", next.label());
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use crate::mech::TurretLimits;
+
+/// Yaw in degrees from `mech_position` to `target_position` on the X/Z plane
+/// (0 = +Z, 90 = +X), matching `Quat::from_rotation_y`.
+pub fn calculate_turret_angle(mech_position: Vec2, target_position: Vec2) -> f32 {
+    let direction = target_position - mech_position;
+    direction.x.atan2(direction.y).to_degrees()
+}
+
+pub fn normalize_angle(angle: f32) -> f32 {
+    let mut normalized = angle % 360.0;
+    if normalized < 0.0 {
+        normalized += 360.0;
+    }
+    normalized
+}
+
+/// Signed difference in degrees (-180..=180) to turn from `from` to `to`.
+pub fn shortest_angle_difference(from: f32, to: f32) -> f32 {
+    let diff = (to - from) % 360.0;
+    if diff > 180.0 {
+        diff - 360.0
+    } else if diff < -180.0 {
+        diff + 360.0
+    } else {
+        diff
+    }
+}
+
+pub fn rotate_towards_angle(
+    current_angle: f32,
+    target_angle: f32,
+    rotation_speed: f32,
+    delta_time: f32,
+) -> f32 {
+    let max_rotation = rotation_speed * delta_time;
+    let angle_diff = shortest_angle_difference(current_angle, target_angle);
+
+    if angle_diff.abs() <= max_rotation {
+        target_angle
+    } else {
+        current_angle + max_rotation * angle_diff.signum()
+    }
+}
+
+/// Steps a local turret angle towards `target_angle` without leaving the traverse arc.
+/// Limited turrets work in signed angles and turn the long way round rather than
+/// sweeping through the dead zone behind them.
+pub fn rotate_within_traverse(
+    current_angle: f32,
+    target_angle: f32,
+    limits: &TurretLimits,
+    rotation_speed: f32,
+    delta_time: f32,
+) -> f32 {
+    if limits.traverse.is_none() {
+        return rotate_towards_angle(current_angle, target_angle, rotation_speed, delta_time);
+    }
+
+    let current = limits.clamp_traverse(shortest_angle_difference(0.0, current_angle));
+    let target = limits.clamp_traverse(shortest_angle_difference(0.0, target_angle));
+    let max_rotation = rotation_speed * delta_time;
+    let angle_diff = target - current;
+
+    let new_angle = if angle_diff.abs() <= max_rotation {
+        target
+    } else {
+        current + max_rotation * angle_diff.signum()
+    };
+    normalize_angle(new_angle)
+}
+
+/// Barrel pitch in degrees needed to point from `from` straight at `to`.
+pub fn required_elevation(from: Vec3, to: Vec3) -> f32 {
+    let horizontal = Vec2::new(to.x - from.x, to.z - from.z).length();
+    (to.y - from.y).atan2(horizontal).to_degrees()
+}
+
+/// World yaw in degrees of a rotation, using the same convention as `calculate_turret_angle`.
+pub fn yaw_degrees(rotation: Quat) -> f32 {
+    rotation.to_euler(EulerRot::YXZ).0.to_degrees()
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use crate::components::TankShell;
+use crate::mech::AmmoType;
+use crate::systems::visual_effects::reflect_vector;
+
+pub const ENEMY_ARMOR: f32 = 30.0;
+pub const LOWER_BODY_ARMOR: f32 = 40.0;
+pub const UPPER_BODY_ARMOR: f32 = 50.0;
+pub const WEAPON_ARMOR: f32 = 20.0;
+/// Fraction of its speed a shell keeps after glancing off armor.
+pub const RICOCHET_SPEED_RETAINED: f32 = 0.6;
+/// Below this cosine (about 87° from the normal) every hit glances off.
+const MIN_IMPACT_COS: f32 = 0.05;
+
+/// Outcome of a kinetic shell meeting armor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArmorHit {
+    Penetrated,
+    Ricochet(Vec3), // New shell velocity
+}
+
+/// Armor a shell travelling along `direction` has to get through, which grows
+/// as the hit moves away from square-on to the face with normal `normal`.
+pub fn effective_armor(thickness: f32, direction: Vec2, normal: Vec2) -> f32 {
+    let cos = direction.normalize_or_zero().dot(normal.normalize_or_zero()).abs();
+    if cos < MIN_IMPACT_COS {
+        f32::INFINITY
+    } else {
+        thickness / cos
+    }
+}
+
+/// Penetration left in a shell, which drops with the speed it has lost since
+/// leaving the barrel. Shells without an ammo type always get through.
+pub fn shell_penetration(ammo_type: Option<AmmoType>, tank_shell: &TankShell, velocity: Vec3) -> f32 {
+    let Some(ammo_type) = ammo_type else {
+        return f32::INFINITY;
+    };
+    let muzzle_speed = tank_shell.velocity.length();
+    if muzzle_speed <= 0.0 {
+        return ammo_type.penetration();
+    }
+    let current_speed = Vec2::new(velocity.x, velocity.z).length();
+    ammo_type.penetration() * (current_speed / muzzle_speed).min(1.0)
+}
+
+/// Normal (X/Z plane) of the face of `target` nearest `impact`. Cuboid
+/// colliders use their box faces, anything else is treated as round.
+pub fn hit_face_normal(target: &GlobalTransform, collider: Option<&Collider>, impact: Vec3, direction: Vec2) -> Vec2 {
+    let fallback = -direction.normalize_or_zero();
+    let center = target.translation();
+    let Some(cuboid) = collider.and_then(|collider| collider.as_cuboid()) else {
+        return Vec2::new(impact.x - center.x, impact.z - center.z).try_normalize().unwrap_or(fallback);
+    };
+
+    let half_extents = cuboid.half_extents();
+    let local = target.affine().inverse().transform_point3(impact);
+    let (x, z) = (local.x / half_extents.x.max(f32::EPSILON), local.z / half_extents.z.max(f32::EPSILON));
+    if x == 0.0 && z == 0.0 {
+        return fallback;
+    }
+    let local_normal = if x.abs() >= z.abs() {
+        Vec3::X * x.signum()
+    } else {
+        Vec3::Z * z.signum()
+    };
+    let world_normal = target.compute_transform().rotation * local_normal;
+    Vec2::new(world_normal.x, world_normal.z).try_normalize().unwrap_or(fallback)
+}
+
+/// Decides whether a shell with `penetration` gets through `thickness` of armor
+/// when hitting a face with `normal`, using the same reflection as shell fragments
+/// for the ones that glance off.
+pub fn resolve_armor_hit(penetration: f32, thickness: f32, velocity: Vec3, normal: Vec2) -> ArmorHit {
+    let direction = Vec2::new(velocity.x, velocity.z);
+    if penetration >= effective_armor(thickness, direction, normal) {
+        return ArmorHit::Penetrated;
+    }
+    let reflected = reflect_vector(direction, normal.normalize_or_zero()) * RICOCHET_SPEED_RETAINED;
+    ArmorHit::Ricochet(Vec3::new(reflected.x, velocity.y * RICOCHET_SPEED_RETAINED, reflected.y))
+}
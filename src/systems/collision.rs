@@ -1,30 +1,66 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
-use crate::components::{Projectile, Enemy, Health, TankShell, HitFlash, FragmentShell, ShellFragment};
-use crate::systems::visual_effects::{calculate_fragment_velocities, calculate_fragment_lifetime, calculate_fragment_max_distance};
+use bevy::utils::HashSet;
+use crate::components::{Projectile, Health, DamageEvent, EnemyDestroyedEvent, TankShell, HitFlash, FragmentShell, ShellFragment, Rocket, FiredBy, Wall, Armor, Destructible, Terrain};
+use crate::mech::{AmmoType, PartHealth};
+use crate::systems::armor::{hit_face_normal, resolve_armor_hit, shell_penetration, ArmorHit};
+use crate::systems::explosion::{Damageable, ExplosionEvent};
+use crate::systems::picking::find_root_entity;
+use crate::systems::projectile_pool::{PoolKind, ProjectileAssets, ProjectilePool, Recycle};
+use crate::systems::visual_effects::{calculate_fragment_velocities, calculate_fragment_lifetime, calculate_fragment_max_distance, calculate_fragment_damage};
 
+/// Targets a fragment can pass through before it is spent on the next one.
+pub const FRAGMENT_PENETRATIONS: u8 = 1;
+
+/// A shell or rocket in flight: its damage, kind, motion and who fired it.
+type StrikingProjectile = (
+    Entity,
+    &'static Projectile,
+    Option<&'static TankShell>,
+    Option<&'static FragmentShell>,
+    &'static Velocity,
+    &'static Transform,
+    Has<Rocket>,
+    Option<&'static FiredBy>,
+);
+
+/// A unit a shell can strike and knock back.
+type StruckUnit = (Entity, &'static mut Health, &'static mut ExternalImpulse, &'static Transform, Has<Destructible>);
+
+/// A unit a fragment can pass through.
+type FragmentTarget = (&'static mut Health, &'static Transform, Has<Destructible>);
+/// What stops a fragment outright.
+type Solid = Or<(With<Wall>, With<Terrain>)>;
+
+#[allow(clippy::too_many_arguments)]
 pub fn collision_detection_system(
     mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
-    projectile_query: Query<(Entity, &Projectile, Option<&TankShell>, Option<&FragmentShell>, &Velocity, &Transform)>,
-    mut enemy_query: Query<(Entity, &mut Health, &mut ExternalImpulse, &Transform), With<Enemy>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    projectile_query: Query<StrikingProjectile, Without<ShellFragment>>,
+    // Destructible structures take hits through the same path as enemies
+    mut enemy_query: Query<StruckUnit, Damageable>,
+    armor_query: Query<(&Armor, &GlobalTransform, Option<&Collider>)>,
+    ammo_query: Query<&AmmoType>,
+    projectile_assets: Res<ProjectileAssets>,
+    mut pool: ResMut<ProjectilePool>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut destroyed_events: EventWriter<EnemyDestroyedEvent>,
+    mut explosion_events: EventWriter<ExplosionEvent>,
 ) {
     for collision_event in collision_events.read() {
         info!("Collision event detected: {:?}", collision_event);
         match collision_event {
             CollisionEvent::Started(entity1, entity2, _) => {
                 let collision_data = 
-                    if let Ok((proj_entity, projectile, tank_shell, fragment_shell, velocity, proj_transform)) = projectile_query.get(*entity1) {
-                        if let Ok((enemy_entity, _, _, enemy_transform)) = enemy_query.get(*entity2) {
-                            Some((proj_entity, projectile, tank_shell, fragment_shell, velocity, proj_transform, enemy_entity, enemy_transform))
+                    if let Ok((proj_entity, projectile, tank_shell, fragment_shell, velocity, proj_transform, is_rocket, fired_by)) = projectile_query.get(*entity1) {
+                        if let Ok((enemy_entity, _, _, enemy_transform, _)) = enemy_query.get(*entity2) {
+                            Some((proj_entity, projectile, tank_shell, fragment_shell, velocity, proj_transform, is_rocket, fired_by, enemy_entity, enemy_transform))
                         } else {
                             None
                         }
-                    } else if let Ok((proj_entity, projectile, tank_shell, fragment_shell, velocity, proj_transform)) = projectile_query.get(*entity2) {
-                        if let Ok((enemy_entity, _, _, enemy_transform)) = enemy_query.get(*entity1) {
-                            Some((proj_entity, projectile, tank_shell, fragment_shell, velocity, proj_transform, enemy_entity, enemy_transform))
+                    } else if let Ok((proj_entity, projectile, tank_shell, fragment_shell, velocity, proj_transform, is_rocket, fired_by)) = projectile_query.get(*entity2) {
+                        if let Ok((enemy_entity, _, _, enemy_transform, _)) = enemy_query.get(*entity1) {
+                            Some((proj_entity, projectile, tank_shell, fragment_shell, velocity, proj_transform, is_rocket, fired_by, enemy_entity, enemy_transform))
                         } else {
                             None
                         }
@@ -32,38 +68,81 @@ pub fn collision_detection_system(
                         None
                     };
                 
-                if let Some((projectile_entity, projectile, tank_shell, fragment_shell, velocity, proj_transform, enemy_entity, enemy_transform)) = collision_data {
+                if let Some((projectile_entity, projectile, tank_shell, fragment_shell, velocity, proj_transform, is_rocket, fired_by, enemy_entity, enemy_transform)) = collision_data {
                     let projectile_damage = projectile.damage;
                     let projectile_velocity = velocity.linvel;
                     let is_tank_shell = tank_shell.is_some();
                     let is_fragment_shell = fragment_shell.is_some();
+                    let ammo_type = ammo_query.get(projectile_entity).ok().copied();
                 
                     // Spawn fragments if this is a fragment shell
                     if is_fragment_shell && is_tank_shell {
                         spawn_fragments(
                             &mut commands,
-                            &mut meshes,
-                            &mut materials,
+                            &projectile_assets,
+                            &mut pool,
                             proj_transform.translation,
                             Vec2::new(projectile_velocity.x, projectile_velocity.z),
                             enemy_transform.translation,
                             tank_shell.as_ref().map(|ts| ts.max_range).unwrap_or(15.0),
                             projectile_damage,
+                            enemy_entity,
+                            fired_by.map(|fired_by| fired_by.0),
                         );
+                        let mut explosion = ExplosionEvent::fragment_shell(proj_transform.translation, projectile_damage)
+                            .with_direct_hit(enemy_entity);
+                        if let Some(fired_by) = fired_by {
+                            explosion = explosion.with_source(fired_by.0);
+                        }
+                        explosion_events.send(explosion);
                         
                         // Always despawn fragment shells on impact
-                        commands.entity(projectile_entity).despawn();
+                        commands.add(Recycle(projectile_entity));
+                    } else if is_rocket {
+                        explosion_events.send(ExplosionEvent::missile(proj_transform.translation, projectile_damage).with_direct_hit(enemy_entity));
+                        commands.add(Recycle(projectile_entity));
+                    } else if let (Some(tank_shell), Ok((armor, armor_transform, collider))) = (tank_shell, armor_query.get(enemy_entity)) {
+                        // Kinetic shells either punch through armor and are spent, or glance off
+                        let penetration = shell_penetration(ammo_type, tank_shell, projectile_velocity);
+                        let direction = Vec2::new(projectile_velocity.x, projectile_velocity.z);
+                        let normal = hit_face_normal(armor_transform, collider, proj_transform.translation, direction);
+                        if let ArmorHit::Ricochet(ricochet_velocity) = resolve_armor_hit(penetration, armor.thickness, projectile_velocity, normal) {
+                            commands.entity(projectile_entity).insert(Velocity::linear(ricochet_velocity));
+                            info!("Shell ricocheted off {} armor, new velocity {:?}", armor.thickness, ricochet_velocity);
+                            continue;
+                        }
+                        commands.add(Recycle(projectile_entity));
                     } else {
-                        // Only despawn projectile for non-tank shells or if it's moving slowly
-                        let should_despawn = !is_tank_shell || projectile_velocity.length() < 2.0;
+                        // Only despawn projectile for non-tank shells or if it's moving slowly,
+                        // unless it struck a structure, which stops every shot, or it is a
+                        // high-explosive shell, which bursts on whatever it hits
+                        let hit_structure = enemy_query.get(enemy_entity).is_ok_and(|(.., is_structure)| is_structure);
+                        let bursts = ammo_type == Some(AmmoType::HighExplosive);
+                        let should_despawn = !is_tank_shell || projectile_velocity.length() < 2.0 || hit_structure || bursts;
                         if should_despawn {
-                            commands.entity(projectile_entity).despawn();
+                            commands.add(Recycle(projectile_entity));
+                        }
+                    }
+                    
+                    // High-explosive shells go off where they land
+                    if ammo_type == Some(AmmoType::HighExplosive) {
+                        let mut explosion = ExplosionEvent::artillery(proj_transform.translation, projectile_damage)
+                            .with_direct_hit(enemy_entity);
+                        if let Some(fired_by) = fired_by {
+                            explosion = explosion.with_source(fired_by.0);
                         }
+                        explosion_events.send(explosion);
                     }
                     
-                    if let Ok((enemy_entity, mut health, mut impulse, _)) = enemy_query.get_mut(enemy_entity) {
+                    if let Ok((enemy_entity, mut health, mut impulse, target_transform, is_structure)) = enemy_query.get_mut(enemy_entity) {
+                    let was_alive = health.current > 0.0;
                     health.current -= projectile_damage;
                     info!("Enemy hit! Damage: {}, Health: {}/{}", projectile_damage, health.current, health.max);
+                    damage_events.send(DamageEvent {
+                        target: enemy_entity,
+                        amount: projectile_damage,
+                        position: target_transform.translation,
+                    });
                     
                     // Apply knockback force for tank shells
                     if is_tank_shell {
@@ -77,8 +156,10 @@ pub fn collision_detection_system(
                         });
                     }
                     
-                        if health.current <= 0.0 {
+                        // Structures collapse into rubble in structure_destruction_system
+                        if was_alive && health.current <= 0.0 && !is_structure {
                             commands.entity(enemy_entity).despawn();
+                            destroyed_events.send(EnemyDestroyedEvent { enemy: enemy_entity, position: target_transform.translation });
                             info!("Enemy destroyed!");
                         }
                     }
@@ -89,15 +170,18 @@ pub fn collision_detection_system(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_fragments(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
+    assets: &ProjectileAssets,
+    pool: &mut ProjectilePool,
     impact_position: Vec3,
     parent_velocity: Vec2,
     enemy_position: Vec3,
     parent_range: f32,
     parent_damage: f32,
+    struck_target: Entity,
+    fired_by: Option<Entity>,
 ) {
     // Calculate surface normal from impact to enemy center
     let impact_to_enemy = Vec2::new(
@@ -110,20 +194,13 @@ fn spawn_fragments(
     let fragment_velocities = calculate_fragment_velocities(parent_velocity, surface_normal);
     let fragment_lifetime_duration = calculate_fragment_lifetime(parent_range);
     let fragment_max_distance = calculate_fragment_max_distance(parent_range);
-    let fragment_damage = parent_damage / 3.0; // Each fragment gets 1/3 damage
+    let fragment_damage = calculate_fragment_damage(parent_damage);
     
     // Create impact flash effect
-    let flash_mesh = meshes.add(Mesh::from(shape::Box::new(0.8, 0.8, 0.8)));
-    let flash_material = materials.add(StandardMaterial {
-        base_color: Color::rgb(1.0, 1.0, 0.0),
-        emissive: Color::rgb(2.0, 1.5, 0.0),
-        ..default()
-    });
-    
-    commands.spawn((
+    pool.spawn(commands, PoolKind::ImpactFlash).insert((
         PbrBundle {
-            mesh: flash_mesh,
-            material: flash_material,
+            mesh: assets.impact_flash_mesh.clone(),
+            material: assets.impact_flash_material.clone(),
             transform: Transform::from_translation(impact_position),
             ..default()
         },
@@ -134,33 +211,30 @@ fn spawn_fragments(
     
     // Spawn three fragments
     for (i, velocity) in fragment_velocities.iter().enumerate() {
-        let fragment_mesh = meshes.add(Mesh::from(shape::Box::new(0.2, 0.1, 0.2))); // Smaller than shell
-        let fragment_material = materials.add(StandardMaterial {
-            base_color: Color::rgb(1.0, 0.8, 0.0),
-            emissive: Color::rgb(1.0, 0.5, 0.0),
-            ..default()
-        });
-        
-        commands.spawn((
+        let mut fragment = pool.spawn(commands, PoolKind::Fragment);
+        fragment.insert((
             Projectile {
                 damage: fragment_damage,
                 speed: velocity.length(),
             },
             ShellFragment {
-                parent_velocity: parent_velocity,
+                parent_velocity,
                 lifetime: Timer::from_seconds(fragment_lifetime_duration, TimerMode::Once),
                 max_distance: fragment_max_distance,
                 spawn_position: Vec2::new(impact_position.x, impact_position.z),
                 fragment_index: i as u8,
+                struck_target: Some(struck_target),
+                penetrations_left: FRAGMENT_PENETRATIONS,
             },
             PbrBundle {
-                mesh: fragment_mesh,
-                material: fragment_material,
+                mesh: assets.fragment_mesh.clone(),
+                material: assets.fragment_material.clone(),
                 transform: Transform::from_translation(impact_position),
                 ..default()
             },
             RigidBody::Dynamic,
             Collider::ball(0.05), // Small collider
+            Sensor, // Fragments pass through what they hit instead of bouncing off it
             ColliderMassProperties::Density(5.0),
             Restitution::coefficient(0.2),
             Friction::coefficient(0.5),
@@ -173,7 +247,90 @@ fn spawn_fragments(
             GravityScale(0.2), // Minimal gravity
             ActiveEvents::COLLISION_EVENTS,
         ));
+        if let Some(shooter) = fired_by {
+            fragment.insert(FiredBy(shooter));
+        }
     }
     
     info!("Spawned 3 fragments at impact position {:?}", impact_position);
-}
\ No newline at end of file
+}
+
+/// Fragments are sub-projectiles: they damage every enemy or mech part they pass
+/// through except the one their shell struck, and are spent after
+/// `penetrations_left` further targets or on hitting a wall or the ground.
+#[allow(clippy::too_many_arguments)]
+pub fn fragment_collision_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut fragment_query: Query<(&Projectile, &mut ShellFragment, Option<&FiredBy>)>,
+    mut enemy_query: Query<FragmentTarget, Damageable>,
+    mut part_query: Query<(&mut PartHealth, &GlobalTransform)>,
+    solid_query: Query<(), Solid>,
+    parents: Query<&Parent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut destroyed_events: EventWriter<EnemyDestroyedEvent>,
+) {
+    let mut spent = HashSet::new();
+
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(entity1, entity2, _) = collision_event else {
+            continue;
+        };
+        let (fragment_entity, target) = if fragment_query.contains(*entity1) {
+            (*entity1, *entity2)
+        } else if fragment_query.contains(*entity2) {
+            (*entity2, *entity1)
+        } else {
+            continue;
+        };
+        if spent.contains(&fragment_entity) {
+            continue;
+        }
+        let Ok((projectile, mut fragment, fired_by)) = fragment_query.get_mut(fragment_entity) else {
+            continue;
+        };
+        if fragment.struck_target == Some(target) {
+            continue;
+        }
+
+        if solid_query.contains(target) {
+            spent.insert(fragment_entity);
+            commands.add(Recycle(fragment_entity));
+            continue;
+        }
+
+        let damage = projectile.damage;
+        if let Ok((mut health, transform, is_structure)) = enemy_query.get_mut(target) {
+            if health.current <= 0.0 {
+                continue;
+            }
+            health.current -= damage;
+            info!("Fragment hit enemy! Damage: {}, Health: {}/{}", damage, health.current, health.max);
+            damage_events.send(DamageEvent { target, amount: damage, position: transform.translation });
+            commands.entity(target).insert(HitFlash {
+                timer: Timer::from_seconds(0.2, TimerMode::Once),
+            });
+            if health.current <= 0.0 && !is_structure {
+                commands.entity(target).despawn_recursive();
+                destroyed_events.send(EnemyDestroyedEvent { enemy: target, position: transform.translation });
+                info!("Enemy destroyed by fragment!");
+            }
+        } else if let Ok((mut part_health, transform)) = part_query.get_mut(target) {
+            let own_part = fired_by.is_some_and(|fired_by| fired_by.0 == find_root_entity(target, &parents));
+            if own_part || part_health.is_destroyed() {
+                continue;
+            }
+            part_health.current -= damage;
+            damage_events.send(DamageEvent { target, amount: damage, position: transform.translation() });
+        } else {
+            continue;
+        }
+
+        if fragment.penetrations_left == 0 {
+            spent.insert(fragment_entity);
+            commands.add(Recycle(fragment_entity));
+        } else {
+            fragment.penetrations_left -= 1;
+        }
+    }
+}
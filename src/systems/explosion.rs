@@ -0,0 +1,207 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use crate::camera::{CameraShakeEvent, CameraShakeSource};
+use crate::components::{DamageEvent, Destructible, Enemy, EnemyDestroyedEvent, Health, Projectile, Wall};
+use crate::mech::PartHealth;
+use crate::systems::picking::find_root_entity;
+
+pub const FRAGMENT_SHELL_BLAST_RADIUS: f32 = 2.5;
+pub const FRAGMENT_SHELL_BLAST_DAMAGE: f32 = 0.5; // Fraction of the shell's damage
+pub const MISSILE_BLAST_RADIUS: f32 = 3.0;
+pub const MISSILE_BLAST_DAMAGE: f32 = 0.75;
+pub const ARTILLERY_BLAST_RADIUS: f32 = 4.0;
+pub const ARTILLERY_BLAST_DAMAGE: f32 = 1.0;
+pub const ARTILLERY_BLAST_IMPULSE: f32 = 60.0;
+pub const BLAST_IMPULSE: f32 = 30.0;
+
+/// How blast damage and impulse drop off from the centre to the edge of the radius.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DamageFalloff {
+    Constant,
+    #[default]
+    Linear,
+    Quadratic,
+}
+
+impl DamageFalloff {
+    /// Fraction of full effect at `distance` from the centre, 0 outside `radius`.
+    pub fn scale(self, distance: f32, radius: f32) -> f32 {
+        if radius <= 0.0 || distance > radius {
+            return 0.0;
+        }
+        let t = distance / radius;
+        match self {
+            DamageFalloff::Constant => 1.0,
+            DamageFalloff::Linear => 1.0 - t,
+            DamageFalloff::Quadratic => 1.0 - t * t,
+        }
+    }
+}
+
+/// Area damage at `position`. Everything with a collider inside `radius` takes
+/// damage and impulse scaled by `falloff`, unless a `Wall` is in the way.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ExplosionEvent {
+    pub position: Vec3,
+    pub radius: f32,
+    pub damage: f32,  // At the centre
+    pub impulse: f32, // At the centre
+    pub falloff: DamageFalloff,
+    pub occluded_by_walls: bool,
+    pub source: Option<Entity>, // Mech that caused it, spared from its own blast
+    pub direct_hit: Option<Entity>, // Unit the shell struck, already damaged by the hit itself
+}
+
+impl ExplosionEvent {
+    pub fn new(position: Vec3, radius: f32, damage: f32, impulse: f32) -> Self {
+        Self {
+            position,
+            radius,
+            damage,
+            impulse,
+            falloff: DamageFalloff::default(),
+            occluded_by_walls: true,
+            source: None,
+            direct_hit: None,
+        }
+    }
+
+    pub fn fragment_shell(position: Vec3, shell_damage: f32) -> Self {
+        Self::new(position, FRAGMENT_SHELL_BLAST_RADIUS, shell_damage * FRAGMENT_SHELL_BLAST_DAMAGE, BLAST_IMPULSE)
+    }
+
+    pub fn missile(position: Vec3, missile_damage: f32) -> Self {
+        Self::new(position, MISSILE_BLAST_RADIUS, missile_damage * MISSILE_BLAST_DAMAGE, BLAST_IMPULSE)
+            .with_falloff(DamageFalloff::Quadratic)
+    }
+
+    /// High-explosive shells lobbed by the cannon: a wide blast that is not
+    /// stopped by walls.
+    pub fn artillery(position: Vec3, shell_damage: f32) -> Self {
+        let mut explosion = Self::new(position, ARTILLERY_BLAST_RADIUS, shell_damage * ARTILLERY_BLAST_DAMAGE, ARTILLERY_BLAST_IMPULSE);
+        explosion.occluded_by_walls = false;
+        explosion
+    }
+
+    pub fn with_falloff(mut self, falloff: DamageFalloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    pub fn with_source(mut self, source: Entity) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn with_direct_hit(mut self, struck: Entity) -> Self {
+        self.direct_hit = Some(struck);
+        self
+    }
+}
+
+/// Outward push on the X/Z plane for a target at `target` from a blast at `center`.
+pub fn blast_impulse(center: Vec3, target: Vec3, strength: f32) -> Vec3 {
+    let outward = Vec3::new(target.x - center.x, 0.0, target.z - center.z);
+    outward.normalize_or_zero() * strength
+}
+
+/// Enemies and destructible structures: everything with `Health` that weapons can wound.
+pub type Damageable = Or<(With<Enemy>, With<Destructible>)>;
+
+/// What a blast needs from a unit it catches.
+type BlastTarget = (&'static mut Health, Has<Destructible>);
+
+/// Resolves explosions with a ball shape query against every collider in range,
+/// ray casting to each one so walls can shelter what is behind them.
+#[allow(clippy::too_many_arguments)]
+pub fn explosion_system(
+    mut commands: Commands,
+    mut explosions: EventReader<ExplosionEvent>,
+    rapier_context: Res<RapierContext>,
+    transform_query: Query<&GlobalTransform>,
+    projectile_query: Query<(), With<Projectile>>,
+    wall_query: Query<(), With<Wall>>,
+    parents: Query<&Parent>,
+    mut enemy_query: Query<BlastTarget, Damageable>,
+    mut part_query: Query<&mut PartHealth>,
+    mut impulse_query: Query<&mut ExternalImpulse>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut destroyed_events: EventWriter<EnemyDestroyedEvent>,
+    mut shake_events: EventWriter<CameraShakeEvent>,
+) {
+    for explosion in explosions.read() {
+        shake_events.send(CameraShakeEvent {
+            source: CameraShakeSource::Explosion,
+            position: explosion.position,
+        });
+
+        let mut caught = Vec::new();
+        let not_projectile = |entity: Entity| !projectile_query.contains(entity);
+        rapier_context.intersections_with_shape(
+            explosion.position,
+            Quat::IDENTITY,
+            &Collider::ball(explosion.radius),
+            QueryFilter::default().predicate(&not_projectile),
+            |entity| {
+                caught.push(entity);
+                true
+            },
+        );
+
+        for entity in caught {
+            let root = find_root_entity(entity, &parents);
+            if explosion.source.is_some_and(|source| source == root) {
+                continue;
+            }
+            // The unit the shell struck has taken its damage from the hit
+            if explosion.direct_hit.is_some_and(|struck| struck == root) {
+                continue;
+            }
+            let Ok(target) = transform_query.get(entity).map(|transform| transform.translation()) else {
+                continue;
+            };
+            let offset = target - explosion.position;
+            let scale = explosion.falloff.scale(offset.length(), explosion.radius);
+            if scale <= 0.0 {
+                continue;
+            }
+
+            if explosion.occluded_by_walls && offset.length() > f32::EPSILON && !wall_query.contains(entity) {
+                let is_wall = |hit: Entity| hit != entity && wall_query.contains(hit);
+                let blocked = rapier_context.cast_ray(
+                    explosion.position,
+                    offset.normalize(),
+                    offset.length(),
+                    true,
+                    QueryFilter::default().predicate(&is_wall),
+                );
+                if blocked.is_some() {
+                    continue;
+                }
+            }
+
+            let damage = explosion.damage * scale;
+            if let Ok((mut health, is_structure)) = enemy_query.get_mut(entity) {
+                if health.current <= 0.0 {
+                    continue;
+                }
+                health.current -= damage;
+                damage_events.send(DamageEvent { target: entity, amount: damage, position: target });
+                if health.current <= 0.0 && !is_structure {
+                    commands.entity(entity).despawn_recursive();
+                    destroyed_events.send(EnemyDestroyedEvent { enemy: entity, position: target });
+                    info!("Enemy destroyed by explosion!");
+                }
+            } else if let Ok(mut part_health) = part_query.get_mut(entity) {
+                if !part_health.is_destroyed() {
+                    part_health.current -= damage;
+                    damage_events.send(DamageEvent { target: entity, amount: damage, position: target });
+                }
+            }
+
+            if let Ok(mut impulse) = impulse_query.get_mut(entity) {
+                impulse.impulse += blast_impulse(explosion.position, target, explosion.impulse * scale);
+            }
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::utils::HashSet;
+use bevy_rapier3d::prelude::*;
+use crate::components::{Enemy, VisionRange, Wall};
+use crate::systems::spatial_index::{SpatialIndex, UnitSide};
+use crate::systems::terrain::Heightmap;
+
+/// Side of a fog cell. Every visible cell costs a ray cast per viewer, so keep it coarse.
+pub const FOG_CELL_SIZE: f32 = 2.0;
+/// Height above the ground that units see from and are seen at.
+pub const SIGHT_HEIGHT: f32 = 1.0;
+pub const HERO_VISION_RANGE: f32 = 18.0;
+pub const ENEMY_VISION_RANGE: f32 = 12.0;
+/// Overlay opacity over ground that has never been seen and over ground seen before.
+pub const UNSEEN_FOG_ALPHA: f32 = 0.85;
+pub const EXPLORED_FOG_ALPHA: f32 = 0.5;
+/// Lift of the fog overlay above the terrain so the two don't z-fight.
+const FOG_OVERLAY_OFFSET: f32 = 0.05;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FogState {
+    #[default]
+    Unseen,
+    Explored, // Seen before but not right now
+    Visible,
+}
+
+/// One side's view of the map: a fog state per cell on the X/Z plane and the
+/// opposing units it can currently see.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FogGrid {
+    cell_size: f32,
+    columns: usize,
+    rows: usize,
+    origin: Vec2, // Corner of cell (0, 0)
+    cells: Vec<FogState>,
+    visible_units: HashSet<Entity>,
+}
+
+impl FogGrid {
+    pub fn new(size: Vec2, cell_size: f32) -> Self {
+        let columns = (size.x / cell_size).ceil().max(1.0) as usize;
+        let rows = (size.y / cell_size).ceil().max(1.0) as usize;
+        Self {
+            cell_size,
+            columns,
+            rows,
+            origin: -size / 2.0,
+            cells: vec![FogState::Unseen; columns * rows],
+            visible_units: HashSet::new(),
+        }
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cell_of(&self, position: Vec2) -> Option<UVec2> {
+        let grid = ((position - self.origin) / self.cell_size).floor();
+        if grid.x < 0.0 || grid.y < 0.0 || grid.x >= self.columns as f32 || grid.y >= self.rows as f32 {
+            return None;
+        }
+        Some(grid.as_uvec2())
+    }
+
+    pub fn cell_center(&self, cell: UVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + 0.5) * self.cell_size
+    }
+
+    /// Cells whose centre lies within `radius` of `center`.
+    pub fn cells_in_range(&self, center: Vec2, radius: f32) -> Vec<UVec2> {
+        let min = ((center - radius - self.origin) / self.cell_size).floor().max(Vec2::ZERO);
+        let max = ((center + radius - self.origin) / self.cell_size)
+            .floor()
+            .min(Vec2::new(self.columns as f32 - 1.0, self.rows as f32 - 1.0));
+        if min.x > max.x || min.y > max.y {
+            return Vec::new();
+        }
+        let (min, max) = (min.as_uvec2(), max.as_uvec2());
+        (min.y..=max.y)
+            .flat_map(|row| (min.x..=max.x).map(move |column| UVec2::new(column, row)))
+            .filter(|cell| self.cell_center(*cell).distance(center) <= radius)
+            .collect()
+    }
+
+    /// Fog state at `position`. Everything off the map stays unseen.
+    pub fn state_at(&self, position: Vec2) -> FogState {
+        self.cell_of(position).map_or(FogState::Unseen, |cell| self.cells[self.slot(cell)])
+    }
+
+    pub fn is_unit_visible(&self, entity: Entity) -> bool {
+        self.visible_units.contains(&entity)
+    }
+
+    /// Turns what was visible last update into explored ground before it is recomputed.
+    pub fn begin_update(&mut self) {
+        for cell in self.cells.iter_mut().filter(|cell| **cell == FogState::Visible) {
+            *cell = FogState::Explored;
+        }
+        self.visible_units.clear();
+    }
+
+    pub fn reveal(&mut self, cell: UVec2) {
+        let slot = self.slot(cell);
+        self.cells[slot] = FogState::Visible;
+    }
+
+    pub fn reveal_unit(&mut self, entity: Entity) {
+        self.visible_units.insert(entity);
+    }
+
+    /// RGBA8 pixels for the fog overlay, one per cell, row by row from the -Z edge.
+    pub fn overlay_pixels(&self) -> Vec<u8> {
+        self.cells.iter().flat_map(|cell| {
+            let alpha = match cell {
+                FogState::Unseen => UNSEEN_FOG_ALPHA,
+                FogState::Explored => EXPLORED_FOG_ALPHA,
+                FogState::Visible => 0.0,
+            };
+            [0, 0, 0, (alpha * 255.0).round() as u8]
+        }).collect()
+    }
+
+    fn slot(&self, cell: UVec2) -> usize {
+        cell.y as usize * self.columns + cell.x as usize
+    }
+}
+
+/// Fog of war for both sides, rebuilt every tick by `fog_of_war_system`. It is only
+/// marked changed when what either side sees actually changed.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct FogOfWar {
+    friendly: FogGrid,
+    enemy: FogGrid,
+}
+
+impl FogOfWar {
+    pub fn new(size: Vec2, cell_size: f32) -> Self {
+        Self {
+            friendly: FogGrid::new(size, cell_size),
+            enemy: FogGrid::new(size, cell_size),
+        }
+    }
+
+    pub fn grid(&self, side: UnitSide) -> &FogGrid {
+        match side {
+            UnitSide::Friendly => &self.friendly,
+            UnitSide::Enemy => &self.enemy,
+        }
+    }
+
+    pub fn grid_mut(&mut self, side: UnitSide) -> &mut FogGrid {
+        match side {
+            UnitSide::Friendly => &mut self.friendly,
+            UnitSide::Enemy => &mut self.enemy,
+        }
+    }
+
+    pub fn can_see(&self, side: UnitSide, entity: Entity) -> bool {
+        self.grid(side).is_unit_visible(entity)
+    }
+}
+
+/// Whether `side` may target `entity`. Without fog of war everything is in view.
+pub fn can_target(fog: Option<&FogOfWar>, side: UnitSide, entity: Entity) -> bool {
+    fog.is_none_or(|fog| fog.can_see(side, entity))
+}
+
+/// Point `SIGHT_HEIGHT` above the ground at `position`.
+pub fn sight_point(heightmap: Option<&Heightmap>, position: Vec2) -> Vec3 {
+    let ground = heightmap.map_or(0.0, |heightmap| heightmap.height_at(position));
+    Vec3::new(position.x, ground + SIGHT_HEIGHT, position.y)
+}
+
+/// Whether nothing blocks the straight line from `from` to `to`: neither terrain
+/// rising above it nor a collider accepted by `is_obstacle`.
+pub fn has_line_of_sight(
+    rapier_context: &RapierContext,
+    heightmap: Option<&Heightmap>,
+    from: Vec3,
+    to: Vec3,
+    is_obstacle: impl Fn(Entity) -> bool,
+) -> bool {
+    let offset = to - from;
+    let length = offset.length();
+    if length <= f32::EPSILON {
+        return true;
+    }
+
+    if let Some(heightmap) = heightmap {
+        let step = heightmap.cell_size().min_element() / 2.0;
+        let steps = (length / step).ceil() as usize;
+        for i in 1..steps {
+            let point = from + offset * (i as f32 / steps as f32);
+            if heightmap.height_at(Vec2::new(point.x, point.z)) > point.y {
+                return false;
+            }
+        }
+    }
+
+    rapier_context
+        .cast_ray(from, offset / length, length, true, QueryFilter::default().predicate(&is_obstacle))
+        .is_none()
+}
+
+/// Recomputes each side's fog from its units' vision ranges. Walls and terrain block
+/// sight; a side only sees opposing units it has a clear line to.
+pub fn fog_of_war_system(
+    mut fog: ResMut<FogOfWar>,
+    spatial_index: Res<SpatialIndex>,
+    rapier_context: Res<RapierContext>,
+    heightmap: Option<Res<Heightmap>>,
+    viewer_query: Query<(Entity, &VisionRange)>,
+    wall_query: Query<(), With<Wall>>,
+) {
+    let heightmap = heightmap.as_deref();
+    let is_wall = |entity: Entity| wall_query.contains(entity);
+    let mut updated = fog.clone();
+    updated.grid_mut(UnitSide::Friendly).begin_update();
+    updated.grid_mut(UnitSide::Enemy).begin_update();
+
+    for (viewer, vision) in viewer_query.iter() {
+        let Some(entry) = spatial_index.get(viewer) else {
+            continue;
+        };
+        let eye = sight_point(heightmap, entry.position);
+        let grid = updated.grid_mut(entry.side);
+
+        for cell in grid.cells_in_range(entry.position, vision.0) {
+            if has_line_of_sight(&rapier_context, heightmap, eye, sight_point(heightmap, grid.cell_center(cell)), is_wall) {
+                grid.reveal(cell);
+            }
+        }
+
+        for target in spatial_index.query_radius(entry.position, vision.0) {
+            if target.side == entry.side || grid.is_unit_visible(target.entity) {
+                continue;
+            }
+            if has_line_of_sight(&rapier_context, heightmap, eye, sight_point(heightmap, target.position), is_wall) {
+                grid.reveal_unit(target.entity);
+            }
+        }
+    }
+
+    fog.set_if_neq(updated);
+}
+
+/// Hides enemies the player's side can't currently see.
+pub fn enemy_fog_visibility_system(
+    fog: Res<FogOfWar>,
+    mut enemy_query: Query<(Entity, &mut Visibility), With<Enemy>>,
+) {
+    for (entity, mut visibility) in enemy_query.iter_mut() {
+        let target = if fog.can_see(UnitSide::Friendly, entity) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+}
+
+/// Ground overlay showing the player's fog of war.
+#[derive(Component)]
+pub struct FogOverlay {
+    pub image: Handle<Image>,
+}
+
+/// Drapes the fog overlay over the terrain, textured with one pixel per fog cell.
+pub fn spawn_fog_overlay_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    fog: Res<FogOfWar>,
+    heightmap: Res<Heightmap>,
+) {
+    let grid = fog.grid(UnitSide::Friendly);
+    let image = images.add(Image::new(
+        Extent3d {
+            width: grid.columns() as u32,
+            height: grid.rows() as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        grid.overlay_pixels(),
+        TextureFormat::Rgba8UnormSrgb,
+    ));
+
+    commands.spawn((
+        FogOverlay { image: image.clone() },
+        PbrBundle {
+            mesh: meshes.add(heightmap.mesh()),
+            material: materials.add(StandardMaterial {
+                base_color_texture: Some(image),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            }),
+            transform: Transform::from_xyz(0.0, FOG_OVERLAY_OFFSET, 0.0),
+            ..default()
+        },
+    ));
+}
+
+/// Copies the player's fog into the overlay texture.
+pub fn fog_overlay_system(
+    fog: Res<FogOfWar>,
+    overlay_query: Query<&FogOverlay>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if !fog.is_changed() {
+        return;
+    }
+    for overlay in overlay_query.iter() {
+        if let Some(image) = images.get_mut(&overlay.image) {
+            image.data = fog.grid(UnitSide::Friendly).overlay_pixels();
+        }
+    }
+}
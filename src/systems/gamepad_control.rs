@@ -0,0 +1,234 @@
+use bevy::prelude::*;
+use crate::components::{Hero, MoveTarget, AttackTarget, TargetIndicator};
+use crate::mech::{CannonWeapon, Heat, MechLowerBody, MechMovement, MechMovementState, MechRotation, MechUpperBody, MechWeapon, WeaponAmmo};
+use crate::resources::ControlScheme;
+use crate::systems::ammo::take_round;
+use crate::systems::angles::{calculate_turret_angle, normalize_angle, rotate_within_traverse, yaw_degrees};
+use crate::systems::heat::{heat_allows_fire, scatter_direction, SPRINT_SPEED_MULTIPLIER};
+use crate::systems::picking::find_root_entity;
+use crate::systems::weapon_control::{fire_cannon, weapon_muzzle_position, CannonFire};
+
+pub const CONTROL_SCHEME_TOGGLE_KEY: KeyCode = KeyCode::Tab;
+pub const CONTROL_SCHEME_TOGGLE_BUTTON: GamepadButtonType = GamepadButtonType::Select;
+pub const FIRE_BUTTON: GamepadButtonType = GamepadButtonType::RightTrigger2;
+pub const AIM_DEADZONE: f32 = 0.3; // Right stick magnitude needed before the turret follows it
+
+/// Maps a stick vector to a world direction on the X/Z plane.
+/// The camera looks straight down with screen-up along -Z, so stick-up maps to -Z.
+/// Returns `None` while the stick is inside the deadzone.
+pub fn stick_to_world_direction(stick: Vec2, deadzone: f32) -> Option<Vec2> {
+    if stick.length() < deadzone {
+        return None;
+    }
+    Some(Vec2::new(stick.x, -stick.y).normalize())
+}
+
+/// Returns the first connected gamepad, if any.
+pub fn active_gamepad(gamepads: &Gamepads) -> Option<Gamepad> {
+    gamepads.iter().next()
+}
+
+fn read_stick(axes: &Axis<GamepadAxis>, gamepad: Gamepad, x: GamepadAxisType, y: GamepadAxisType) -> Vec2 {
+    Vec2::new(
+        axes.get(GamepadAxis::new(gamepad, x)).unwrap_or(0.0),
+        axes.get(GamepadAxis::new(gamepad, y)).unwrap_or(0.0),
+    )
+}
+
+fn is_hero_part(entity: Entity, parents: &Query<&Parent>, heroes: &Query<(), With<Hero>>) -> bool {
+    heroes.contains(find_root_entity(entity, parents))
+}
+
+/// Switches between the RTS mouse scheme and direct gamepad control.
+/// Pending orders are cleared so the two schemes never fight over the hero.
+#[allow(clippy::too_many_arguments)]
+pub fn control_scheme_toggle_system(
+    mut commands: Commands,
+    mut control_scheme: ResMut<ControlScheme>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    mut hero_query: Query<(Entity, Option<&mut MechMovement>), With<Hero>>,
+    attack_target_query: Query<Entity, With<AttackTarget>>,
+    indicator_query: Query<Entity, With<TargetIndicator>>,
+) {
+    let button_pressed = active_gamepad(&gamepads)
+        .map(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, CONTROL_SCHEME_TOGGLE_BUTTON)))
+        .unwrap_or(false);
+
+    if !keyboard_input.just_pressed(CONTROL_SCHEME_TOGGLE_KEY) && !button_pressed {
+        return;
+    }
+
+    *control_scheme = match *control_scheme {
+        ControlScheme::Rts => ControlScheme::Gamepad,
+        ControlScheme::Gamepad => ControlScheme::Rts,
+    };
+    info!("Control scheme switched to {:?}", *control_scheme);
+
+    for (hero_entity, movement) in hero_query.iter_mut() {
+        commands.entity(hero_entity).remove::<MoveTarget>();
+        if let Some(mut movement) = movement {
+            movement.movement_state = MechMovementState::Idle;
+            movement.current_speed = 0.0;
+        }
+    }
+
+    // AttackTarget is propagated down the mech hierarchy, so clear every copy
+    for entity in attack_target_query.iter() {
+        commands.entity(entity).remove::<AttackTarget>();
+    }
+    for indicator in indicator_query.iter() {
+        commands.entity(indicator).despawn();
+    }
+}
+
+/// Left stick drives the hero chassis directly: Y is throttle, X turns.
+pub fn gamepad_drive_system(
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut hero_query: Query<(&mut Transform, &mut MechMovement, &MechLowerBody, Option<&Heat>), With<Hero>>,
+) {
+    let Some(gamepad) = active_gamepad(&gamepads) else {
+        return;
+    };
+
+    let stick = read_stick(&axes, gamepad, GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY);
+    let throttle = stick.y.clamp(-1.0, 1.0);
+    let turn = stick.x.clamp(-1.0, 1.0);
+    let delta_time = time.delta_seconds();
+
+    for (mut transform, mut movement, lower_body, heat) in hero_query.iter_mut() {
+        let stats = &lower_body.movement_stats;
+        if heat.is_some_and(|heat| !heat.can_move()) {
+            movement.current_speed = 0.0;
+            continue;
+        }
+
+        // Screen-clockwise is a decreasing yaw with the top-down camera
+        let turn_step = -turn * stats.turn_rate * delta_time;
+        transform.rotate_y(turn_step.to_radians());
+
+        let max_speed = if movement.sprinting {
+            stats.max_speed * SPRINT_SPEED_MULTIPLIER
+        } else {
+            stats.max_speed
+        };
+        let target_speed = throttle * max_speed;
+        let speed_step = stats.acceleration * delta_time;
+        let speed_diff = target_speed - movement.current_speed;
+        movement.current_speed += speed_diff.clamp(-speed_step, speed_step);
+
+        let forward = transform.rotation * Vec3::Z;
+        let step = forward * movement.current_speed * delta_time;
+        transform.translation.x += step.x;
+        transform.translation.z += step.z;
+
+        // Keep the click-to-move state machine parked while driving directly
+        movement.movement_state = MechMovementState::Idle;
+        movement.target_rotation = yaw_degrees(transform.rotation);
+    }
+}
+
+/// Right stick aims the hero's upper bodies in world space, independent of the chassis.
+pub fn gamepad_turret_aim_system(
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut upper_query: Query<(Entity, &mut Transform, &mut MechRotation, &MechUpperBody, &Parent)>,
+    global_transforms: Query<&GlobalTransform>,
+    parents: Query<&Parent>,
+    heroes: Query<(), With<Hero>>,
+) {
+    let Some(gamepad) = active_gamepad(&gamepads) else {
+        return;
+    };
+
+    let stick = read_stick(&axes, gamepad, GamepadAxisType::RightStickX, GamepadAxisType::RightStickY);
+    let Some(aim_direction) = stick_to_world_direction(stick, AIM_DEADZONE) else {
+        return;
+    };
+    let world_target_angle = normalize_angle(calculate_turret_angle(Vec2::ZERO, aim_direction));
+
+    for (entity, mut transform, mut rotation, upper_body, parent) in upper_query.iter_mut() {
+        if !is_hero_part(entity, &parents, &heroes) {
+            continue;
+        }
+
+        let parent_rotation = global_transforms
+            .get(parent.get())
+            .map(|global| global.to_scale_rotation_translation().1.to_euler(EulerRot::YXZ).0)
+            .unwrap_or(0.0);
+        let local_target_angle = normalize_angle(world_target_angle - normalize_angle(parent_rotation.to_degrees()));
+
+        let new_angle = normalize_angle(rotate_within_traverse(
+            normalize_angle(rotation.current_angle),
+            local_target_angle,
+            &upper_body.rotation_capability.limits,
+            upper_body.rotation_capability.rotation_speed,
+            time.delta_seconds(),
+        ));
+
+        rotation.current_angle = new_angle;
+        rotation.target_angle = world_target_angle;
+        transform.rotation = Quat::from_rotation_y(new_angle.to_radians());
+    }
+}
+
+/// Fires every hero weapon along its barrel while the fire trigger is held.
+/// Cooldowns are advanced by `weapon_cooldown_system`.
+#[allow(clippy::too_many_arguments)]
+pub fn gamepad_fire_system(
+    mut commands: Commands,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut weapon_query: Query<(Entity, &mut MechWeapon, &CannonWeapon, &Parent, Option<&mut WeaponAmmo>)>,
+    upper_query: Query<&GlobalTransform, With<MechUpperBody>>,
+    parents: Query<&Parent>,
+    heroes: Query<(), With<Hero>>,
+    mut heat_query: Query<&mut Heat>,
+    mut cannon_fire: CannonFire,
+) {
+    let Some(gamepad) = active_gamepad(&gamepads) else {
+        return;
+    };
+    if !gamepad_buttons.pressed(GamepadButton::new(gamepad, FIRE_BUTTON)) {
+        return;
+    }
+
+    for (entity, mut weapon, cannon, parent, ammo) in weapon_query.iter_mut() {
+        if !is_hero_part(entity, &parents, &heroes) || weapon.last_fire_time < weapon.weapon_stats.fire_rate {
+            continue;
+        }
+        let Ok(upper_transform) = upper_query.get(parent.get()) else {
+            continue;
+        };
+        let shooter = find_root_entity(entity, &parents);
+        let mut heat = heat_query.get_mut(shooter).ok();
+        if !heat_allows_fire(heat.as_deref()) {
+            continue;
+        }
+        let Some(ammo_type) = take_round(ammo) else {
+            continue;
+        };
+
+        let muzzle = weapon_muzzle_position(upper_transform, &weapon, cannon.barrel_length);
+        let forward = upper_transform.to_scale_rotation_translation().1 * Vec3::Z;
+        let spread = heat.as_ref().map_or(0.0, |heat| heat.accuracy_spread());
+        if let Some(heat) = heat.as_mut() {
+            heat.add(weapon.weapon_stats.heat_per_shot);
+        }
+
+        fire_cannon(
+            &mut commands,
+            &mut cannon_fire,
+            shooter,
+            muzzle,
+            scatter_direction(Vec2::new(forward.x, forward.z).normalize(), spread),
+            &weapon,
+            ammo_type,
+        );
+        weapon.last_fire_time = 0.0;
+    }
+}
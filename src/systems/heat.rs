@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::components::Hero;
+use crate::mech::{Heat, MechMovement, MechUpperBody};
+use crate::systems::gamepad_control::active_gamepad;
+
+pub const SPRINT_KEY: KeyCode = KeyCode::ShiftLeft;
+pub const SPRINT_BUTTON: GamepadButtonType = GamepadButtonType::LeftThumb;
+pub const SPRINT_SPEED_MULTIPLIER: f32 = 1.6;
+pub const SPRINT_HEAT_PER_SECOND: f32 = 10.0;
+
+/// Whether a mech with an optional heat state may fire. Mechs without `Heat` never overheat.
+pub fn heat_allows_fire(heat: Option<&Heat>) -> bool {
+    heat.is_none_or(Heat::can_fire)
+}
+
+/// Rotates `direction` by a random angle within ±`spread_degrees`.
+pub fn scatter_direction(direction: Vec2, spread_degrees: f32) -> Vec2 {
+    if spread_degrees <= 0.0 {
+        return direction;
+    }
+    let angle = rand::thread_rng().gen_range(-spread_degrees..=spread_degrees).to_radians();
+    Vec2::from_angle(angle).rotate(direction)
+}
+
+/// Holding the sprint input makes the hero sprint while it has somewhere to go.
+pub fn sprint_input_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut hero_query: Query<&mut MechMovement, With<Hero>>,
+) {
+    let gamepad_held = active_gamepad(&gamepads)
+        .map(|gamepad| gamepad_buttons.pressed(GamepadButton::new(gamepad, SPRINT_BUTTON)))
+        .unwrap_or(false);
+    let sprinting = keyboard_input.pressed(SPRINT_KEY) || gamepad_held;
+
+    for mut movement in hero_query.iter_mut() {
+        if movement.sprinting != sprinting {
+            movement.sprinting = sprinting;
+        }
+    }
+}
+
+/// Adds sprint heat and bleeds heat off through the mech's upper bodies.
+pub fn heat_system(
+    time: Res<Time>,
+    mut mech_query: Query<(&mut Heat, Option<&MechMovement>, Option<&Children>)>,
+    upper_query: Query<&MechUpperBody>,
+) {
+    let delta_time = time.delta_seconds();
+
+    for (mut heat, movement, children) in mech_query.iter_mut() {
+        let sprinting = movement.is_some_and(|movement| movement.sprinting && movement.current_speed > 0.0);
+        if sprinting && heat.can_move() {
+            heat.add(SPRINT_HEAT_PER_SECOND * delta_time);
+        }
+
+        let cooling: f32 = children
+            .map(|children| {
+                children
+                    .iter()
+                    .filter_map(|child| upper_query.get(*child).ok())
+                    .map(|upper_body| upper_body.cooling_rating)
+                    .sum()
+            })
+            .unwrap_or(0.0);
+        heat.dissipate(cooling * delta_time);
+    }
+}
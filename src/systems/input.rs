@@ -1,111 +1,152 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
-use crate::components::{Hero, MoveTarget, Enemy, AttackTarget, TargetIndicator};
-use crate::resources::MouseWorldPosition;
+use crate::components::{Hero, MoveTarget, Enemy, AttackTarget, TargetIndicator, Selected};
+use crate::mech::MechLowerBody;
+use crate::resources::{MouseWorldPosition, PickingState};
+use crate::camera::{cursor_to_ground_position, GROUND_PLANE_HEIGHT};
+use crate::systems::fog_of_war::{can_target, FogOfWar};
+use crate::systems::mech_movement::is_valid_move_target;
+use crate::systems::navigation::NavigationGrid;
+use crate::systems::picking::SelectionRingAssets;
+use crate::systems::projectile_pool::{spawn_click_marker, ProjectileAssets, ProjectilePool};
+use crate::systems::spatial_index::{SpatialIndex, UnitSide};
+use crate::systems::terrain::Heightmap;
 
+/// Projects the cursor onto the ground every frame, so the world position
+/// stays correct while the camera pans or zooms under a stationary cursor.
+/// With a heightmap the cursor lands on the terrain surface rather than the flat plane.
 pub fn mouse_position_system(
     mut mouse_world_pos: ResMut<MouseWorldPosition>,
     windows: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform, &Projection)>,
-    mut cursor_events: EventReader<CursorMoved>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    heightmap: Option<Res<Heightmap>>,
 ) {
-    let event_count = cursor_events.len();
-    if event_count > 0 {
-        info!("Processing {} cursor events", event_count);
-    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let Some(cursor_position) = window.cursor_position() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        warn!("No camera found!");
+        return;
+    };
     
-    for event in cursor_events.read() {
-        info!("Cursor moved to: {:?}", event.position);
-        
-        if let Ok(window) = windows.get_single() {
-            if let Ok((_camera, _camera_transform, projection)) = camera_query.get_single() {
-                if let Projection::Orthographic(ortho) = projection {
-                    // For a top-down orthographic camera, we can use simple math
-                    let window_size = Vec2::new(window.width(), window.height());
-                    let cursor_ndc = (event.position / window_size) * 2.0 - Vec2::ONE;
-                    
-                    // The orthographic view width/height in world units
-                    let view_width = ortho.scale * window_size.x;
-                    let view_height = ortho.scale * window_size.y;
-                    
-                    // Convert NDC to world coordinates
-                    // Since camera is looking down from above, we need to account for the view
-                    let world_x = cursor_ndc.x * view_width * 0.5;
-                    let world_z = cursor_ndc.y * view_height * 0.5;  // Don't negate for our camera setup
-                    
-                    mouse_world_pos.position = Vec2::new(world_x, world_z);
-                    info!("Screen: {:?}, NDC: {:?}, World: {:?}", event.position, cursor_ndc, mouse_world_pos.position);
-                }
-            } else {
-                warn!("No camera found!");
-            }
-        } else {
-            warn!("No window found!");
-        }
+    let hit = match heightmap {
+        Some(heightmap) => camera.viewport_to_world(camera_transform, cursor_position)
+            .and_then(|ray| heightmap.ray_intersection(ray.origin, ray.direction)),
+        None => cursor_to_ground_position(camera, camera_transform, cursor_position, GROUND_PLANE_HEIGHT),
+    };
+    if let Some(hit) = hit {
+        mouse_world_pos.position = Vec2::new(hit.x, hit.z);
     }
 }
 
+/// A hero that takes move orders: whether it is selected and what it drives on.
+pub type OrderableHero = (Entity, Has<Selected>, Option<&'static MechLowerBody>);
+
+#[allow(clippy::too_many_arguments)]
 pub fn click_to_move_system(
     mut commands: Commands,
     mouse_button: Res<Input<MouseButton>>,
     mouse_world_pos: Res<MouseWorldPosition>,
-    hero_query: Query<Entity, With<Hero>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    picking: Res<PickingState>,
+    heightmap: Option<Res<Heightmap>>,
+    navigation: Option<Res<NavigationGrid>>,
+    hero_query: Query<OrderableHero, With<Hero>>,
+    projectile_assets: Res<ProjectileAssets>,
+    mut pool: ResMut<ProjectilePool>,
 ) {
-    if mouse_button.just_pressed(MouseButton::Left) {
+    if mouse_button.just_pressed(MouseButton::Left) && !picking.pointer_over_ui {
+        // Clicking a friendly unit selects it instead of issuing a move
+        if let Some(picked) = picking.hovered.filter(|entity| hero_query.contains(*entity)) {
+            for (hero_entity, ..) in hero_query.iter() {
+                commands.entity(hero_entity).remove::<Selected>();
+            }
+            commands.entity(picked).insert(Selected);
+            info!("Selected unit {:?}", picked);
+            return;
+        }
+        
         let target_pos = mouse_world_pos.position;
         info!("Click target: {:?}", target_pos);
         
         // Spawn a visual marker at the click position
-        commands.spawn(PbrBundle {
-            mesh: meshes.add(Mesh::from(shape::Cube { size: 0.3 })),
-            material: materials.add(Color::rgb(1.0, 1.0, 0.0).into()),
-            transform: Transform::from_xyz(target_pos.x, 0.5, target_pos.y),
-            ..default()
-        });
+        spawn_click_marker(&mut commands, &projectile_assets, &mut pool, target_pos);
         
-        for hero_entity in hero_query.iter() {
-            commands.entity(hero_entity).insert(MoveTarget {
-                position: target_pos,
+        // Orders go to the selected units, or every hero when nothing is selected.
+        // Units refuse orders to ground they can't stand on.
+        let any_selected = hero_query.iter().any(|(_, selected, _)| selected);
+        for (hero_entity, selected, lower_body) in hero_query.iter() {
+            let reachable = lower_body.is_none_or(|lower_body| {
+                is_valid_move_target(heightmap.as_deref(), navigation.as_deref(), target_pos, &lower_body.movement_stats)
             });
+            if (selected || !any_selected) && reachable {
+                commands.entity(hero_entity).insert(MoveTarget {
+                    position: target_pos,
+                });
+            }
         }
     }
 }
 
+/// Everything that decides which enemy a targeting request picks: the right-click
+/// or Q key, what the cursor is over, and which enemies the fog lets through.
+#[derive(SystemParam)]
+pub struct TargetPicking<'w> {
+    mouse_button: Res<'w, Input<MouseButton>>,
+    keyboard_input: Res<'w, Input<KeyCode>>,
+    mouse_world_pos: Res<'w, MouseWorldPosition>,
+    picking: Res<'w, PickingState>,
+    spatial_index: Res<'w, SpatialIndex>,
+    fog: Option<Res<'w, FogOfWar>>,
+}
+
+impl TargetPicking<'_> {
+    /// Whether the player asked to target this frame.
+    /// Supports both right-click and the Q key.
+    fn requested(&self) -> bool {
+        let right_clicked = self.mouse_button.just_pressed(MouseButton::Right) && !self.picking.pointer_over_ui;
+        right_clicked || self.keyboard_input.just_pressed(KeyCode::Q)
+    }
+
+    /// The enemy under the cursor, if any can be targeted.
+    fn pick(&self, enemy_query: &Query<(Entity, &Transform), With<Enemy>>) -> Option<Entity> {
+        // Enemies hidden in the fog of war can't be selected
+        let targetable = |entity: Entity| {
+            enemy_query.contains(entity) && can_target(self.fog.as_deref(), UnitSide::Friendly, entity)
+        };
+        
+        // Prefer the enemy the picking ray hit; fall back to the closest enemy
+        // near the ground point when nothing with a collider is under the cursor
+        let picked_enemy = self.picking.hovered.filter(|entity| targetable(*entity));
+        picked_enemy.or_else(|| {
+            const SELECTION_RADIUS: f32 = 2.0;
+            
+            self.spatial_index
+                .nearest(self.mouse_world_pos.position, SELECTION_RADIUS, |entry| {
+                    entry.side == UnitSide::Enemy && targetable(entry.entity)
+                })
+                .map(|entry| entry.entity)
+        })
+    }
+}
+
 pub fn enemy_selection_system(
     mut commands: Commands,
-    mouse_button: Res<Input<MouseButton>>,
-    keyboard_input: Res<Input<KeyCode>>,
-    mouse_world_pos: Res<MouseWorldPosition>,
+    target_picking: TargetPicking,
     hero_query: Query<Entity, With<Hero>>,
     enemy_query: Query<(Entity, &Transform), With<Enemy>>,
     existing_indicators: Query<Entity, With<TargetIndicator>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    ring_assets: Res<SelectionRingAssets>,
 ) {
-    // Support both right-click and Q key for targeting
-    if mouse_button.just_pressed(MouseButton::Right) || keyboard_input.just_pressed(KeyCode::Q) {
-        let click_pos = mouse_world_pos.position;
-        
-        // Find the closest enemy within a reasonable distance
-        let mut closest_enemy = None;
-        let mut closest_distance = f32::MAX;
-        const SELECTION_RADIUS: f32 = 2.0;
-        
-        for (enemy_entity, enemy_transform) in enemy_query.iter() {
-            let enemy_pos_2d = Vec2::new(enemy_transform.translation.x, enemy_transform.translation.z);
-            let distance = enemy_pos_2d.distance(click_pos);
-            
-            if distance < SELECTION_RADIUS && distance < closest_distance {
-                closest_distance = distance;
-                closest_enemy = Some(enemy_entity);
-            }
-        }
+    if target_picking.requested() {
+        let closest_enemy = target_picking.pick(&enemy_query);
         
         // If we found an enemy, set it as the attack target for all heroes
         if let Some(target_entity) = closest_enemy {
-            info!("Selected enemy at distance: {}", closest_distance);
+            info!("Selected enemy {:?}", target_entity);
             
             // Remove any existing target indicators
             for indicator in existing_indicators.iter() {
@@ -119,13 +160,8 @@ pub fn enemy_selection_system(
                         target: target_entity,
                     },
                     PbrBundle {
-                        mesh: meshes.add(shape::Torus {
-                            radius: 1.0,
-                            ring_radius: 0.1,
-                            subdivisions_segments: 24,
-                            subdivisions_sides: 12,
-                        }.into()),
-                        material: materials.add(Color::rgb(1.0, 1.0, 0.0).into()),
+                        mesh: ring_assets.target_indicator_mesh.clone(),
+                        material: ring_assets.target_indicator_material.clone(),
                         transform: Transform::from_xyz(
                             enemy_transform.translation.x,
                             0.1,
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use crate::mech::*;
 use crate::rendering::*;
 use bevy_rapier3d::prelude::*;
+use crate::systems::terrain::UNIT_COLLISION_GROUPS;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Team {
@@ -73,10 +74,6 @@ pub fn spawn_mech(
                 weapon_mount_offset: Vec3::new(0.0, 0.0, turret_cannon.barrel_length / 2.0),
             },
             turret_cannon.clone(),
-            TurretRotation {
-                target_angle: 0.0,
-                current_angle: 0.0,
-            },
             TransformBundle {
                 local: Transform::from_translation(get_mech_part_offset(MechPartType::TurretBase)),
                 ..default()
@@ -124,6 +121,7 @@ pub fn spawn_mech_with_visuals(
             },
             RigidBody::Dynamic,
             Collider::cuboid(0.3, 0.4, 0.5),  // 3D collider
+            UNIT_COLLISION_GROUPS,
         ))
         .id();
 
@@ -158,10 +156,6 @@ pub fn spawn_mech_with_visuals(
                 weapon_mount_offset: Vec3::new(0.0, 0.0, turret_cannon.barrel_length / 2.0),
             },
             turret_cannon.clone(),
-            TurretRotation {
-                target_angle: 0.0,
-                current_angle: 0.0,
-            },
             PbrBundle {
                 mesh: upper_mesh,
                 material: upper_material,
@@ -1,23 +1,77 @@
 use bevy::prelude::*;
-use crate::components::{MoveTarget, TankMovement, TankRotationState};
-use crate::mech::{MechMovement, MechMovementState, MechLowerBody, MechHierarchy};
+use crate::components::MoveTarget;
+use crate::mech::{Heat, MechMovement, MechMovementState, MechLowerBody, MechHierarchy, MovementStats};
+use crate::systems::angles::shortest_angle_difference;
+use crate::systems::heat::SPRINT_SPEED_MULTIPLIER;
+use crate::systems::navigation::NavigationGrid;
+use crate::systems::terrain::{slope_speed_factor, Heightmap};
 
 const ROTATION_TOLERANCE: f32 = 1.0; // degrees
 const ARRIVAL_THRESHOLD: f32 = 0.5; // units
+/// How far ahead a mech checks the navigation grid for the ground it is driving onto.
+const NAV_LOOKAHEAD: f32 = 0.5; // units
+
+/// How much of its speed a mech keeps driving forward from where it stands: slowed by
+/// slopes and rough cells, and stopped short of blocked ones.
+fn ground_speed_factor(
+    heightmap: Option<&Heightmap>,
+    navigation: Option<&NavigationGrid>,
+    transform: &Transform,
+    stats: &MovementStats,
+) -> f32 {
+    let forward = transform.rotation * Vec3::Z;
+    let forward = Vec2::new(forward.x, forward.z);
+    let position = Vec2::new(transform.translation.x, transform.translation.z);
+    let slope_factor = heightmap.map_or(1.0, |heightmap| {
+        slope_speed_factor(heightmap.incline_along(position, forward), stats)
+    });
+    let cost = navigation.map_or(Some(1.0), |navigation| {
+        navigation.movement_cost(position + forward * NAV_LOOKAHEAD)
+    });
+    cost.map_or(0.0, |cost| slope_factor / cost)
+}
+
+/// Whether a unit with these stats may be ordered to `position`: on the map, no steeper
+/// than its lower body can stand and not in a blocked navigation cell.
+pub fn is_valid_move_target(
+    heightmap: Option<&Heightmap>,
+    navigation: Option<&NavigationGrid>,
+    position: Vec2,
+    stats: &MovementStats,
+) -> bool {
+    heightmap.is_none_or(|heightmap| heightmap.is_walkable(position, stats))
+        && navigation.is_none_or(|navigation| navigation.is_passable(position))
+}
+
+/// A driven mech: its drive state and stats, any move order and its heat.
+type DrivenMech = (
+    Entity,
+    &'static mut Transform,
+    &'static mut MechMovement,
+    &'static MechLowerBody,
+    Option<&'static MoveTarget>,
+    Option<&'static Heat>,
+);
 
 pub fn mech_movement_system(
     time: Res<Time>,
     mut commands: Commands,
-    mut query: Query<(
-        Entity,
-        &mut Transform,
-        &mut MechMovement,
-        &MechLowerBody,
-        Option<&MoveTarget>,
-    )>,
+    heightmap: Option<Res<Heightmap>>,
+    navigation: Option<Res<NavigationGrid>>,
+    mut query: Query<DrivenMech>,
 ) {
-    for (entity, mut transform, mut movement, lower_body, move_target) in query.iter_mut() {
+    for (entity, mut transform, mut movement, lower_body, move_target, heat) in query.iter_mut() {
         let stats = &lower_body.movement_stats;
+        // A shut down mech stops dead and keeps its orders for when it restarts
+        if heat.is_some_and(|heat| !heat.can_move()) {
+            movement.current_speed = 0.0;
+            continue;
+        }
+        let max_speed = if movement.sprinting {
+            stats.max_speed * SPRINT_SPEED_MULTIPLIER
+        } else {
+            stats.max_speed
+        };
         
         match movement.movement_state {
             MechMovementState::Idle => {
@@ -61,7 +115,8 @@ pub fn mech_movement_system(
                             
                         // Continue moving forward while rotating
                         let forward = transform.rotation * Vec3::Z;
-                        let move_delta = forward * movement.current_speed * time.delta_seconds();
+                        let ground_factor = ground_speed_factor(heightmap.as_deref(), navigation.as_deref(), &transform, stats);
+                        let move_delta = forward * movement.current_speed * ground_factor * time.delta_seconds();
                         transform.translation.x += move_delta.x;
                         transform.translation.z += move_delta.z;
                     }
@@ -83,10 +138,13 @@ pub fn mech_movement_system(
                     if distance > ARRIVAL_THRESHOLD {
                         movement.current_speed = (movement.current_speed + 
                             stats.acceleration * time.delta_seconds())
-                            .min(stats.max_speed);
+                            .min(max_speed);
                         
+                        // Steep or rough ground slows the mech; slopes past its limit and
+                        // blocked cells stop it outright
                         let forward = transform.rotation * Vec3::Z;
-                        let move_delta = forward * movement.current_speed * time.delta_seconds();
+                        let ground_factor = ground_speed_factor(heightmap.as_deref(), navigation.as_deref(), &transform, stats);
+                        let move_delta = forward * movement.current_speed * ground_factor * time.delta_seconds();
                         transform.translation.x += move_delta.x;
                         transform.translation.z += move_delta.z;
                         
@@ -124,51 +182,6 @@ pub fn mech_movement_system(
     }
 }
 
-// Adapter system to convert old TankMovement to new MechMovement
-pub fn tank_movement_adapter_system(
-    mut commands: Commands,
-    query: Query<(Entity, &TankMovement), Without<MechMovement>>,
-) {
-    for (entity, tank_movement) in query.iter() {
-        let mech_movement = MechMovement {
-            movement_state: match tank_movement.rotation_state {
-                TankRotationState::Idle => MechMovementState::Idle,
-                TankRotationState::Rotating => MechMovementState::Rotating,
-                TankRotationState::Moving => MechMovementState::Moving,
-            },
-            target_rotation: tank_movement.target_rotation,
-            current_speed: tank_movement.current_speed,
-        };
-        
-        let mech_lower = MechLowerBody {
-            movement_stats: crate::mech::MovementStats {
-                max_speed: tank_movement.max_speed,
-                turn_rate: tank_movement.rotation_speed,
-                acceleration: tank_movement.acceleration,
-            },
-        };
-        
-        commands.entity(entity)
-            .insert(mech_movement)
-            .insert(mech_lower);
-    }
-}
-
-fn shortest_angle_difference(from: f32, to: f32) -> f32 {
-    // Normalize angles to 0-360 range
-    let from_normalized = ((from % 360.0) + 360.0) % 360.0;
-    let to_normalized = ((to % 360.0) + 360.0) % 360.0;
-    
-    let diff = to_normalized - from_normalized;
-    if diff > 180.0 {
-        diff - 360.0
-    } else if diff < -180.0 {
-        diff + 360.0
-    } else {
-        diff
-    }
-}
-
 // System to sync lower body visual rotation with the main mech entity
 pub fn sync_lower_body_rotation_system(
     mech_query: Query<(&Transform, &MechHierarchy), With<MechMovement>>,
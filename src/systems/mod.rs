@@ -1,25 +1,53 @@
+pub mod ammo;
+pub mod armor;
+pub mod angles;
 pub mod collision;
+pub mod explosion;
+pub mod fog_of_war;
+pub mod heat;
 pub mod input;
 pub mod mech_assembly;
 pub mod movement;
+pub mod navigation;
 pub mod projectile;
-pub mod tank_movement;
+pub mod projectile_pool;
 pub mod turret_control;
 pub mod visual_effects;
 pub mod mech_movement;
 pub mod upper_body_control;
 pub mod weapon_control;
 pub mod attack_target_propagation;
+pub mod gamepad_control;
+pub mod picking;
+pub mod part_damage;
+pub mod spatial_index;
+pub mod structures;
+pub mod terrain;
+pub mod weapon_range;
 
+pub use ammo::*;
+pub use armor::*;
+pub use angles::*;
 pub use collision::*;
+pub use explosion::*;
+pub use fog_of_war::*;
+pub use heat::*;
 pub use input::*;
 pub use mech_assembly::*;
 pub use movement::*;
+pub use navigation::*;
 pub use projectile::*;
-pub use tank_movement::*;
-pub use turret_control::{turret_control_system, get_turret_forward_direction, is_turret_facing_target};
+pub use projectile_pool::*;
+pub use turret_control::{traverse_hull_to_target_system, get_turret_forward_direction, is_turret_facing_target};
 pub use visual_effects::*;
 pub use mech_movement::*;
 pub use upper_body_control::*;
 pub use weapon_control::*;
-pub use attack_target_propagation::*;
\ No newline at end of file
+pub use attack_target_propagation::*;
+pub use gamepad_control::*;
+pub use picking::*;
+pub use part_damage::*;
+pub use spatial_index::*;
+pub use structures::*;
+pub use terrain::*;
+pub use weapon_range::*;
\ No newline at end of file
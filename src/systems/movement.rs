@@ -1,14 +1,14 @@
 use bevy::prelude::*;
-use crate::components::{Hero, MoveTarget, AttackTarget, TankMovement};
-use crate::mech::{MechLower, TankTreads, MechMovement};
+use crate::components::{Hero, MoveTarget, AttackTarget};
+use crate::mech::{MechLower, TankTreads, MechMovement, MechWeapon};
+use crate::systems::weapon_range::{engagement_range, weapon_ranges};
 
 const ARRIVAL_THRESHOLD: f32 = 0.05;
-const ATTACK_RANGE: f32 = 10.0;  // Match projectile.rs
 
 pub fn movement_system(
     time: Res<Time>,
     mut commands: Commands,
-    mut query: Query<(Entity, &mut Transform, &MoveTarget, &Children), (With<Hero>, Without<TankMovement>, Without<MechMovement>)>,
+    mut query: Query<(Entity, &mut Transform, &MoveTarget, &Children), (With<Hero>, Without<MechMovement>)>,
     lower_query: Query<(&MechLower, &TankTreads)>,
 ) {
     for (entity, mut transform, target, children) in query.iter_mut() {
@@ -45,19 +45,27 @@ pub fn attack_move_system(
     mut commands: Commands,
     hero_query: Query<(Entity, &Transform, Option<&AttackTarget>), With<Hero>>,
     enemy_transforms: Query<&Transform, With<crate::components::Enemy>>,
+    children_query: Query<&Children>,
+    weapon_query: Query<&MechWeapon>,
 ) {
     for (hero_entity, hero_transform, attack_target) in hero_query.iter() {
         if let Some(target) = attack_target {
             // Check if the target still exists
             if let Ok(enemy_transform) = enemy_transforms.get(target.entity) {
+                // Units without weapons have nothing to close in for
+                let ranges = weapon_ranges(hero_entity, &children_query, &weapon_query);
+                let Some(attack_range) = engagement_range(&ranges) else {
+                    continue;
+                };
+                
                 let hero_pos = Vec2::new(hero_transform.translation.x, hero_transform.translation.z);
                 let enemy_pos = Vec2::new(enemy_transform.translation.x, enemy_transform.translation.z);
                 let distance = hero_pos.distance(enemy_pos);
                 
                 // If we're not in attack range, set a move target
-                if distance > ATTACK_RANGE {
+                if distance > attack_range {
                     let direction = (enemy_pos - hero_pos).normalize();
-                    let move_to_pos = enemy_pos - direction * (ATTACK_RANGE - 0.5);
+                    let move_to_pos = enemy_pos - direction * (attack_range - 0.5);
                     
                     commands.entity(hero_entity).insert(MoveTarget {
                         position: move_to_pos,
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+/// Side of a navigation cell.
+pub const NAV_CELL_SIZE: f32 = 1.0;
+/// Relative cost of crossing a rough cell, such as rubble, against open ground.
+pub const ROUGH_MOVEMENT_COST: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NavCell {
+    #[default]
+    Open,
+    Rough,
+    Blocked,
+}
+
+/// Grid over the map on the X/Z plane recording where static structures block movement.
+/// Mechs slow down on rough cells and stop short of blocked ones, and move orders into
+/// blocked cells are refused. Slope limits depend on the lower body, so move orders also
+/// check `Heightmap::is_walkable`.
+#[derive(Resource, Debug, Clone)]
+pub struct NavigationGrid {
+    cell_size: f32,
+    columns: usize,
+    rows: usize,
+    origin: Vec2, // Corner of cell (0, 0)
+    cells: Vec<NavCell>,
+}
+
+impl NavigationGrid {
+    pub fn new(size: Vec2, cell_size: f32) -> Self {
+        let columns = (size.x / cell_size).ceil().max(1.0) as usize;
+        let rows = (size.y / cell_size).ceil().max(1.0) as usize;
+        Self {
+            cell_size,
+            columns,
+            rows,
+            origin: -size / 2.0,
+            cells: vec![NavCell::Open; columns * rows],
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Column and row of the cell holding `position`, if it is on the grid.
+    pub fn cell_of(&self, position: Vec2) -> Option<UVec2> {
+        let grid = ((position - self.origin) / self.cell_size).floor();
+        if grid.x < 0.0 || grid.y < 0.0 || grid.x >= self.columns as f32 || grid.y >= self.rows as f32 {
+            return None;
+        }
+        Some(grid.as_uvec2())
+    }
+
+    pub fn cell_center(&self, cell: UVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + 0.5) * self.cell_size
+    }
+
+    pub fn get(&self, position: Vec2) -> Option<NavCell> {
+        self.cell_of(position).map(|cell| self.cells[cell.y as usize * self.columns + cell.x as usize])
+    }
+
+    pub fn is_passable(&self, position: Vec2) -> bool {
+        self.movement_cost(position).is_some()
+    }
+
+    /// Cost of crossing the cell at `position`, or `None` where it can't be entered.
+    pub fn movement_cost(&self, position: Vec2) -> Option<f32> {
+        match self.get(position)? {
+            NavCell::Open => Some(1.0),
+            NavCell::Rough => Some(ROUGH_MOVEMENT_COST),
+            NavCell::Blocked => None,
+        }
+    }
+
+    /// Sets every cell whose centre lies under a footprint of `half_extents`, placed and
+    /// turned about Y by `transform`.
+    pub fn mark_footprint(&mut self, transform: &Transform, half_extents: Vec2, cell: NavCell) {
+        let center = Vec2::new(transform.translation.x, transform.translation.z);
+        let to_local = transform.rotation.inverse();
+        let reach = half_extents.length();
+        let last = Vec2::new((self.columns - 1) as f32, (self.rows - 1) as f32);
+        let min = ((center - Vec2::splat(reach) - self.origin) / self.cell_size).floor().max(Vec2::ZERO);
+        let max = ((center + Vec2::splat(reach) - self.origin) / self.cell_size).floor().min(last);
+        if min.cmpgt(max).any() {
+            return;
+        }
+        let (min, max) = (min.as_uvec2(), max.as_uvec2());
+
+        for row in min.y..=max.y {
+            for column in min.x..=max.x {
+                let offset = self.cell_center(UVec2::new(column, row)) - center;
+                let local = to_local * Vec3::new(offset.x, 0.0, offset.y);
+                if local.x.abs() <= half_extents.x && local.z.abs() <= half_extents.y {
+                    self.cells[row as usize * self.columns + column as usize] = cell;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use crate::camera::{CameraShakeEvent, CameraShakeSource};
+use crate::components::{Armor, DamageEvent, FiredBy, FragmentShell, Hero, MoveTarget, Projectile, ShellFragment, TankShell};
+use crate::mech::{AmmoType, MechHierarchy, MechLowerBody, MechMovement, MechMovementState, MechPartSlot, MechUpperBody, PartHealth};
+use crate::systems::armor::{hit_face_normal, resolve_armor_hit, shell_penetration, ArmorHit};
+use crate::systems::picking::find_root_entity;
+use crate::systems::projectile_pool::Recycle;
+
+/// Sent when a mech part runs out of hit points, before it is removed.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PartDestroyedEvent {
+    pub mech: Entity,
+    pub part: Entity,
+    pub slot: MechPartSlot,
+}
+
+/// Applies projectile hits to the mech part that was struck. Projectiles are
+/// spent on impact and never damage the mech that fired them. Kinetic shells
+/// glance off armored parts they cannot penetrate. Hits on the hero shake the camera.
+/// Shell fragments are handled by `fragment_collision_system`.
+#[allow(clippy::too_many_arguments)]
+pub fn mech_part_collision_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    projectile_query: Query<(&Projectile, Option<&FiredBy>), Without<ShellFragment>>,
+    shell_query: Query<(&TankShell, &Velocity, &Transform, Option<&AmmoType>), Without<FragmentShell>>,
+    mut part_query: Query<(&mut PartHealth, &GlobalTransform)>,
+    armor_query: Query<(&Armor, &GlobalTransform, Option<&Collider>)>,
+    parents: Query<&Parent>,
+    heroes: Query<(), With<Hero>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut shake_events: EventWriter<CameraShakeEvent>,
+) {
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(entity1, entity2, _) = collision_event else {
+            continue;
+        };
+        let (projectile_entity, part_entity) = if projectile_query.contains(*entity1) && part_query.contains(*entity2) {
+            (*entity1, *entity2)
+        } else if projectile_query.contains(*entity2) && part_query.contains(*entity1) {
+            (*entity2, *entity1)
+        } else {
+            continue;
+        };
+
+        let Ok((projectile, fired_by)) = projectile_query.get(projectile_entity) else {
+            continue;
+        };
+        let mech = find_root_entity(part_entity, &parents);
+        if fired_by.is_some_and(|fired_by| fired_by.0 == mech) {
+            continue;
+        }
+        let Ok((mut part_health, part_transform)) = part_query.get_mut(part_entity) else {
+            continue;
+        };
+        if part_health.is_destroyed() {
+            continue;
+        }
+        if let (Ok((tank_shell, velocity, shell_transform, ammo_type)), Ok((armor, armor_transform, collider))) =
+            (shell_query.get(projectile_entity), armor_query.get(part_entity))
+        {
+            let penetration = shell_penetration(ammo_type.copied(), tank_shell, velocity.linvel);
+            let direction = Vec2::new(velocity.linvel.x, velocity.linvel.z);
+            let normal = hit_face_normal(armor_transform, collider, shell_transform.translation, direction);
+            if let ArmorHit::Ricochet(ricochet_velocity) = resolve_armor_hit(penetration, armor.thickness, velocity.linvel, normal) {
+                commands.entity(projectile_entity).insert(Velocity::linear(ricochet_velocity));
+                info!("Shell ricocheted off {:?} part", part_health.slot);
+                continue;
+            }
+        }
+
+        part_health.current -= projectile.damage;
+        info!("{:?} part hit! Damage: {}, Health: {}/{}", part_health.slot, projectile.damage, part_health.current, part_health.max);
+        damage_events.send(DamageEvent {
+            target: part_entity,
+            amount: projectile.damage,
+            position: part_transform.translation(),
+        });
+        if heroes.contains(mech) {
+            shake_events.send(CameraShakeEvent {
+                source: CameraShakeSource::OwnMechHit,
+                position: part_transform.translation(),
+            });
+        }
+        commands.add(Recycle(projectile_entity));
+    }
+}
+
+/// Removes destroyed parts and applies the consequences to their mech:
+/// a lost lower body immobilises it, a lost weapon frees its hardpoint
+/// and a lost upper body destroys the whole mech.
+pub fn mech_part_destruction_system(
+    mut commands: Commands,
+    part_query: Query<(Entity, &PartHealth)>,
+    parents: Query<&Parent>,
+    mut hierarchy_query: Query<&mut MechHierarchy>,
+    mut movement_query: Query<&mut MechMovement>,
+    mut upper_query: Query<&mut MechUpperBody>,
+    mut destroyed_events: EventWriter<PartDestroyedEvent>,
+) {
+    for (part, part_health) in part_query.iter() {
+        if !part_health.is_destroyed() {
+            continue;
+        }
+        let mech = find_root_entity(part, &parents);
+        destroyed_events.send(PartDestroyedEvent {
+            mech,
+            part,
+            slot: part_health.slot,
+        });
+
+        match part_health.slot {
+            MechPartSlot::Lower => {
+                if let Ok(mut hierarchy) = hierarchy_query.get_mut(mech) {
+                    hierarchy.lower = None;
+                }
+                if let Ok(mut movement) = movement_query.get_mut(mech) {
+                    movement.current_speed = 0.0;
+                    movement.movement_state = MechMovementState::Idle;
+                }
+                // Without drive stats neither the move orders nor the gamepad can move it
+                commands.entity(mech).remove::<(MechLowerBody, MoveTarget)>();
+                commands.entity(part).despawn_recursive();
+                info!("Lower body destroyed, mech immobilised");
+            }
+            MechPartSlot::Weapon => {
+                if let Ok(mut hierarchy) = hierarchy_query.get_mut(mech) {
+                    hierarchy.weapons.retain(|weapon| *weapon != part);
+                }
+                if let Ok(mut upper_body) = parents.get(part).and_then(|parent| upper_query.get_mut(parent.get())) {
+                    for hardpoint in upper_body.hardpoints.iter_mut() {
+                        if hardpoint.occupied_by == Some(part) {
+                            hardpoint.occupied_by = None;
+                        }
+                    }
+                }
+                commands.entity(part).despawn_recursive();
+                info!("Weapon destroyed");
+            }
+            MechPartSlot::Upper => {
+                commands.entity(mech).despawn_recursive();
+                info!("Upper body destroyed, mech lost");
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_rapier3d::prelude::*;
+use crate::components::{Projectile, HoverHighlight, Health, Terrain};
+use crate::resources::PickingState;
+
+pub const PICK_RAY_LENGTH: f32 = 1000.0;
+pub const INSPECT_KEY: KeyCode = KeyCode::I;
+
+/// Ring mesh and material handles shared by every target indicator and hover
+/// highlight, so selecting and hovering does not add new assets.
+#[derive(Resource, Clone)]
+pub struct SelectionRingAssets {
+    pub target_indicator_mesh: Handle<Mesh>,
+    pub target_indicator_material: Handle<StandardMaterial>,
+    pub hover_highlight_mesh: Handle<Mesh>,
+    pub hover_highlight_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for SelectionRingAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let target_indicator_mesh = meshes.add(shape::Torus {
+            radius: 1.0,
+            ring_radius: 0.1,
+            subdivisions_segments: 24,
+            subdivisions_sides: 12,
+        }.into());
+        let hover_highlight_mesh = meshes.add(shape::Torus {
+            radius: 1.1,
+            ring_radius: 0.05,
+            subdivisions_segments: 24,
+            subdivisions_sides: 8,
+        }.into());
+
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        Self {
+            target_indicator_mesh,
+            target_indicator_material: materials.add(Color::rgb(1.0, 1.0, 0.0).into()),
+            hover_highlight_mesh,
+            hover_highlight_material: materials.add(Color::rgb(0.8, 0.8, 0.8).into()),
+        }
+    }
+}
+
+/// Casts a ray against the physics colliders and returns the first entity hit and the hit point.
+pub fn pick_entity(
+    rapier_context: &RapierContext,
+    ray: Ray,
+    filter: QueryFilter,
+) -> Option<(Entity, Vec3)> {
+    rapier_context
+        .cast_ray(ray.origin, ray.direction, PICK_RAY_LENGTH, true, filter)
+        .map(|(entity, toi)| (entity, ray.get_point(toi)))
+}
+
+/// Colliders often live on child parts (e.g. a mech's lower body),
+/// so picks resolve to the root of the hierarchy.
+pub fn find_root_entity(entity: Entity, parents: &Query<&Parent>) -> Entity {
+    let mut current = entity;
+    while let Ok(parent) = parents.get(current) {
+        current = parent.get();
+    }
+    current
+}
+
+/// Every part below `entity` in its hierarchy, each parent before its children.
+pub fn find_descendants(entity: Entity, children_query: &Query<&Children>) -> Vec<Entity> {
+    let mut descendants = Vec::new();
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter() {
+            descendants.push(*child);
+            descendants.extend(find_descendants(*child, children_query));
+        }
+    }
+    descendants
+}
+
+/// Builds the picking ray from the camera through the cursor.
+pub fn cursor_ray_system(
+    mut picking: ResMut<PickingState>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+) {
+    let cursor_position = windows.get_single().ok().and_then(|window| window.cursor_position());
+
+    picking.ray = match (cursor_position, camera_query.get_single()) {
+        (Some(cursor_position), Ok((camera, camera_transform))) => {
+            camera.viewport_to_world(camera_transform, cursor_position)
+        }
+        _ => None,
+    };
+}
+
+/// Ray-casts the picking ray against Rapier colliders and records the hovered entity.
+/// Projectiles and sensors are ignored so shells in flight never steal the cursor.
+/// Hitting the terrain records the ground point but hovers nothing.
+pub fn entity_picking_system(
+    mut picking: ResMut<PickingState>,
+    rapier_context: Res<RapierContext>,
+    projectile_query: Query<(), With<Projectile>>,
+    terrain_query: Query<(), With<Terrain>>,
+    parents: Query<&Parent>,
+) {
+    let hit = picking.ray.and_then(|ray| {
+        let predicate = |entity: Entity| projectile_query.get(entity).is_err();
+        let filter = QueryFilter::default().exclude_sensors().predicate(&predicate);
+        pick_entity(&rapier_context, ray, filter)
+    });
+
+    match hit {
+        Some((entity, point)) => {
+            let root = find_root_entity(entity, &parents);
+            picking.hovered = Some(root).filter(|root| !terrain_query.contains(*root));
+            picking.hit_point = Some(point);
+        }
+        None => {
+            picking.hovered = None;
+            picking.hit_point = None;
+        }
+    }
+}
+
+/// Keeps a ring under the hovered entity.
+pub fn hover_highlight_system(
+    mut commands: Commands,
+    picking: Res<PickingState>,
+    mut highlight_query: Query<(Entity, &mut Transform, &HoverHighlight)>,
+    target_query: Query<&GlobalTransform, Without<HoverHighlight>>,
+    ring_assets: Res<SelectionRingAssets>,
+) {
+    let mut has_highlight = false;
+
+    for (highlight_entity, mut transform, highlight) in highlight_query.iter_mut() {
+        if picking.hovered == Some(highlight.target) {
+            if let Ok(target_transform) = target_query.get(highlight.target) {
+                let target_position = target_transform.translation();
+                transform.translation.x = target_position.x;
+                transform.translation.z = target_position.z;
+                has_highlight = true;
+                continue;
+            }
+        }
+
+        commands.entity(highlight_entity).despawn();
+    }
+
+    if has_highlight {
+        return;
+    }
+
+    if let Some(hovered) = picking.hovered {
+        if let Ok(target_transform) = target_query.get(hovered) {
+            let target_position = target_transform.translation();
+            commands.spawn((
+                HoverHighlight { target: hovered },
+                PbrBundle {
+                    mesh: ring_assets.hover_highlight_mesh.clone(),
+                    material: ring_assets.hover_highlight_material.clone(),
+                    transform: Transform::from_xyz(target_position.x, 0.05, target_position.z),
+                    ..default()
+                },
+            ));
+        }
+    }
+}
+
+/// Inspects whatever is under the cursor (I key or middle click).
+pub fn entity_inspect_system(
+    mut picking: ResMut<PickingState>,
+    mouse_button: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    health_query: Query<&Health>,
+) {
+    if !mouse_button.just_pressed(MouseButton::Middle) && !keyboard_input.just_pressed(INSPECT_KEY) {
+        return;
+    }
+
+    picking.inspected = picking.hovered;
+
+    if let Some(entity) = picking.inspected {
+        match health_query.get(entity) {
+            Ok(health) => info!("Inspecting {:?}: Health {}/{}", entity, health.current, health.max),
+            Err(_) => info!("Inspecting {:?}", entity),
+        }
+    }
+}
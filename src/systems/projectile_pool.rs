@@ -0,0 +1,165 @@
+use bevy::ecs::system::{Command, EntityCommands};
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use bevy_rapier3d::prelude::*;
+use crate::components::{ClickMarker, FiredBy, FragmentShell, HitFlash, Projectile, Rocket, ShellFragment, TankShell};
+use crate::mech::AmmoType;
+use crate::rendering;
+
+pub const CLICK_MARKER_LIFETIME: f32 = 1.0;
+
+/// Mesh and material handles shared by every projectile, impact flash and click
+/// marker, so firing does not add new assets.
+#[derive(Resource, Clone)]
+pub struct ProjectileAssets {
+    pub shell_mesh: Handle<Mesh>,
+    pub shell_material: Handle<StandardMaterial>,
+    pub fragment_mesh: Handle<Mesh>,
+    pub fragment_material: Handle<StandardMaterial>,
+    pub impact_flash_mesh: Handle<Mesh>,
+    pub impact_flash_material: Handle<StandardMaterial>,
+    pub rocket_mesh: Handle<Mesh>,
+    pub rocket_material: Handle<StandardMaterial>,
+    pub click_marker_mesh: Handle<Mesh>,
+    pub click_marker_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for ProjectileAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let shell_mesh = meshes.add(Mesh::from(shape::Box::new(0.4, 0.2, 0.4)));
+        let fragment_mesh = meshes.add(Mesh::from(shape::Box::new(0.2, 0.1, 0.2))); // Smaller than shell
+        let impact_flash_mesh = meshes.add(Mesh::from(shape::Box::new(0.8, 0.8, 0.8)));
+        let rocket_mesh = meshes.add(rendering::create_sprite_mesh(Vec2::new(0.2, 0.2)));
+        let click_marker_mesh = meshes.add(Mesh::from(shape::Cube { size: 0.3 }));
+
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        Self {
+            shell_mesh,
+            shell_material: materials.add(Color::rgb(1.0, 1.0, 0.0).into()), // Bright yellow
+            fragment_mesh,
+            fragment_material: materials.add(StandardMaterial {
+                base_color: Color::rgb(1.0, 0.8, 0.0),
+                emissive: Color::rgb(1.0, 0.5, 0.0),
+                ..default()
+            }),
+            impact_flash_mesh,
+            impact_flash_material: materials.add(StandardMaterial {
+                base_color: Color::rgb(1.0, 1.0, 0.0),
+                emissive: Color::rgb(2.0, 1.5, 0.0),
+                ..default()
+            }),
+            rocket_mesh,
+            rocket_material: rendering::create_sprite_material(Color::rgb(1.0, 0.5, 0.0), &mut materials),
+            click_marker_mesh,
+            click_marker_material: materials.add(Color::rgb(1.0, 1.0, 0.0).into()),
+        }
+    }
+}
+
+/// Which free list a pooled entity goes back to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PoolKind {
+    Shell,
+    Fragment,
+    ImpactFlash,
+    ClickMarker,
+}
+
+/// Marks an entity as owned by the `ProjectilePool`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pooled(pub PoolKind);
+
+/// Spent shells, fragments, flashes and markers waiting to be reused. Parked
+/// entities keep their physics components but are disabled and hidden.
+#[derive(Resource, Default)]
+pub struct ProjectilePool {
+    free: HashMap<PoolKind, Vec<Entity>>,
+    parked: HashSet<Entity>,
+}
+
+impl ProjectilePool {
+    /// Reuses a parked entity of `kind`, or spawns a new one. The caller inserts
+    /// the full bundle for the projectile, as it would on a fresh spawn.
+    pub fn spawn<'w, 's, 'a>(&mut self, commands: &'a mut Commands<'w, 's>, kind: PoolKind) -> EntityCommands<'w, 's, 'a> {
+        let free = self.free.entry(kind).or_default();
+        while let Some(entity) = free.pop() {
+            self.parked.remove(&entity);
+            // Parked entities can still be despawned by anything that sweeps the world
+            if commands.get_entity(entity).is_some() {
+                let mut entity_commands = commands.entity(entity);
+                entity_commands.remove::<(RigidBodyDisabled, ColliderDisabled)>();
+                return entity_commands;
+            }
+        }
+        commands.spawn(Pooled(kind))
+    }
+
+    pub fn parked(&self, kind: PoolKind) -> usize {
+        self.free.get(&kind).map_or(0, Vec::len)
+    }
+
+    fn park(&mut self, kind: PoolKind, entity: Entity) {
+        if self.parked.insert(entity) {
+            self.free.entry(kind).or_default().push(entity);
+        }
+    }
+}
+
+/// Returns a spent projectile, flash or marker to the `ProjectilePool`, or
+/// despawns it when it was not spawned through the pool. Safe to issue more
+/// than once for the same entity in a frame.
+pub struct Recycle(pub Entity);
+
+impl Command for Recycle {
+    fn apply(self, world: &mut World) {
+        let Some(mut entity) = world.get_entity_mut(self.0) else {
+            return;
+        };
+        let kind = match entity.get::<Pooled>() {
+            Some(&Pooled(kind)) if entity.world().contains_resource::<ProjectilePool>() => kind,
+            _ => {
+                entity.despawn();
+                return;
+            }
+        };
+        entity
+            .remove::<(Projectile, TankShell, FragmentShell, ShellFragment, Rocket, AmmoType, FiredBy, HitFlash, ClickMarker)>()
+            .insert((
+                RigidBodyDisabled,
+                ColliderDisabled,
+                Velocity::zero(),
+                ExternalImpulse::default(),
+                Visibility::Hidden,
+            ));
+        world.resource_mut::<ProjectilePool>().park(kind, self.0);
+    }
+}
+
+/// Spawns a click marker at `position` on the ground, reusing a pooled one.
+pub fn spawn_click_marker(commands: &mut Commands, assets: &ProjectileAssets, pool: &mut ProjectilePool, position: Vec2) {
+    pool.spawn(commands, PoolKind::ClickMarker).insert((
+        ClickMarker {
+            timer: Timer::from_seconds(CLICK_MARKER_LIFETIME, TimerMode::Once),
+        },
+        PbrBundle {
+            mesh: assets.click_marker_mesh.clone(),
+            material: assets.click_marker_material.clone(),
+            transform: Transform::from_xyz(position.x, 0.5, position.y),
+            ..default()
+        },
+    ));
+}
+
+pub fn click_marker_lifetime_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ClickMarker)>,
+) {
+    for (entity, mut marker) in query.iter_mut() {
+        marker.timer.tick(time.delta());
+        if marker.timer.finished() {
+            commands.add(Recycle(entity));
+        }
+    }
+}
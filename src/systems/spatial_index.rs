@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_rapier3d::prelude::Collider;
+use crate::components::{Enemy, Hero};
+
+/// Side of a grid cell. About the size of an engagement so radius queries touch few cells.
+pub const SPATIAL_CELL_SIZE: f32 = 4.0;
+/// Footprint assumed for units without a collider.
+pub const DEFAULT_UNIT_RADIUS: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitSide {
+    Friendly,
+    Enemy,
+}
+
+/// A unit's footprint on the X/Z plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialEntry {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub radius: f32,
+    pub side: UnitSide,
+}
+
+/// Uniform grid over unit positions on the X/Z plane, rebuilt once per tick by
+/// `spatial_index_system`. Lookups that would otherwise walk every unit go through here.
+#[derive(Resource, Debug)]
+pub struct SpatialIndex {
+    cell_size: f32,
+    entries: Vec<SpatialEntry>,
+    cells: HashMap<IVec2, Vec<usize>>, // Indices into `entries`
+    slots: HashMap<Entity, usize>,
+    max_radius: f32,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(SPATIAL_CELL_SIZE)
+    }
+}
+
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            entries: Vec::new(),
+            cells: HashMap::new(),
+            slots: HashMap::new(),
+            max_radius: 0.0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.cells.clear();
+        self.slots.clear();
+        self.max_radius = 0.0;
+    }
+
+    /// Adds a unit, filed under the cell holding its centre.
+    pub fn insert(&mut self, entry: SpatialEntry) {
+        let cell = self.cell_of(entry.position);
+        let slot = self.entries.len();
+        self.entries.push(entry);
+        self.cells.entry(cell).or_default().push(slot);
+        self.slots.insert(entry.entity, slot);
+        self.max_radius = self.max_radius.max(entry.radius);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&SpatialEntry> {
+        self.slots.get(&entity).map(|&slot| &self.entries[slot])
+    }
+
+    /// Units whose footprint overlaps the circle at `center`.
+    pub fn query_radius(&self, center: Vec2, radius: f32) -> Vec<&SpatialEntry> {
+        // Footprints are filed by centre, so widen the search by the largest one
+        let reach = radius + self.max_radius;
+        self.candidates(center - Vec2::splat(reach), center + Vec2::splat(reach))
+            .filter(|entry| entry.position.distance(center) <= radius + entry.radius)
+            .collect()
+    }
+
+    /// Units whose centre lies inside the rectangle spanned by `min` and `max`.
+    pub fn query_rect(&self, min: Vec2, max: Vec2) -> Vec<&SpatialEntry> {
+        let (min, max) = (min.min(max), min.max(max));
+        self.candidates(min, max)
+            .filter(|entry| entry.position.cmpge(min).all() && entry.position.cmple(max).all())
+            .collect()
+    }
+
+    /// Closest unit accepted by `filter` whose centre is within `max_distance`.
+    pub fn nearest(&self, center: Vec2, max_distance: f32, filter: impl Fn(&SpatialEntry) -> bool) -> Option<&SpatialEntry> {
+        self.query_radius(center, max_distance)
+            .into_iter()
+            .filter(|entry| entry.position.distance(center) <= max_distance && filter(entry))
+            .min_by(|a, b| a.position.distance(center).total_cmp(&b.position.distance(center)))
+    }
+
+    fn cell_of(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+
+    fn candidates(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = &SpatialEntry> {
+        let (min_cell, max_cell) = (self.cell_of(min), self.cell_of(max));
+        (min_cell.x..=max_cell.x)
+            .flat_map(move |x| (min_cell.y..=max_cell.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|&slot| &self.entries[slot])
+    }
+}
+
+/// Footprint radius on the X/Z plane from a unit's collider bounds.
+pub fn unit_radius(collider: Option<&Collider>) -> f32 {
+    collider.map_or(DEFAULT_UNIT_RADIUS, |collider| {
+        let half_extents = collider.raw.compute_local_aabb().half_extents();
+        half_extents.x.max(half_extents.z)
+    })
+}
+
+/// A hero or enemy as the index sees it: position, footprint and side.
+type IndexedUnit = (Entity, &'static Transform, Option<&'static Collider>, Has<Enemy>);
+type HeroOrEnemy = Or<(With<Hero>, With<Enemy>)>;
+
+/// Rebuilds the `SpatialIndex` from every hero and enemy.
+pub fn spatial_index_system(
+    mut index: ResMut<SpatialIndex>,
+    units: Query<IndexedUnit, HeroOrEnemy>,
+) {
+    index.clear();
+    for (entity, transform, collider, is_enemy) in units.iter() {
+        index.insert(SpatialEntry {
+            entity,
+            position: Vec2::new(transform.translation.x, transform.translation.z),
+            radius: unit_radius(collider),
+            side: if is_enemy { UnitSide::Enemy } else { UnitSide::Friendly },
+        });
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use crate::components::{Destructible, Health, Rubble};
+use crate::systems::navigation::{NavCell, NavigationGrid};
+
+/// Health fractions at which a structure shows its next damage stage.
+pub const DAMAGE_STAGE_THRESHOLDS: [f32; 2] = [0.66, 0.33];
+/// How much each damage stage darkens a structure.
+pub const DAMAGE_STAGE_DARKENING: f32 = 0.3;
+pub const RUBBLE_HEIGHT: f32 = 0.3;
+
+/// Mesh and material shared by every rubble pile. The mesh is a unit cube scaled to
+/// each fallen structure's footprint.
+#[derive(Resource, Clone)]
+pub struct RubbleAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for RubbleAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world.resource_mut::<Assets<Mesh>>().add(Mesh::from(shape::Cube { size: 1.0 }));
+        let material = world.resource_mut::<Assets<StandardMaterial>>().add(Color::rgb(0.25, 0.22, 0.2).into());
+        Self { mesh, material }
+    }
+}
+
+/// Number of damage thresholds `health` has fallen through.
+pub fn damage_stage(health: &Health) -> u8 {
+    let fraction = health.current / health.max;
+    DAMAGE_STAGE_THRESHOLDS.iter().filter(|threshold| fraction <= **threshold).count() as u8
+}
+
+/// Darkens structures as they pass each damage stage.
+pub fn structure_damage_stage_system(
+    mut query: Query<(&Health, &mut Destructible, &Handle<StandardMaterial>), Changed<Health>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (health, mut destructible, material) in query.iter_mut() {
+        let stage = damage_stage(health);
+        if stage == destructible.stage {
+            continue;
+        }
+        destructible.stage = stage;
+        if let Some(material) = materials.get_mut(material) {
+            let shade = 1.0 - DAMAGE_STAGE_DARKENING * stage as f32;
+            let [r, g, b, a] = destructible.base_color.as_rgba_f32();
+            material.base_color = Color::rgba(r * shade, g * shade, b * shade, a);
+        }
+    }
+}
+
+/// Replaces destroyed structures with low rubble that no longer blocks shots,
+/// and turns their footprint on the navigation grid into rough ground.
+pub fn structure_destruction_system(
+    mut commands: Commands,
+    rubble_assets: Res<RubbleAssets>,
+    mut navigation: Option<ResMut<NavigationGrid>>,
+    query: Query<(Entity, &Health, &Destructible, &Transform)>,
+) {
+    for (entity, health, destructible, transform) in query.iter() {
+        if health.current > 0.0 {
+            continue;
+        }
+        commands.entity(entity).despawn_recursive();
+
+        let footprint = destructible.half_extents.xz();
+        if let Some(navigation) = navigation.as_deref_mut() {
+            navigation.mark_footprint(transform, footprint, NavCell::Rough);
+        }
+
+        // Rest the rubble on the ground the structure stood on
+        let size = footprint * 2.0;
+        let rubble_transform = Transform {
+            translation: transform.translation - Vec3::Y * (destructible.half_extents.y - RUBBLE_HEIGHT / 2.0),
+            rotation: transform.rotation,
+            scale: Vec3::new(size.x, RUBBLE_HEIGHT, size.y),
+        };
+        commands.spawn((
+            Rubble,
+            PbrBundle {
+                mesh: rubble_assets.mesh.clone(),
+                material: rubble_assets.material.clone(),
+                transform: rubble_transform,
+                ..default()
+            },
+        ));
+        info!("Structure destroyed, leaving rubble at {:?}", transform.translation);
+    }
+}
@@ -0,0 +1,290 @@
+use bevy::prelude::*;
+use bevy::render::mesh::Indices;
+use bevy::render::render_resource::{PrimitiveTopology, TextureFormat};
+use bevy::render::texture::{CompressedImageFormats, ImageSampler, ImageType};
+use bevy_rapier3d::prelude::{Collider, CollisionGroups, Group, RigidBody};
+use crate::camera::ray_plane_intersection;
+use crate::components::{GroundClearance, Terrain};
+use crate::mech::MovementStats;
+
+/// Share of top speed left just below a lower body's `max_slope`.
+pub const MIN_SLOPE_SPEED_FACTOR: f32 = 0.25;
+/// Collision group holding the terrain collider.
+pub const TERRAIN_GROUP: Group = Group::GROUP_2;
+/// Shells and rockets strike the terrain collider like any other solid; picking rays hit it too.
+pub const TERRAIN_COLLISION_GROUPS: CollisionGroups = CollisionGroups::new(TERRAIN_GROUP, Group::ALL);
+/// Units and loose props filter the terrain out. `terrain_follow_system` keeps them on the
+/// ground instead, so ground contact never shoves the dynamic bodies standing on it.
+pub const UNIT_COLLISION_GROUPS: CollisionGroups = CollisionGroups::new(Group::ALL, Group::ALL.difference(TERRAIN_GROUP));
+
+const RAY_REFINE_STEPS: usize = 16;
+
+/// Ground heights sampled on a regular grid over the X/Z plane.
+/// Samples are stored row by row, rows running along +Z and columns along +X.
+#[derive(Resource, Debug, Clone)]
+pub struct Heightmap {
+    columns: usize,
+    rows: usize,
+    size: Vec2,
+    heights: Vec<f32>,
+    min_height: f32,
+    max_height: f32,
+}
+
+impl Heightmap {
+    pub fn new(columns: usize, rows: usize, size: Vec2, heights: Vec<f32>) -> Self {
+        assert!(columns >= 2 && rows >= 2, "A heightmap needs at least 2x2 samples");
+        assert_eq!(heights.len(), columns * rows, "Invalid number of heights provided");
+        let min_height = heights.iter().copied().fold(f32::INFINITY, f32::min);
+        let max_height = heights.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        Self { columns, rows, size, heights, min_height, max_height }
+    }
+
+    pub fn flat(size: Vec2) -> Self {
+        Self::new(2, 2, size, vec![0.0; 4])
+    }
+
+    /// Reads heights from the first channel of a grayscale (or colour) image, black at zero
+    /// and white at `max_height`. Image rows run from -Z at the top to +Z at the bottom.
+    /// Images smaller than 2x2 samples, short of data or in other formats give `None`.
+    pub fn from_image(image: &Image, size: Vec2, max_height: f32) -> Option<Self> {
+        let (stride, scale) = match image.texture_descriptor.format {
+            TextureFormat::R8Unorm => (1, u8::MAX as f32),
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => (4, u8::MAX as f32),
+            TextureFormat::R16Uint => (2, u16::MAX as f32),
+            _ => return None,
+        };
+        let (columns, rows) = (image.width() as usize, image.height() as usize);
+        if columns < 2 || rows < 2 {
+            return None;
+        }
+        let heights: Vec<f32> = image.data
+            .chunks_exact(stride)
+            .take(columns * rows)
+            .map(|pixel| {
+                let value = if image.texture_descriptor.format == TextureFormat::R16Uint {
+                    u16::from_le_bytes([pixel[0], pixel[1]]) as f32
+                } else {
+                    pixel[0] as f32
+                };
+                value / scale * max_height
+            })
+            .collect();
+        (heights.len() == columns * rows).then(|| Self::new(columns, rows, size, heights))
+    }
+
+    pub fn from_png(bytes: &[u8], size: Vec2, max_height: f32) -> Option<Self> {
+        let image = Image::from_buffer(
+            bytes,
+            ImageType::Extension("png"),
+            CompressedImageFormats::NONE,
+            false,
+            ImageSampler::Default,
+        ).ok()?;
+        Self::from_image(&image, size, max_height)
+    }
+
+    /// Loads the heightmap at `path`, falling back to flat ground if it can't be read.
+    pub fn load(path: &str, size: Vec2, max_height: f32) -> Self {
+        let heightmap = std::fs::read(path).ok().and_then(|bytes| Self::from_png(&bytes, size, max_height));
+        heightmap.unwrap_or_else(|| {
+            warn!("Could not load heightmap {}, using flat ground", path);
+            Self::flat(size)
+        })
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// Distance between neighbouring samples along X and Z.
+    pub fn cell_size(&self) -> Vec2 {
+        self.size / Vec2::new((self.columns - 1) as f32, (self.rows - 1) as f32)
+    }
+
+    pub fn contains(&self, position: Vec2) -> bool {
+        position.abs().cmple(self.size / 2.0).all()
+    }
+
+    fn sample(&self, column: usize, row: usize) -> f32 {
+        self.heights[row * self.columns + column]
+    }
+
+    fn sample_position(&self, column: usize, row: usize) -> Vec2 {
+        -self.size / 2.0 + self.cell_size() * Vec2::new(column as f32, row as f32)
+    }
+
+    /// Ground height at `position`, interpolated between samples and clamped to the edges.
+    pub fn height_at(&self, position: Vec2) -> f32 {
+        let grid = ((position + self.size / 2.0) / self.cell_size())
+            .clamp(Vec2::ZERO, Vec2::new((self.columns - 1) as f32, (self.rows - 1) as f32));
+        let column = (grid.x as usize).min(self.columns - 2);
+        let row = (grid.y as usize).min(self.rows - 2);
+        let t = grid - Vec2::new(column as f32, row as f32);
+
+        let near = self.sample(column, row) + (self.sample(column + 1, row) - self.sample(column, row)) * t.x;
+        let far = self.sample(column, row + 1) + (self.sample(column + 1, row + 1) - self.sample(column, row + 1)) * t.x;
+        near + (far - near) * t.y
+    }
+
+    /// Rise per unit of distance along X and Z.
+    pub fn gradient_at(&self, position: Vec2) -> Vec2 {
+        let step = self.cell_size() / 2.0;
+        Vec2::new(
+            (self.height_at(position + Vec2::X * step.x) - self.height_at(position - Vec2::X * step.x)) / (2.0 * step.x),
+            (self.height_at(position + Vec2::Y * step.y) - self.height_at(position - Vec2::Y * step.y)) / (2.0 * step.y),
+        )
+    }
+
+    pub fn normal_at(&self, position: Vec2) -> Vec3 {
+        let gradient = self.gradient_at(position);
+        Vec3::new(-gradient.x, 1.0, -gradient.y).normalize()
+    }
+
+    /// Steepest slope at `position`, in degrees.
+    pub fn slope_at(&self, position: Vec2) -> f32 {
+        self.gradient_at(position).length().atan().to_degrees()
+    }
+
+    /// Slope met when heading in `direction` from `position`, in degrees. Positive is uphill.
+    pub fn incline_along(&self, position: Vec2, direction: Vec2) -> f32 {
+        self.gradient_at(position).dot(direction.normalize_or_zero()).atan().to_degrees()
+    }
+
+    /// Whether a lower body with these stats can stand at `position`. Move orders to
+    /// ground that fails this are refused.
+    pub fn is_walkable(&self, position: Vec2, stats: &MovementStats) -> bool {
+        self.contains(position) && self.slope_at(position) < stats.max_slope
+    }
+
+    /// First point where a ray meets the ground. Marches the ray through the height range
+    /// half a cell at a time, then bisects the crossing.
+    pub fn ray_intersection(&self, origin: Vec3, direction: Vec3) -> Option<Vec3> {
+        if direction.y >= 0.0 {
+            return None;
+        }
+        let start = if origin.y > self.max_height {
+            ray_plane_intersection(origin, direction, self.max_height)?
+        } else {
+            origin
+        };
+        let end = ray_plane_intersection(origin, direction, self.min_height)?;
+        let above_ground = |point: Vec3| point.y > self.height_at(Vec2::new(point.x, point.z));
+        if !above_ground(start) {
+            return Some(start);
+        }
+
+        let length = start.distance(end);
+        let step = self.cell_size().min_element() / 2.0;
+        let steps = (length / step).ceil().max(1.0) as usize;
+        let direction = direction.normalize();
+        let mut previous = start;
+        for i in 1..=steps {
+            let mut next = start + direction * (length * i as f32 / steps as f32);
+            if above_ground(next) {
+                previous = next;
+                continue;
+            }
+            for _ in 0..RAY_REFINE_STEPS {
+                let middle = (previous + next) / 2.0;
+                if above_ground(middle) {
+                    previous = middle;
+                } else {
+                    next = middle;
+                }
+            }
+            return Some(next);
+        }
+        Some(end)
+    }
+
+    pub fn mesh(&self) -> Mesh {
+        let mut positions = Vec::with_capacity(self.heights.len());
+        let mut normals = Vec::with_capacity(self.heights.len());
+        let mut uvs = Vec::with_capacity(self.heights.len());
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let position = self.sample_position(column, row);
+                positions.push([position.x, self.sample(column, row), position.y]);
+                normals.push(self.normal_at(position).to_array());
+                uvs.push([column as f32 / (self.columns - 1) as f32, row as f32 / (self.rows - 1) as f32]);
+            }
+        }
+
+        let mut indices = Vec::with_capacity((self.columns - 1) * (self.rows - 1) * 6);
+        for row in 0..self.rows - 1 {
+            for column in 0..self.columns - 1 {
+                let near = (row * self.columns + column) as u32;
+                let far = near + self.columns as u32;
+                // Counter-clockwise seen from above
+                indices.extend_from_slice(&[near, far, near + 1, near + 1, far, far + 1]);
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.set_indices(Some(Indices::U32(indices)));
+        mesh
+    }
+
+    /// Rapier heightfield matching `mesh`. Rapier wants the heights column-major.
+    pub fn collider(&self) -> Collider {
+        let heights = (0..self.columns)
+            .flat_map(|column| (0..self.rows).map(move |row| (column, row)))
+            .map(|(column, row)| self.sample(column, row))
+            .collect();
+        Collider::heightfield(heights, self.rows, self.columns, Vec3::new(self.size.x, 1.0, self.size.y))
+    }
+}
+
+/// Speed multiplier for driving up `incline` degrees: full speed up to the lower body's
+/// `climb_slope`, tapering to `MIN_SLOPE_SPEED_FACTOR` at `max_slope` and stopped beyond it.
+/// Downhill never slows a unit, so it can always back off a slope it is stuck on.
+pub fn slope_speed_factor(incline: f32, stats: &MovementStats) -> f32 {
+    if incline <= stats.climb_slope {
+        1.0
+    } else if incline >= stats.max_slope {
+        0.0
+    } else {
+        let t = (incline - stats.climb_slope) / (stats.max_slope - stats.climb_slope);
+        1.0 + (MIN_SLOPE_SPEED_FACTOR - 1.0) * t
+    }
+}
+
+pub fn spawn_terrain(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    heightmap: &Heightmap,
+) -> Entity {
+    commands.spawn((
+        Terrain,
+        PbrBundle {
+            mesh: meshes.add(heightmap.mesh()),
+            material: materials.add(Color::rgb(0.1, 0.2, 0.1).into()),
+            ..default()
+        },
+        RigidBody::Fixed,
+        heightmap.collider(),
+        TERRAIN_COLLISION_GROUPS,
+    )).id()
+}
+
+/// Keeps units at their clearance above the ground as they move.
+pub fn terrain_follow_system(
+    heightmap: Option<Res<Heightmap>>,
+    mut query: Query<(&mut Transform, &GroundClearance)>,
+) {
+    let Some(heightmap) = heightmap else {
+        return;
+    };
+    for (mut transform, clearance) in query.iter_mut() {
+        let height = heightmap.height_at(Vec2::new(transform.translation.x, transform.translation.z)) + clearance.0;
+        // Only write on change so physics bodies aren't teleported every frame
+        if transform.translation.y != height {
+            transform.translation.y = height;
+        }
+    }
+}
@@ -1,164 +1,62 @@
 use bevy::prelude::*;
 use crate::mech::*;
-use crate::resources::*;
-use crate::components::{AttackTarget, Enemy};
+use crate::systems::angles::*;
+use crate::components::{AttackTarget, MoveTarget};
 
-pub fn calculate_turret_angle(mech_position: Vec2, target_position: Vec2) -> f32 {
-    let direction = target_position - mech_position;
-    direction.x.atan2(direction.y).to_degrees()
-}
-
-pub fn normalize_angle(angle: f32) -> f32 {
-    let mut normalized = angle % 360.0;
-    if normalized < 0.0 {
-        normalized += 360.0;
-    }
-    normalized
-}
-
-pub fn shortest_angle_difference(from: f32, to: f32) -> f32 {
-    let diff = to - from;
-    if diff > 180.0 {
-        diff - 360.0
-    } else if diff < -180.0 {
-        diff + 360.0
-    } else {
-        diff
-    }
-}
-
-pub fn rotate_towards_angle(
-    current_angle: f32,
-    target_angle: f32,
-    rotation_speed: f32,
-    delta_time: f32,
-) -> f32 {
-    let max_rotation = rotation_speed * delta_time;
-    let angle_diff = shortest_angle_difference(current_angle, target_angle);
-    
-    if angle_diff.abs() <= max_rotation {
-        target_angle
-    } else {
-        current_angle + max_rotation * angle_diff.signum()
-    }
-}
-
-pub fn turret_control_system(
-    mut set: ParamSet<(
-        Query<(&mut Transform, &GlobalTransform, &mut TurretRotation, &TurretCannon, &Parent)>,
-        Query<(&Transform, &GlobalTransform, Option<&AttackTarget>), Without<TurretRotation>>,
-        Query<&Transform, With<Enemy>>,
-    )>,
-    mouse_position: Res<MouseWorldPosition>,
-    time: Res<Time>,
+/// Turns the hull of an idle unit towards its target when the target has left
+/// the traverse arc of one of its `MechUpperBody` turrets.
+pub fn traverse_hull_to_target_system(
+    turret_query: Query<(Entity, &GlobalTransform, &MechUpperBody)>,
+    parent_query: Query<&Parent>,
+    attack_query: Query<&AttackTarget>,
+    target_query: Query<&GlobalTransform>,
+    mut hull_query: Query<(&GlobalTransform, &mut MechMovement, Has<MoveTarget>)>,
 ) {
-    let _mouse_pos = mouse_position.position;
-    
-    // Collect data we need from queries
-    let mut turret_data = Vec::new();
-    for (entity_index, (_transform, global_transform, turret_rotation, turret_cannon, parent)) in set.p0().iter().enumerate() {
-        turret_data.push((
-            entity_index,
-            parent.get(),
-            turret_rotation.current_angle,
-            turret_rotation.target_angle,
-            turret_cannon.rotation_speed,
-            Vec2::new(global_transform.translation().x, global_transform.translation().z),
-        ));
-    }
-    
-    if turret_data.is_empty() {
-        // No turrets found - might be a timing issue
-        return;
-    }
-    
-    // Process each turret and collect parent data
-    let mut parent_data = Vec::new();
-    for (entity_index, parent_entity, current_angle, _, rotation_speed, turret_position) in &turret_data {
-        // Get parent transform and attack target
-        if let Ok((transform, global_transform, attack_target)) = set.p1().get(*parent_entity) {
-            let attack_entity = attack_target.map(|at| at.entity);
-            // Extract the parent's world Y rotation from GlobalTransform
-            let (parent_y_rotation, _, _) = global_transform.to_scale_rotation_translation().1.to_euler(EulerRot::YXZ);
-            parent_data.push((*entity_index, *turret_position, attack_entity, *current_angle, *rotation_speed, parent_y_rotation));
+    for (turret_entity, turret_transform, upper_body) in turret_query.iter() {
+        let limits = upper_body.rotation_capability.limits;
+        if limits.traverse.is_none() {
+            continue;
         }
-    }
-    
-    // Get enemy positions
-    let mut enemy_positions = Vec::new();
-    for (entity_index, _turret_position, attack_entity, _current_angle, _rotation_speed, _parent_rotation) in &parent_data {
-        if let Some(enemy_entity) = attack_entity {
-            if let Ok(enemy_transform) = set.p2().get(*enemy_entity) {
-                enemy_positions.push((*entity_index, Vec2::new(enemy_transform.translation.x, enemy_transform.translation.z)));
+
+        // Walk up to the hull, picking up the attack target on the way
+        let mut attack_target = attack_query.get(turret_entity).ok().map(|target| target.entity);
+        let mut hull = None;
+        let mut current = turret_entity;
+        while let Ok(parent) = parent_query.get(current) {
+            current = parent.get();
+            attack_target = attack_target.or_else(|| attack_query.get(current).ok().map(|target| target.entity));
+            if hull_query.contains(current) {
+                hull = Some(current);
+                break;
             }
         }
-    }
-    
-    // Calculate updates
-    let mut updates = Vec::new();
-    for (entity_index, turret_position, attack_entity, current_angle, rotation_speed, parent_rotation) in parent_data {
-        let (target_position, has_valid_target) = if let Some(_) = attack_entity {
-            // Look for enemy position
-            if let Some((_, enemy_pos)) = enemy_positions.iter()
-                .find(|(idx, _)| *idx == entity_index) {
-                (*enemy_pos, true)
-            } else {
-                // Enemy not found but we have an attack target
-                // Maintain current angle by using a position that results in the current angle
-                let current_rad = current_angle.to_radians();
-                let maintain_pos = turret_position + Vec2::new(current_rad.cos(), current_rad.sin()) * 10.0;
-                (maintain_pos, false)
-            }
-        } else {
-            // No attack target - maintain current angle
-            let current_rad = current_angle.to_radians();
-            let maintain_pos = turret_position + Vec2::new(current_rad.cos(), current_rad.sin()) * 10.0;
-            (maintain_pos, false)
+
+        let (Some(hull), Some(attack_target)) = (hull, attack_target) else {
+            continue;
         };
-        
-        // Calculate world-space angle to target
+        let Ok(target_transform) = target_query.get(attack_target) else {
+            continue;
+        };
+
+        let turret_position = Vec2::new(turret_transform.translation().x, turret_transform.translation().z);
+        let target_position = Vec2::new(target_transform.translation().x, target_transform.translation().z);
         let world_target_angle = calculate_turret_angle(turret_position, target_position);
-        let world_target_angle_normalized = normalize_angle(world_target_angle);
-        
-        
-        // Convert parent rotation from radians to degrees
-        let parent_rotation_degrees = normalize_angle(parent_rotation.to_degrees());
-        
-        // Calculate the local turret angle by subtracting parent's rotation
-        // This converts world-space angle to local-space angle
-        let local_target_angle = normalize_angle(world_target_angle_normalized - parent_rotation_degrees);
-        
-        // Debug logging (commented out to reduce noise)
-        // info!("Turret control: world_angle={:.1}, parent_rot={:.1}, local_angle={:.1}", 
-        //       world_target_angle_normalized, parent_rotation_degrees, local_target_angle);
-        
-        // Current angle is already in local space
-        let current_angle_normalized = normalize_angle(current_angle);
-        
-        // Only rotate if we have a valid target or are following mouse
-        let new_angle = if has_valid_target {
-            rotate_towards_angle(
-                current_angle_normalized,
-                local_target_angle,
-                rotation_speed,
-                time.delta_seconds(),
-            )
-        } else {
-            // Maintain current angle when enemy is lost but attack target exists
-            current_angle_normalized
+
+        let Ok((hull_transform, mut mech_movement, has_move_target)) = hull_query.get_mut(hull) else {
+            continue;
         };
-        
-        // Store the new local angle and world target angle
-        // The world target angle is what we're aiming at in world space
-        updates.push((entity_index, new_angle, world_target_angle_normalized));
-    }
-    
-    // Apply updates
-    for (entity_index, new_angle, target_angle) in updates {
-        if let Some((mut transform, _, mut turret_rotation, _, _)) = set.p0().iter_mut().nth(entity_index) {
-            turret_rotation.current_angle = new_angle;
-            turret_rotation.target_angle = target_angle;
-            transform.rotation = Quat::from_rotation_y(new_angle.to_radians());
+        // Movement orders take priority over aiming
+        if has_move_target {
+            continue;
+        }
+        let hull_angle = yaw_degrees(hull_transform.to_scale_rotation_translation().1);
+        if limits.is_within_traverse(shortest_angle_difference(hull_angle, world_target_angle)) {
+            continue;
+        }
+
+        if mech_movement.movement_state == MechMovementState::Idle {
+            mech_movement.target_rotation = world_target_angle;
+            mech_movement.movement_state = MechMovementState::Rotating;
         }
     }
 }
@@ -1,48 +1,9 @@
 use bevy::prelude::*;
-use crate::mech::{MechUpperBody, MechRotation, TurretRotation};
+use crate::mech::{MechUpperBody, MechRotation, MechWeapon};
+use crate::systems::angles::*;
 use crate::components::{AttackTarget, Enemy};
 use crate::resources::MouseWorldPosition;
 
-pub fn calculate_turret_angle(mech_position: Vec2, target_position: Vec2) -> f32 {
-    let direction = target_position - mech_position;
-    direction.x.atan2(direction.y).to_degrees()
-}
-
-pub fn normalize_angle(angle: f32) -> f32 {
-    let mut normalized = angle % 360.0;
-    if normalized < 0.0 {
-        normalized += 360.0;
-    }
-    normalized
-}
-
-pub fn shortest_angle_difference(from: f32, to: f32) -> f32 {
-    let diff = to - from;
-    if diff > 180.0 {
-        diff - 360.0
-    } else if diff < -180.0 {
-        diff + 360.0
-    } else {
-        diff
-    }
-}
-
-pub fn rotate_towards_angle(
-    current_angle: f32,
-    target_angle: f32,
-    rotation_speed: f32,
-    delta_time: f32,
-) -> f32 {
-    let max_rotation = rotation_speed * delta_time;
-    let angle_diff = shortest_angle_difference(current_angle, target_angle);
-    
-    if angle_diff.abs() <= max_rotation {
-        target_angle
-    } else {
-        current_angle + max_rotation * angle_diff.signum()
-    }
-}
-
 pub fn upper_body_control_system(
     mut set: ParamSet<(
         Query<(&mut Transform, &GlobalTransform, &mut MechRotation, &MechUpperBody, &Parent)>,
@@ -60,7 +21,7 @@ pub fn upper_body_control_system(
             entity_index,
             parent.get(),
             rotation.current_angle,
-            rotation.target_angle,
+            upper_body.rotation_capability.limits,
             upper_body.rotation_capability.rotation_speed,
             Vec2::new(global_transform.translation().x, global_transform.translation().z),
         ));
@@ -71,16 +32,16 @@ pub fn upper_body_control_system(
     }
     
     let mut parent_data = Vec::new();
-    for (entity_index, parent_entity, current_angle, _, rotation_speed, upper_position) in &upper_data {
+    for (entity_index, parent_entity, current_angle, limits, rotation_speed, upper_position) in &upper_data {
         if let Ok((transform, attack_target)) = set.p1().get(*parent_entity) {
             let attack_entity = attack_target.map(|at| at.entity);
             let (parent_y_rotation, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
-            parent_data.push((*entity_index, *upper_position, attack_entity, *current_angle, *rotation_speed, parent_y_rotation));
+            parent_data.push((*entity_index, *upper_position, attack_entity, *current_angle, *limits, *rotation_speed, parent_y_rotation));
         }
     }
     
     let mut enemy_positions = Vec::new();
-    for (entity_index, _upper_position, attack_entity, _current_angle, _rotation_speed, _parent_rotation) in &parent_data {
+    for (entity_index, _upper_position, attack_entity, _current_angle, _limits, _rotation_speed, _parent_rotation) in &parent_data {
         if let Some(enemy_entity) = attack_entity {
             if let Ok(enemy_transform) = set.p2().get(*enemy_entity) {
                 enemy_positions.push((*entity_index, Vec2::new(enemy_transform.translation.x, enemy_transform.translation.z)));
@@ -89,7 +50,7 @@ pub fn upper_body_control_system(
     }
     
     let mut updates = Vec::new();
-    for (entity_index, upper_position, attack_entity, current_angle, rotation_speed, parent_rotation) in parent_data {
+    for (entity_index, upper_position, attack_entity, current_angle, limits, rotation_speed, parent_rotation) in parent_data {
         let (target_position, has_valid_target) = if let Some(_) = attack_entity {
             if let Some((_, enemy_pos)) = enemy_positions.iter()
                 .find(|(idx, _)| *idx == entity_index) {
@@ -114,9 +75,10 @@ pub fn upper_body_control_system(
         let current_angle_normalized = normalize_angle(current_angle);
         
         let new_angle = if has_valid_target {
-            rotate_towards_angle(
+            rotate_within_traverse(
                 current_angle_normalized,
                 local_target_angle,
+                &limits,
                 rotation_speed,
                 time.delta_seconds(),
             )
@@ -136,16 +98,34 @@ pub fn upper_body_control_system(
     }
 }
 
-// Adapter system to convert old TurretRotation to new MechRotation
-pub fn turret_rotation_adapter_system(
-    mut commands: Commands,
-    query: Query<(Entity, &TurretRotation), Without<MechRotation>>,
+/// Pitches the weapons on each upper body towards its mech's attack target, held
+/// inside the upper body's elevation limits, and levels them when there is no target.
+pub fn weapon_elevation_system(
+    upper_query: Query<(&GlobalTransform, &MechUpperBody, &Parent, &Children)>,
+    attack_query: Query<&AttackTarget>,
+    target_query: Query<&GlobalTransform>,
+    mut weapon_query: Query<&mut Transform, With<MechWeapon>>,
+    time: Res<Time>,
 ) {
-    for (entity, turret_rotation) in query.iter() {
-        commands.entity(entity).insert(MechRotation {
-            target_angle: turret_rotation.target_angle,
-            current_angle: turret_rotation.current_angle,
-        });
+    for (upper_transform, upper_body, parent, children) in upper_query.iter() {
+        let capability = &upper_body.rotation_capability;
+        let target_elevation = attack_query
+            .get(parent.get())
+            .ok()
+            .and_then(|attack_target| target_query.get(attack_target.entity).ok())
+            .map(|target| required_elevation(upper_transform.translation(), target.translation()))
+            .map_or(0.0, |elevation| capability.limits.clamp_elevation(elevation));
+
+        for child in children.iter() {
+            let Ok(mut weapon_transform) = weapon_query.get_mut(*child) else {
+                continue;
+            };
+            // Positive elevation tilts the barrel's +Z up, a negative turn about X
+            let current_elevation = -weapon_transform.rotation.to_euler(EulerRot::YXZ).1.to_degrees();
+            let max_step = capability.rotation_speed * time.delta_seconds();
+            let step = (target_elevation - current_elevation).clamp(-max_step, max_step);
+            weapon_transform.rotation = Quat::from_rotation_x(-(current_elevation + step).to_radians());
+        }
     }
 }
 
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use std::f32::consts::PI;
 use crate::components::{ShellFragment, Projectile, HitFlash};
+use crate::systems::projectile_pool::Recycle;
 
 pub fn calculate_fragment_directions(impact_velocity: Vec2, surface_normal: Vec2) -> Vec<Vec2> {
     let impact_dir = impact_velocity.normalize();
@@ -39,6 +40,11 @@ pub fn calculate_fragment_velocities(parent_velocity: Vec2, surface_normal: Vec2
         .collect()
 }
 
+pub fn calculate_fragment_damage(parent_damage: f32) -> f32 {
+    // The shell's damage is split between its three fragments
+    parent_damage / 3.0
+}
+
 pub fn calculate_fragment_lifetime(parent_range: f32) -> f32 {
     // Fragment travels 15% of parent range (middle of 10-20% range)
     parent_range * 0.15
@@ -58,7 +64,7 @@ fn rotate_vec2(v: Vec2, angle: f32) -> Vec2 {
     )
 }
 
-fn reflect_vector(incident: Vec2, normal: Vec2) -> Vec2 {
+pub fn reflect_vector(incident: Vec2, normal: Vec2) -> Vec2 {
     incident - 2.0 * incident.dot(normal) * normal
 }
 
@@ -75,25 +81,19 @@ pub fn fragment_lifetime_system(
         
         // Despawn if lifetime expired or max distance reached
         if fragment.lifetime.finished() || distance_traveled >= fragment.max_distance {
-            commands.entity(entity).despawn();
+            commands.add(Recycle(entity));
         }
     }
 }
 
-// TODO: Add visual fade system for fragments
+/// Shrinks fragments away over their lifetime. Fragments share one material,
+/// so fading it would fade every fragment at once.
 pub fn fragment_visual_fade_system(
-    mut query: Query<(&ShellFragment, &Handle<StandardMaterial>)>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut query: Query<(&ShellFragment, &mut Transform)>,
 ) {
-    for (fragment, material_handle) in query.iter() {
-        if let Some(material) = materials.get_mut(material_handle) {
-            // Calculate fade based on lifetime remaining
-            let alpha = 1.0 - fragment.lifetime.percent();
-            material.base_color.set_a(alpha);
-            
-            // Optional: Also reduce emissive intensity
-            material.emissive = Color::rgb(1.0 * alpha, 0.8 * alpha, 0.0);
-        }
+    for (fragment, mut transform) in query.iter_mut() {
+        let remaining = 1.0 - fragment.lifetime.percent();
+        transform.scale = Vec3::splat(remaining.max(0.1));
     }
 }
 
@@ -106,7 +106,8 @@ pub fn hit_flash_system(
         hit_flash.timer.tick(time.delta());
         
         if hit_flash.timer.finished() {
-            commands.entity(entity).despawn();
+            // Pooled impact flashes are parked, anything else is despawned
+            commands.add(Recycle(entity));
         }
     }
 }
\ No newline at end of file
@@ -1,55 +1,89 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
-use crate::components::{Hero, Enemy, Projectile, TankShell, AttackTarget};
-use crate::mech::{MechWeapon, MechUpperBody, MechRotation, CannonWeapon};
+use crate::camera::{CameraShakeEvent, CameraShakeSource};
+use crate::components::{Hero, Enemy, Projectile, FiredBy, TankShell, AttackTarget, FragmentShell};
+use crate::mech::{AmmoType, Heat, MechWeapon, MechUpperBody, MechRotation, CannonWeapon, WeaponAmmo};
+use crate::systems::ammo::take_round;
+use crate::systems::angles::required_elevation;
+use crate::systems::fog_of_war::{can_target, FogOfWar};
+use crate::systems::heat::{heat_allows_fire, scatter_direction};
+use crate::systems::projectile_pool::{PoolKind, ProjectileAssets, ProjectilePool};
+use crate::systems::spatial_index::UnitSide;
 use crate::systems::upper_body_control::is_upper_facing_target;
+use crate::systems::weapon_range::FIRING_ANGLE_TOLERANCE;
 
-const ATTACK_RANGE: f32 = 10.0;
-const ANGLE_TOLERANCE: f32 = 5.0;
+/// Advances every weapon's time since it last fired.
+pub fn weapon_cooldown_system(time: Res<Time>, mut weapon_query: Query<&mut MechWeapon>) {
+    for mut weapon in weapon_query.iter_mut() {
+        weapon.last_fire_time += time.delta_seconds();
+    }
+}
+
+/// What firing a cannon draws on: the shared shell handles, the shell pool and the
+/// camera shake every shot kicks off.
+#[derive(SystemParam)]
+pub struct CannonFire<'w> {
+    assets: Res<'w, ProjectileAssets>,
+    pool: ResMut<'w, ProjectilePool>,
+    shake_events: EventWriter<'w, CameraShakeEvent>,
+}
+
+/// A hero that is aiming: its attack target and, for mechs that run hot, its heat.
+type AimingHero = (Entity, &'static Transform, &'static Children, &'static AttackTarget, Option<&'static mut Heat>);
 
+/// Fires the hero's weapons at its attack target. A weapon fires once the target
+/// is inside its own range, its cooldown has elapsed, the upper body faces it and
+/// the mech is cool enough. Every shot heats the mech and hot mechs scatter their shots.
+/// Targets hidden in the fog of war are held fire on until they are seen again.
 pub fn weapon_control_system(
     mut commands: Commands,
-    time: Res<Time>,
-    hero_query: Query<(&Transform, &Children, &AttackTarget), With<Hero>>,
-    upper_query: Query<(&Transform, &GlobalTransform, &MechUpperBody, &MechRotation, &Children)>,
-    mut weapon_query: Query<(&mut MechWeapon, &CannonWeapon, &Parent)>,
+    mut hero_query: Query<AimingHero, With<Hero>>,
+    upper_query: Query<(&GlobalTransform, &MechUpperBody, &MechRotation, &Children)>,
+    mut weapon_query: Query<(&mut MechWeapon, &CannonWeapon, Option<&mut WeaponAmmo>)>,
     enemy_query: Query<&Transform, With<Enemy>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    fog: Option<Res<FogOfWar>>,
+    mut cannon_fire: CannonFire,
 ) {
-    let delta_time = time.delta_seconds();
-    
-    for (hero_transform, children, attack_target) in hero_query.iter() {
+    for (hero_entity, hero_transform, children, attack_target, mut heat) in hero_query.iter_mut() {
+        if !can_target(fog.as_deref(), UnitSide::Friendly, attack_target.entity) {
+            continue;
+        }
         if let Ok(enemy_transform) = enemy_query.get(attack_target.entity) {
             let hero_pos = Vec2::new(hero_transform.translation.x, hero_transform.translation.z);
             let enemy_pos = Vec2::new(enemy_transform.translation.x, enemy_transform.translation.z);
             let distance = hero_pos.distance(enemy_pos);
             
-            if distance <= ATTACK_RANGE {
-                for child in children {
-                    if let Ok((upper_transform, global_upper_transform, _upper_body, _rotation, upper_children)) = upper_query.get(*child) {
-                        let upper_position = Vec2::new(global_upper_transform.translation().x, global_upper_transform.translation().z);
-                        let is_facing = is_upper_facing_target(upper_transform, upper_position, enemy_pos, ANGLE_TOLERANCE);
-                        
-                        if is_facing {
-                            for upper_child in upper_children {
-                                if let Ok((mut weapon, cannon, _parent)) = weapon_query.get_mut(*upper_child) {
-                                    weapon.last_fire_time += delta_time;
-                                    
-                                    if weapon.last_fire_time >= weapon.weapon_stats.fire_rate {
-                                        fire_cannon(
-                                            &mut commands,
-                                            &global_upper_transform,
-                                            upper_transform,
-                                            weapon.as_ref(),
-                                            cannon,
-                                            enemy_pos,
-                                            &mut meshes,
-                                            &mut materials,
-                                        );
-                                        
-                                        weapon.last_fire_time = 0.0;
+            for child in children {
+                if let Ok((global_upper_transform, upper_body, _rotation, upper_children)) = upper_query.get(*child) {
+                    // Facing is checked in world space so a turning chassis is accounted for
+                    let (_, rotation, translation) = global_upper_transform.to_scale_rotation_translation();
+                    let world_transform = Transform::from_translation(translation).with_rotation(rotation);
+                    let upper_position = Vec2::new(translation.x, translation.z);
+                    let is_facing = is_upper_facing_target(&world_transform, upper_position, enemy_pos, FIRING_ANGLE_TOLERANCE);
+                    let elevation = required_elevation(translation, enemy_transform.translation);
+                    let can_elevate = upper_body.rotation_capability.limits.is_within_elevation(elevation);
+                    
+                    if is_facing && can_elevate {
+                        for upper_child in upper_children {
+                            if let Ok((mut weapon, cannon, ammo)) = weapon_query.get_mut(*upper_child) {
+                                // Each weapon engages at its own range
+                                if distance <= weapon.weapon_stats.range
+                                    && weapon.last_fire_time >= weapon.weapon_stats.fire_rate
+                                    && heat_allows_fire(heat.as_deref())
+                                {
+                                    // Weapons without an ammo state fire without limit
+                                    let Some(ammo_type) = take_round(ammo) else {
+                                        continue;
+                                    };
+                                    let muzzle = weapon_muzzle_position(global_upper_transform, &weapon, cannon.barrel_length);
+                                    let mut direction = (enemy_pos - Vec2::new(muzzle.x, muzzle.z)).normalize();
+                                    if let Some(heat) = heat.as_mut() {
+                                        direction = scatter_direction(direction, heat.accuracy_spread());
+                                        heat.add(weapon.weapon_stats.heat_per_shot);
                                     }
+                                    fire_cannon(&mut commands, &mut cannon_fire, hero_entity, muzzle, direction, &weapon, ammo_type);
+                                    weapon.last_fire_time = 0.0;
                                 }
                             }
                         }
@@ -60,38 +94,39 @@ pub fn weapon_control_system(
     }
 }
 
-fn fire_cannon(
+/// Spawns a shell of `ammo_type` fired by the mech rooted at `shooter` from `muzzle`,
+/// travelling along `direction` (X/Z plane) with the weapon's damage, speed and range
+/// scaled by the ammo type, and shakes the camera with the recoil.
+pub fn fire_cannon(
     commands: &mut Commands,
-    global_upper_transform: &GlobalTransform,
-    local_upper_transform: &Transform,
+    cannon_fire: &mut CannonFire,
+    shooter: Entity,
+    muzzle: Vec3,
+    direction: Vec2,
     weapon: &MechWeapon,
-    cannon: &CannonWeapon,
-    enemy_pos: Vec2,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
+    ammo_type: AmmoType,
 ) {
-    let barrel_tip = get_barrel_tip_position(global_upper_transform, local_upper_transform, weapon, cannon.barrel_length);
-    let spawn_pos = Vec2::new(barrel_tip.x, barrel_tip.z);
-    
-    let direction = (enemy_pos - spawn_pos).normalize();
-    let shell_velocity = direction * weapon.weapon_stats.projectile_speed;
+    let spawn_pos = Vec2::new(muzzle.x, muzzle.z);
+    let speed = weapon.weapon_stats.projectile_speed * ammo_type.speed_multiplier();
+    let shell_velocity = direction * speed;
     
-    let shell_mesh = meshes.add(Mesh::from(shape::Box::new(0.4, 0.2, 0.4)));
-    let shell_material = materials.add(Color::rgb(1.0, 1.0, 0.0).into());
-    
-    commands.spawn((
+    let assets = &cannon_fire.assets;
+    let mut shell = cannon_fire.pool.spawn(commands, PoolKind::Shell);
+    shell.insert((
         Projectile {
-            damage: weapon.weapon_stats.damage,
-            speed: weapon.weapon_stats.projectile_speed,
+            damage: weapon.weapon_stats.damage * ammo_type.damage_multiplier(),
+            speed,
         },
         TankShell {
             velocity: shell_velocity,
             spawn_position: spawn_pos,
             max_range: weapon.weapon_stats.range,
         },
+        ammo_type,
+        FiredBy(shooter),
         PbrBundle {
-            mesh: shell_mesh,
-            material: shell_material,
+            mesh: assets.shell_mesh.clone(),
+            material: assets.shell_material.clone(),
             transform: Transform::from_xyz(spawn_pos.x, 0.75, spawn_pos.y),
             ..default()
         },
@@ -109,14 +144,21 @@ fn fire_cannon(
         GravityScale(0.3),
         ActiveEvents::COLLISION_EVENTS
     ));
+    if ammo_type == AmmoType::Fragmentation {
+        shell.insert(FragmentShell);
+    }
+    cannon_fire.shake_events.send(CameraShakeEvent {
+        source: CameraShakeSource::HeavyWeaponFire,
+        position: muzzle,
+    });
     
     info!("Cannon fired from hardpoint {} at pos ({}, {}, {}) with velocity {:?}", 
           weapon.hardpoint_id, spawn_pos.x, 0.75, spawn_pos.y, shell_velocity);
 }
 
-fn get_barrel_tip_position(
+/// World position of the muzzle of `weapon`, mounted on the upper body at `global_transform`.
+pub fn weapon_muzzle_position(
     global_transform: &GlobalTransform,
-    local_transform: &Transform,
     weapon: &MechWeapon,
     barrel_length: f32,
 ) -> Vec3 {
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use crate::components::{AttackTarget, Selected};
+use crate::mech::{MechUpperBody, MechWeapon};
+use crate::systems::picking::find_descendants;
+
+pub const FIRING_ANGLE_TOLERANCE: f32 = 5.0; // degrees either side of the barrel
+pub const RANGE_RING_HEIGHT: f32 = 0.05;
+pub const FIRING_ARC_SEGMENTS: usize = 8;
+
+/// Range of every weapon mounted on `entity` or its descendants.
+pub fn weapon_ranges(
+    entity: Entity,
+    children_query: &Query<&Children>,
+    weapon_query: &Query<&MechWeapon>,
+) -> Vec<f32> {
+    std::iter::once(entity)
+        .chain(find_descendants(entity, children_query))
+        .filter_map(|part| weapon_query.get(part).ok())
+        .map(|weapon| weapon.weapon_stats.range)
+        .collect()
+}
+
+/// The distance a unit closes to before engaging: its shortest weapon range,
+/// so every weapon can fire once it stops.
+pub fn engagement_range(ranges: &[f32]) -> Option<f32> {
+    ranges.iter().copied().reduce(f32::min)
+}
+
+/// Outline of a firing cone on the ground: origin, the arc at `range`, and back to the origin.
+/// `facing_degrees` follows the turret convention (0 = +Z, measured with atan2(x, z)).
+pub fn firing_arc_points(origin: Vec3, facing_degrees: f32, half_angle_degrees: f32, range: f32, segments: usize) -> Vec<Vec3> {
+    let mut points = Vec::with_capacity(segments + 3);
+    points.push(origin);
+    for i in 0..=segments {
+        let t = i as f32 / segments as f32;
+        let angle = (facing_degrees - half_angle_degrees + t * 2.0 * half_angle_degrees).to_radians();
+        points.push(origin + Vec3::new(angle.sin(), 0.0, angle.cos()) * range);
+    }
+    points.push(origin);
+    points
+}
+
+fn ground_position(transform: &GlobalTransform) -> Vec3 {
+    let translation = transform.translation();
+    Vec3::new(translation.x, RANGE_RING_HEIGHT, translation.z)
+}
+
+fn facing_degrees(transform: &GlobalTransform) -> f32 {
+    let forward = transform.to_scale_rotation_translation().1 * Vec3::Z;
+    forward.x.atan2(forward.z).to_degrees()
+}
+
+/// Draws a ground ring at each weapon's range around the selected units.
+pub fn weapon_range_ring_system(
+    mut gizmos: Gizmos,
+    selected_query: Query<Entity, With<Selected>>,
+    children_query: Query<&Children>,
+    weapon_query: Query<(&MechWeapon, &GlobalTransform)>,
+) {
+    for selected in selected_query.iter() {
+        for entity in std::iter::once(selected).chain(find_descendants(selected, &children_query)) {
+            if let Ok((weapon, transform)) = weapon_query.get(entity) {
+                gizmos.circle(ground_position(transform), Vec3::Y, weapon.weapon_stats.range, Color::rgba(1.0, 0.8, 0.2, 0.8));
+            }
+        }
+    }
+}
+
+/// Draws the facing tolerance cone for turrets that are aiming at a target.
+/// Shots are only fired while the target sits inside this cone.
+pub fn firing_arc_system(
+    mut gizmos: Gizmos,
+    upper_query: Query<(&GlobalTransform, &Parent, &Children), With<MechUpperBody>>,
+    aiming_query: Query<(), With<AttackTarget>>,
+    weapon_query: Query<&MechWeapon>,
+) {
+    let color = Color::rgba(0.3, 0.9, 1.0, 0.8);
+
+    for (transform, parent, children) in upper_query.iter() {
+        if aiming_query.get(parent.get()).is_err() {
+            continue;
+        }
+        let longest_range = children
+            .iter()
+            .filter_map(|child| weapon_query.get(*child).ok())
+            .map(|weapon| weapon.weapon_stats.range)
+            .reduce(f32::max);
+        if let Some(range) = longest_range {
+            let points = firing_arc_points(ground_position(transform), facing_degrees(transform), FIRING_ANGLE_TOLERANCE, range, FIRING_ARC_SEGMENTS);
+            gizmos.linestrip(points, color);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use crate::game_mode::{AppState, ObjectiveTracker, WaveDirector};
+use crate::resources::{GameOutcome, GameState};
+
+pub const GAME_HUD_MARGIN: f32 = 10.0; // pixels
+
+/// Banner at the top of the screen: the menu, pause and game-over prompts, or the
+/// wave, score, clock and active objectives while playing.
+#[derive(Component)]
+pub struct GameHudText;
+
+pub struct GameHudPlugin;
+
+impl Plugin for GameHudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_game_hud)
+            .add_systems(Update, game_hud_system);
+    }
+}
+
+/// `mm:ss`
+pub fn format_game_clock(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+pub fn game_hud_label(
+    state: AppState,
+    game_state: &GameState,
+    director: &WaveDirector,
+    objectives: &ObjectiveTracker,
+) -> String {
+    match state {
+        AppState::MainMenu => "RUST AND RUIN\nPress Enter to start".to_string(),
+        AppState::Paused => "PAUSED\nPress Esc to resume".to_string(),
+        AppState::Playing => {
+            let next_wave = director
+                .time_to_next_wave()
+                .map(|seconds| format!("   Next wave in {:.0}s", seconds.ceil()))
+                .unwrap_or_default();
+            let mut label = format!(
+                "WAVE {}/{}   SCORE {}   {}{}",
+                director.waves_spawned(),
+                director.waves().len(),
+                game_state.score,
+                format_game_clock(game_state.game_time),
+                next_wave,
+            );
+            for objective in objectives.active() {
+                label.push_str(&format!("\n> {}", objective.definition.description));
+            }
+            label
+        }
+        AppState::GameOver => {
+            let title = match game_state.outcome {
+                Some(GameOutcome::Victory) => "VICTORY",
+                Some(GameOutcome::Defeat) | None => "DEFEAT",
+            };
+            format!(
+                "{}\nScore {} in {}\nPress Enter to return to the menu",
+                title,
+                game_state.score,
+                format_game_clock(game_state.game_time),
+            )
+        }
+    }
+}
+
+pub fn setup_game_hud(mut commands: Commands) {
+    commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(GAME_HUD_MARGIN),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        ..default()
+    }).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ).with_text_alignment(TextAlignment::Center),
+            GameHudText,
+        ));
+    });
+}
+
+pub fn game_hud_system(
+    state: Res<State<AppState>>,
+    game_state: Res<GameState>,
+    director: Res<WaveDirector>,
+    objectives: Res<ObjectiveTracker>,
+    mut text_query: Query<&mut Text, With<GameHudText>>,
+) {
+    let label = game_hud_label(*state.get(), &game_state, &director, &objectives);
+    for mut text in text_query.iter_mut() {
+        if text.sections[0].value != label {
+            text.sections[0].value = label.clone();
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use bevy::prelude::*;
+use crate::components::{Health, Enemy, Selected, DamageEvent};
+
+pub const HEALTH_BAR_TOGGLE_KEY: KeyCode = KeyCode::H;
+pub const HEALTH_BAR_WIDTH: f32 = 1.2;
+pub const HEALTH_BAR_HEIGHT: f32 = 0.15;
+pub const HEALTH_BAR_OFFSET: f32 = 1.5; // above the unit's origin
+pub const DAMAGE_NUMBER_LIFETIME: f32 = 1.0; // seconds
+pub const DAMAGE_NUMBER_RISE_SPEED: f32 = 1.5; // world units per second
+
+/// `show_all` shows every bar; otherwise only damaged or selected units get one.
+#[derive(Resource, Debug, Default)]
+pub struct HealthBarSettings {
+    pub show_all: bool,
+}
+
+/// Billboarded bar floating above `target`.
+#[derive(Component)]
+pub struct HealthBar {
+    pub target: Entity,
+}
+
+#[derive(Component)]
+pub struct HealthBarFill;
+
+/// Quad and material handles shared by every health bar, so units spawning
+/// in does not add new assets.
+#[derive(Resource, Clone)]
+pub struct HealthBarAssets {
+    pub quad: Handle<Mesh>,
+    pub background_material: Handle<StandardMaterial>,
+    pub friendly_fill_material: Handle<StandardMaterial>,
+    pub enemy_fill_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for HealthBarAssets {
+    fn from_world(world: &mut World) -> Self {
+        let quad = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Mesh::from(shape::Quad::new(Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT))));
+
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let mut fill_material = |is_enemy| materials.add(StandardMaterial {
+            base_color: health_bar_color(is_enemy),
+            unlit: true,
+            ..default()
+        });
+        let friendly_fill_material = fill_material(false);
+        let enemy_fill_material = fill_material(true);
+        Self {
+            quad,
+            background_material: materials.add(StandardMaterial {
+                base_color: Color::rgba(0.0, 0.0, 0.0, 0.7),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            }),
+            friendly_fill_material,
+            enemy_fill_material,
+        }
+    }
+}
+
+impl HealthBarAssets {
+    pub fn fill_material(&self, is_enemy: bool) -> Handle<StandardMaterial> {
+        if is_enemy {
+            self.enemy_fill_material.clone()
+        } else {
+            self.friendly_fill_material.clone()
+        }
+    }
+}
+
+/// Screen-space text that floats up from `world_position` and fades out.
+#[derive(Component)]
+pub struct DamageNumber {
+    pub world_position: Vec3,
+    pub lifetime: Timer,
+}
+
+pub struct HealthBarPlugin;
+
+impl Plugin for HealthBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>()
+            .init_resource::<HealthBarSettings>()
+            .init_resource::<HealthBarAssets>()
+            .add_systems(Update, (
+                health_bar_toggle_system,
+                spawn_health_bars_system,
+                update_health_bars_system,
+                spawn_damage_numbers_system,
+                update_damage_numbers_system,
+            ).chain());
+    }
+}
+
+pub fn health_fraction(health: &Health) -> f32 {
+    if health.max <= 0.0 {
+        return 0.0;
+    }
+    (health.current / health.max).clamp(0.0, 1.0)
+}
+
+pub fn is_health_bar_visible(health: &Health, selected: bool, show_all: bool) -> bool {
+    show_all || selected || health.current < health.max
+}
+
+pub fn health_bar_color(is_enemy: bool) -> Color {
+    if is_enemy {
+        Color::rgb(0.9, 0.15, 0.15)
+    } else {
+        Color::rgb(0.2, 0.8, 0.3)
+    }
+}
+
+/// Local transform of the fill quad for the given health fraction, keeping it left-aligned.
+pub fn health_bar_fill_transform(fraction: f32) -> Transform {
+    Transform::from_xyz(-(1.0 - fraction) * HEALTH_BAR_WIDTH * 0.5, 0.0, 0.01)
+        .with_scale(Vec3::new(fraction.max(0.001), 1.0, 1.0))
+}
+
+pub fn health_bar_toggle_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut settings: ResMut<HealthBarSettings>,
+) {
+    if keyboard_input.just_pressed(HEALTH_BAR_TOGGLE_KEY) {
+        settings.show_all = !settings.show_all;
+        info!("Health bars: {}", if settings.show_all { "all units" } else { "damaged/selected only" });
+    }
+}
+
+pub fn spawn_health_bars_system(
+    mut commands: Commands,
+    new_health_query: Query<(Entity, Has<Enemy>), Added<Health>>,
+    assets: Res<HealthBarAssets>,
+) {
+    for (target, is_enemy) in new_health_query.iter() {
+        commands.spawn((
+            HealthBar { target },
+            SpatialBundle {
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+        )).with_children(|parent| {
+            parent.spawn(PbrBundle {
+                mesh: assets.quad.clone(),
+                material: assets.background_material.clone(),
+                ..default()
+            });
+            parent.spawn((
+                HealthBarFill,
+                PbrBundle {
+                    mesh: assets.quad.clone(),
+                    material: assets.fill_material(is_enemy),
+                    transform: health_bar_fill_transform(1.0),
+                    ..default()
+                },
+            ));
+        });
+    }
+}
+
+/// What a health bar needs to know about the unit it hangs over.
+type HealthBarTarget = (&'static Health, &'static GlobalTransform, Has<Selected>, Option<&'static Visibility>);
+
+/// Moves bars above their targets, faces them towards the camera and updates the fill.
+pub fn update_health_bars_system(
+    mut commands: Commands,
+    settings: Res<HealthBarSettings>,
+    camera_query: Query<&GlobalTransform, With<Camera3d>>,
+    target_query: Query<HealthBarTarget, Without<HealthBar>>,
+    mut bar_query: Query<(Entity, &HealthBar, &mut Transform, &mut Visibility, &Children), Without<HealthBarFill>>,
+    mut fill_query: Query<&mut Transform, With<HealthBarFill>>,
+) {
+    let camera_rotation = camera_query
+        .get_single()
+        .map(|camera| camera.to_scale_rotation_translation().1)
+        .unwrap_or_default();
+
+    for (bar_entity, bar, mut transform, mut visibility, children) in bar_query.iter_mut() {
+        let Ok((health, target_transform, selected, target_visibility)) = target_query.get(bar.target) else {
+            commands.entity(bar_entity).despawn_recursive();
+            continue;
+        };
+
+        transform.translation = target_transform.translation() + Vec3::Y * HEALTH_BAR_OFFSET;
+        transform.rotation = camera_rotation;
+        // Targets hidden in the fog of war don't give themselves away with a bar
+        let target_hidden = target_visibility == Some(&Visibility::Hidden);
+        *visibility = if !target_hidden && is_health_bar_visible(health, selected, settings.show_all) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        let fraction = health_fraction(health);
+        for child in children.iter() {
+            if let Ok(mut fill_transform) = fill_query.get_mut(*child) {
+                *fill_transform = health_bar_fill_transform(fraction);
+            }
+        }
+    }
+}
+
+pub fn spawn_damage_numbers_system(
+    mut commands: Commands,
+    mut damage_events: EventReader<DamageEvent>,
+) {
+    for event in damage_events.read() {
+        commands.spawn((
+            DamageNumber {
+                world_position: event.position + Vec3::Y * HEALTH_BAR_OFFSET,
+                lifetime: Timer::from_seconds(DAMAGE_NUMBER_LIFETIME, TimerMode::Once),
+            },
+            TextBundle::from_section(
+                format!("{:.0}", event.amount),
+                TextStyle {
+                    font_size: 18.0,
+                    color: Color::rgb(1.0, 0.9, 0.2),
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                ..default()
+            }),
+        ));
+    }
+}
+
+/// Floats damage numbers upwards in world space and pins the text to their projected screen position.
+pub fn update_damage_numbers_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut number_query: Query<(Entity, &mut DamageNumber, &mut Style, &mut Text)>,
+) {
+    let camera = camera_query.get_single().ok();
+
+    for (entity, mut number, mut style, mut text) in number_query.iter_mut() {
+        number.lifetime.tick(time.delta());
+        if number.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        number.world_position.y += DAMAGE_NUMBER_RISE_SPEED * time.delta_seconds();
+
+        let screen_position = camera
+            .and_then(|(camera, camera_transform)| camera.world_to_viewport(camera_transform, number.world_position));
+        match screen_position {
+            Some(position) => {
+                style.display = Display::Flex;
+                style.left = Val::Px(position.x);
+                style.top = Val::Px(position.y);
+            }
+            None => style.display = Display::None,
+        }
+
+        let alpha = 1.0 - number.lifetime.percent();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(alpha);
+        }
+    }
+}
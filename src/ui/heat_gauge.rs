@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use crate::components::Hero;
+use crate::mech::{Heat, HeatLevel, HOT_FRACTION, LOCKOUT_FRACTION};
+
+pub const HEAT_GAUGE_WIDTH: f32 = 240.0; // pixels
+pub const HEAT_GAUGE_HEIGHT: f32 = 14.0;
+pub const HEAT_GAUGE_MARGIN: f32 = 10.0;
+
+/// Bar at the bottom of the screen showing the hero's heat.
+#[derive(Component)]
+pub struct HeatGauge;
+
+#[derive(Component)]
+pub struct HeatGaugeFill;
+
+#[derive(Component)]
+pub struct HeatGaugeText;
+
+pub struct HeatGaugePlugin;
+
+impl Plugin for HeatGaugePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_heat_gauge)
+            .add_systems(Update, heat_gauge_system);
+    }
+}
+
+pub fn heat_gauge_color(level: HeatLevel) -> Color {
+    match level {
+        HeatLevel::Normal => Color::rgb(0.3, 0.7, 1.0),
+        HeatLevel::Hot => Color::rgb(1.0, 0.7, 0.1),
+        HeatLevel::Lockout => Color::rgb(1.0, 0.25, 0.1),
+        HeatLevel::Shutdown => Color::rgb(0.5, 0.5, 0.5),
+    }
+}
+
+pub fn heat_gauge_label(heat: &Heat) -> String {
+    let level = heat.level();
+    let percent = heat.fraction() * 100.0;
+    match level {
+        HeatLevel::Normal => format!("HEAT {:.0}%", percent),
+        _ => format!("HEAT {:.0}% {}", percent, level.label()),
+    }
+}
+
+/// Draws a tick on the gauge at `fraction` of its width.
+fn spawn_threshold_tick(parent: &mut ChildBuilder, fraction: f32) {
+    parent.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(fraction * 100.0),
+            width: Val::Px(1.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        background_color: Color::WHITE.into(),
+        z_index: ZIndex::Local(1),
+        ..default()
+    });
+}
+
+pub fn setup_heat_gauge(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(HEAT_GAUGE_MARGIN),
+                left: Val::Percent(50.0),
+                margin: UiRect::left(Val::Px(-HEAT_GAUGE_WIDTH / 2.0)),
+                width: Val::Px(HEAT_GAUGE_WIDTH),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..default()
+            },
+            ..default()
+        },
+        HeatGauge,
+    )).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            HeatGaugeText,
+        ));
+        parent.spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Px(HEAT_GAUGE_HEIGHT),
+                ..default()
+            },
+            background_color: Color::rgba(0.05, 0.05, 0.08, 0.85).into(),
+            ..default()
+        }).with_children(|bar| {
+            bar.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    background_color: heat_gauge_color(HeatLevel::Normal).into(),
+                    ..default()
+                },
+                HeatGaugeFill,
+            ));
+            spawn_threshold_tick(bar, HOT_FRACTION);
+            spawn_threshold_tick(bar, LOCKOUT_FRACTION);
+        });
+    });
+}
+
+/// Follows the first hero with a `Heat` component; hidden when there is none.
+pub fn heat_gauge_system(
+    hero_query: Query<&Heat, With<Hero>>,
+    mut gauge_query: Query<&mut Style, (With<HeatGauge>, Without<HeatGaugeFill>)>,
+    mut fill_query: Query<(&mut Style, &mut BackgroundColor), With<HeatGaugeFill>>,
+    mut text_query: Query<&mut Text, With<HeatGaugeText>>,
+) {
+    let heat = hero_query.iter().next();
+
+    for mut style in gauge_query.iter_mut() {
+        style.display = if heat.is_some() { Display::Flex } else { Display::None };
+    }
+
+    let Some(heat) = heat else {
+        return;
+    };
+
+    for (mut style, mut color) in fill_query.iter_mut() {
+        style.width = Val::Percent(heat.fraction() * 100.0);
+        *color = heat_gauge_color(heat.level()).into();
+    }
+
+    let label = heat_gauge_label(heat);
+    for mut text in text_query.iter_mut() {
+        if text.sections[0].value != label {
+            text.sections[0].value = label.clone();
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use bevy::utils::HashSet;
+use bevy::window::PrimaryWindow;
+use crate::camera::{screen_to_world_position, CameraController};
+use crate::components::{Hero, Enemy, Projectile, MoveTarget};
+use crate::game_mode::AppState;
+use crate::systems::input::OrderableHero;
+use crate::systems::mech_movement::is_valid_move_target;
+use crate::systems::navigation::NavigationGrid;
+use crate::systems::terrain::Heightmap;
+
+pub const MINIMAP_SIZE: f32 = 200.0; // pixels
+pub const MINIMAP_MARGIN: f32 = 10.0;
+
+/// The minimap panel. `world_bounds` is the X/Z area it shows, with -Z at the top
+/// to match the default camera orientation.
+#[derive(Component, Debug, Clone)]
+pub struct Minimap {
+    pub world_bounds: Rect,
+    pub size: Vec2,
+}
+
+impl Default for Minimap {
+    fn default() -> Self {
+        Self {
+            world_bounds: Rect::new(-25.0, -25.0, 25.0, 25.0),
+            size: Vec2::splat(MINIMAP_SIZE),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlipKind {
+    Friendly,
+    Enemy,
+    Projectile,
+}
+
+impl BlipKind {
+    pub fn color(&self) -> Color {
+        match self {
+            BlipKind::Friendly => Color::rgb(0.2, 0.6, 1.0),
+            BlipKind::Enemy => Color::rgb(1.0, 0.2, 0.2),
+            BlipKind::Projectile => Color::rgb(1.0, 1.0, 0.0),
+        }
+    }
+
+    pub fn size(&self) -> f32 {
+        match self {
+            BlipKind::Friendly | BlipKind::Enemy => 6.0,
+            BlipKind::Projectile => 2.0,
+        }
+    }
+}
+
+/// A dot on the minimap tracking `target`.
+#[derive(Component)]
+pub struct MinimapBlip {
+    pub target: Entity,
+    pub kind: BlipKind,
+}
+
+/// Outline of the camera's ground footprint on the minimap.
+#[derive(Component)]
+pub struct MinimapViewport;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_minimap)
+            .add_systems(Update, (
+                minimap_click_system.run_if(in_state(AppState::Playing)),
+                minimap_blip_system,
+                minimap_viewport_system,
+            ));
+    }
+}
+
+/// Maps a world position on the X/Z plane to minimap pixels (top-left origin).
+pub fn world_to_minimap(world_position: Vec2, world_bounds: Rect, minimap_size: Vec2) -> Vec2 {
+    (world_position - world_bounds.min) / world_bounds.size() * minimap_size
+}
+
+/// Inverse of [`world_to_minimap`].
+pub fn minimap_to_world(minimap_position: Vec2, world_bounds: Rect, minimap_size: Vec2) -> Vec2 {
+    world_bounds.min + minimap_position / minimap_size * world_bounds.size()
+}
+
+/// Bounding rectangle on the X/Z plane of what an orthographic camera sees.
+/// Returns `None` if any screen corner misses the ground (camera looking at the horizon).
+pub fn camera_ground_footprint(
+    camera_transform: &Transform,
+    window_size: Vec2,
+    orthographic_scale: f32,
+) -> Option<Rect> {
+    let corners = [
+        Vec2::ZERO,
+        Vec2::new(window_size.x, 0.0),
+        Vec2::new(0.0, window_size.y),
+        window_size,
+    ];
+
+    let mut footprint: Option<Rect> = None;
+    for corner in corners {
+        let hit = screen_to_world_position(corner, camera_transform, window_size.x, window_size.y, orthographic_scale)?;
+        let point = Vec2::new(hit.x, hit.z);
+        footprint = Some(match footprint {
+            Some(rect) => rect.union_point(point),
+            None => Rect::from_center_size(point, Vec2::ZERO),
+        });
+    }
+    footprint
+}
+
+pub fn setup_minimap(mut commands: Commands) {
+    let minimap = Minimap::default();
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                right: Val::Px(MINIMAP_MARGIN),
+                bottom: Val::Px(MINIMAP_MARGIN),
+                width: Val::Px(minimap.size.x),
+                height: Val::Px(minimap.size.y),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            background_color: Color::rgba(0.05, 0.1, 0.05, 0.85).into(),
+            ..default()
+        },
+        Interaction::default(),
+        RelativeCursorPosition::default(),
+        minimap,
+    )).with_children(|parent| {
+        parent.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                border_color: Color::WHITE.into(),
+                z_index: ZIndex::Local(1),
+                ..default()
+            },
+            MinimapViewport,
+        ));
+    });
+}
+
+/// Left click moves the camera to the clicked point, right click orders a move there.
+pub fn minimap_click_system(
+    mut commands: Commands,
+    mouse_button: Res<Input<MouseButton>>,
+    minimap_query: Query<(&Minimap, &RelativeCursorPosition)>,
+    mut camera_query: Query<&mut CameraController>,
+    heightmap: Option<Res<Heightmap>>,
+    navigation: Option<Res<NavigationGrid>>,
+    hero_query: Query<OrderableHero, With<Hero>>,
+) {
+    let left_clicked = mouse_button.just_pressed(MouseButton::Left);
+    let right_clicked = mouse_button.just_pressed(MouseButton::Right);
+    if !left_clicked && !right_clicked {
+        return;
+    }
+
+    for (minimap, cursor) in minimap_query.iter() {
+        if !cursor.mouse_over() {
+            continue;
+        }
+        let Some(normalized) = cursor.normalized else {
+            continue;
+        };
+        let target = minimap_to_world(normalized * minimap.size, minimap.world_bounds, minimap.size);
+
+        if left_clicked {
+            for mut controller in camera_query.iter_mut() {
+                controller.follow_selected = false;
+                controller.focus.x = target.x;
+                controller.focus.z = target.y;
+                controller.clamp_focus();
+            }
+        }
+
+        if right_clicked {
+            // Same rule as world clicks: selected units, or every hero when nothing is selected
+            let any_selected = hero_query.iter().any(|(_, selected, _)| selected);
+            for (hero_entity, selected, lower_body) in hero_query.iter() {
+                let reachable = lower_body.is_none_or(|lower_body| {
+                    is_valid_move_target(heightmap.as_deref(), navigation.as_deref(), target, &lower_body.movement_stats)
+                });
+                if (selected || !any_selected) && reachable {
+                    commands.entity(hero_entity).insert(MoveTarget { position: target });
+                }
+            }
+        }
+    }
+}
+
+/// An entity shown on the minimap, with whether it is a hero or an enemy.
+type TrackedEntity = (Entity, &'static GlobalTransform, Has<Hero>, Has<Enemy>, Option<&'static Visibility>);
+type Tracked = Or<(With<Hero>, With<Enemy>, With<Projectile>)>;
+
+/// Keeps one blip per hero, enemy and projectile, positioned over the minimap.
+/// Hidden entities, such as enemies in the fog of war, get no blip.
+pub fn minimap_blip_system(
+    mut commands: Commands,
+    minimap_query: Query<(Entity, &Minimap)>,
+    tracked_query: Query<TrackedEntity, Tracked>,
+    mut blip_query: Query<(Entity, &MinimapBlip, &mut Style)>,
+) {
+    let Ok((minimap_entity, minimap)) = minimap_query.get_single() else {
+        return;
+    };
+
+    let mut tracked = HashSet::new();
+
+    for (blip_entity, blip, mut style) in blip_query.iter_mut() {
+        let Some(transform) = tracked_query.get(blip.target).ok()
+            .filter(|(.., visibility)| *visibility != Some(&Visibility::Hidden))
+            .map(|(_, transform, ..)| transform)
+        else {
+            commands.entity(blip_entity).despawn_recursive();
+            continue;
+        };
+        tracked.insert(blip.target);
+
+        let world_position = Vec2::new(transform.translation().x, transform.translation().z);
+        place_blip(&mut style, world_position, blip.kind, minimap);
+    }
+
+    for (entity, transform, is_hero, is_enemy, visibility) in tracked_query.iter() {
+        if tracked.contains(&entity) || visibility == Some(&Visibility::Hidden) {
+            continue;
+        }
+
+        let kind = if is_hero {
+            BlipKind::Friendly
+        } else if is_enemy {
+            BlipKind::Enemy
+        } else {
+            BlipKind::Projectile
+        };
+
+        let mut style = Style {
+            position_type: PositionType::Absolute,
+            width: Val::Px(kind.size()),
+            height: Val::Px(kind.size()),
+            ..default()
+        };
+        let world_position = Vec2::new(transform.translation().x, transform.translation().z);
+        place_blip(&mut style, world_position, kind, minimap);
+
+        let blip = commands.spawn((
+            NodeBundle {
+                style,
+                background_color: kind.color().into(),
+                ..default()
+            },
+            MinimapBlip { target: entity, kind },
+        )).id();
+        commands.entity(minimap_entity).add_child(blip);
+    }
+}
+
+fn place_blip(style: &mut Style, world_position: Vec2, kind: BlipKind, minimap: &Minimap) {
+    if !minimap.world_bounds.contains(world_position) {
+        style.display = Display::None;
+        return;
+    }
+
+    let position = world_to_minimap(world_position, minimap.world_bounds, minimap.size) - kind.size() * 0.5;
+    style.display = Display::Flex;
+    style.left = Val::Px(position.x);
+    style.top = Val::Px(position.y);
+}
+
+/// Draws the camera's ground footprint as an outline on the minimap.
+pub fn minimap_viewport_system(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &Projection), With<CameraController>>,
+    minimap_query: Query<&Minimap>,
+    mut viewport_query: Query<&mut Style, With<MinimapViewport>>,
+) {
+    let (Ok(window), Ok((camera_transform, projection)), Ok(minimap)) =
+        (windows.get_single(), camera_query.get_single(), minimap_query.get_single()) else {
+        return;
+    };
+    let Projection::Orthographic(ortho) = projection else {
+        return;
+    };
+
+    let window_size = Vec2::new(window.width(), window.height());
+    let footprint = camera_ground_footprint(camera_transform, window_size, ortho.scale);
+
+    for mut style in viewport_query.iter_mut() {
+        let Some(footprint) = footprint else {
+            style.display = Display::None;
+            continue;
+        };
+
+        let min = world_to_minimap(footprint.min, minimap.world_bounds, minimap.size);
+        let max = world_to_minimap(footprint.max, minimap.world_bounds, minimap.size);
+        style.display = Display::Flex;
+        style.left = Val::Px(min.x);
+        style.top = Val::Px(min.y);
+        style.width = Val::Px(max.x - min.x);
+        style.height = Val::Px(max.y - min.y);
+    }
+}
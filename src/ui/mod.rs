@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+use crate::resources::PickingState;
+
+pub mod game_hud;
+pub mod health_bars;
+pub mod heat_gauge;
+pub mod minimap;
+pub mod unit_panel;
+
+pub use game_hud::*;
+pub use health_bars::*;
+pub use heat_gauge::*;
+pub use minimap::*;
+pub use unit_panel::*;
+
+/// Flags the cursor as being over an interactive UI node so world clicks underneath are ignored.
+pub fn pointer_over_ui_system(
+    mut picking: ResMut<PickingState>,
+    interaction_query: Query<&Interaction, With<Node>>,
+) {
+    picking.pointer_over_ui = interaction_query.iter().any(|interaction| *interaction != Interaction::None);
+}
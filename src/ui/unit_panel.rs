@@ -0,0 +1,254 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use crate::components::{Health, Selected};
+use crate::mech::{
+    Mech, MechHierarchy, MechLowerBody, MechUpperBody, MechWeapon, TankTreads, WeaponAmmo,
+};
+use crate::systems::picking::find_descendants;
+
+/// Bottom-left panel describing the selected mech.
+#[derive(Component)]
+pub struct UnitInfoPanel;
+
+#[derive(Component)]
+pub struct UnitInfoText;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeaponInfo {
+    pub name: String,
+    pub damage: f32,
+    pub range: f32,
+    pub cooldown_progress: f32, // 0 = just fired, 1 = ready
+    pub ammo: Option<WeaponAmmo>, // None = unlimited
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UnitInfo {
+    pub name: String,
+    pub health: Option<(f32, f32)>,
+    pub lower_body: Option<String>,
+    pub upper_body: Option<String>,
+    pub weapons: Vec<WeaponInfo>,
+}
+
+pub struct UnitInfoPanelPlugin;
+
+impl Plugin for UnitInfoPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_unit_info_panel)
+            .add_systems(Update, unit_info_panel_system);
+    }
+}
+
+pub fn cooldown_progress(time_since_fire: f32, fire_rate: f32) -> f32 {
+    if fire_rate <= 0.0 {
+        return 1.0;
+    }
+    (time_since_fire / fire_rate).clamp(0.0, 1.0)
+}
+
+/// Ten-segment text bar, e.g. `[######----]`.
+pub fn format_progress_bar(progress: f32) -> String {
+    let filled = (progress.clamp(0.0, 1.0) * 10.0).round() as usize;
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(10 - filled))
+}
+
+pub fn format_unit_info(info: &UnitInfo) -> String {
+    let mut text = format!("{}\n", info.name);
+
+    if let Some((current, max)) = info.health {
+        text.push_str(&format!("Health: {:.0}/{:.0}\n", current.max(0.0), max));
+    }
+    text.push_str(&format!("Lower: {}\n", info.lower_body.as_deref().unwrap_or("none")));
+    text.push_str(&format!("Upper: {}\n", info.upper_body.as_deref().unwrap_or("none")));
+
+    if info.weapons.is_empty() {
+        text.push_str("No weapons\n");
+    }
+    for weapon in &info.weapons {
+        let (progress, status) = match &weapon.ammo {
+            Some(ammo) if ammo.is_reloading() => (ammo.reload_progress(), "RELOADING"),
+            Some(ammo) if ammo.magazine == 0 => (0.0, "EMPTY"),
+            _ if weapon.cooldown_progress >= 1.0 => (1.0, "READY"),
+            _ => (weapon.cooldown_progress, "RELOADING"),
+        };
+        let ammo = weapon
+            .ammo
+            .as_ref()
+            .map(|ammo| format!("{}/{} {}", ammo.magazine, ammo.reserve, ammo.ammo_type.label()))
+            .unwrap_or_else(|| "inf".to_string());
+        text.push_str(&format!(
+            "{}: dmg {:.0}, range {:.0}, ammo {}\n  {} {}\n",
+            weapon.name,
+            weapon.damage,
+            weapon.range,
+            ammo,
+            format_progress_bar(progress),
+            status,
+        ));
+    }
+
+    text
+}
+
+pub fn setup_unit_info_panel(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                min_width: Val::Px(240.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                display: Display::None,
+                ..default()
+            },
+            background_color: Color::rgba(0.05, 0.05, 0.08, 0.85).into(),
+            ..default()
+        },
+        Interaction::default(),
+        UnitInfoPanel,
+    )).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 16.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            UnitInfoText,
+        ));
+    });
+}
+
+fn describe_treads(max_speed: f32, turn_rate: f32) -> String {
+    format!("Treads (speed {:.1}, turn {:.0}°/s)", max_speed, turn_rate)
+}
+
+/// The selected unit's root: its name, health and how it is put together.
+pub type UnitRoot = (
+    Option<&'static Mech>,
+    Option<&'static Health>,
+    Option<&'static MechHierarchy>,
+    Option<&'static MechLowerBody>,
+);
+
+/// Whatever a part of the selected unit contributes to the panel.
+pub type UnitPart = (
+    Option<&'static MechLowerBody>,
+    Option<&'static TankTreads>,
+    Option<&'static MechUpperBody>,
+    Option<&'static MechWeapon>,
+    Option<&'static WeaponAmmo>,
+);
+
+/// Gathers the parts of the first selected unit into a [`UnitInfo`].
+pub fn gather_unit_info(
+    selected: Entity,
+    mech_query: &Query<UnitRoot>,
+    children_query: &Query<&Children>,
+    part_query: &Query<UnitPart>,
+) -> UnitInfo {
+    let (mech, health, hierarchy, root_lower_body) =
+        mech_query.get(selected).unwrap_or((None, None, None, None));
+
+    let mut info = UnitInfo {
+        name: mech.map(|mech| mech.name.clone()).unwrap_or_else(|| "Unit".to_string()),
+        health: health.map(|health| (health.current, health.max)),
+        // Driven mechs carry their drive stats on the root rather than on a lower-body part
+        lower_body: root_lower_body
+            .map(|lower_body| describe_treads(lower_body.movement_stats.max_speed, lower_body.movement_stats.turn_rate)),
+        ..default()
+    };
+
+    let mut parts = Vec::new();
+    if let Some(hierarchy) = hierarchy {
+        parts.extend(hierarchy.lower);
+        parts.extend(hierarchy.upper);
+        parts.extend(hierarchy.weapons.iter().copied());
+    }
+    parts.extend(find_descendants(selected, children_query));
+    let mut seen = HashSet::new();
+    parts.retain(|part| seen.insert(*part));
+
+    let mut mech_weapons = Vec::new();
+
+    for part in &parts {
+        let Ok((lower_body, treads, upper_body, weapon, ammo)) = part_query.get(*part) else {
+            continue;
+        };
+
+        if let Some(lower_body) = lower_body {
+            info.lower_body = Some(describe_treads(lower_body.movement_stats.max_speed, lower_body.movement_stats.turn_rate));
+        } else if let Some(treads) = treads {
+            info.lower_body = Some(describe_treads(treads.speed, treads.turn_rate));
+        }
+
+        if let Some(upper_body) = upper_body {
+            info.upper_body = Some(format!(
+                "Turret ({} hardpoints, {:.0}°/s)",
+                upper_body.hardpoints.len(),
+                upper_body.rotation_capability.rotation_speed,
+            ));
+        }
+
+        if let Some(weapon) = weapon {
+            mech_weapons.push((weapon, ammo));
+        }
+    }
+
+    // List weapons in hardpoint order when the upper body declares its hardpoints
+    let hardpoint_order: Vec<String> = parts
+        .iter()
+        .filter_map(|part| part_query.get(*part).ok().and_then(|(_, _, upper, ..)| upper))
+        .flat_map(|upper| upper.hardpoints.iter().map(|hardpoint| hardpoint.id.clone()))
+        .collect();
+    mech_weapons.sort_by_key(|(weapon, _)| {
+        hardpoint_order
+            .iter()
+            .position(|id| *id == weapon.hardpoint_id)
+            .unwrap_or(usize::MAX)
+    });
+
+    for (weapon, ammo) in mech_weapons {
+        info.weapons.push(WeaponInfo {
+            name: format!("Cannon [{}]", weapon.hardpoint_id),
+            damage: weapon.weapon_stats.damage,
+            range: weapon.weapon_stats.range,
+            cooldown_progress: cooldown_progress(weapon.last_fire_time, weapon.weapon_stats.fire_rate),
+            ammo: ammo.cloned(),
+        });
+    }
+
+    info
+}
+
+pub fn unit_info_panel_system(
+    selected_query: Query<Entity, With<Selected>>,
+    mech_query: Query<UnitRoot>,
+    children_query: Query<&Children>,
+    part_query: Query<UnitPart>,
+    mut panel_query: Query<&mut Style, With<UnitInfoPanel>>,
+    mut text_query: Query<&mut Text, With<UnitInfoText>>,
+) {
+    let selected = selected_query.iter().next();
+
+    for mut style in panel_query.iter_mut() {
+        style.display = if selected.is_some() { Display::Flex } else { Display::None };
+    }
+
+    let Some(selected) = selected else {
+        return;
+    };
+
+    let info = gather_unit_info(selected, &mech_query, &children_query, &part_query);
+    let description = format_unit_info(&info);
+
+    for mut text in text_query.iter_mut() {
+        if text.sections[0].value != description {
+            text.sections[0].value = description.clone();
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::camera::CameraShakeEvent;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::resources::MouseWorldPosition;
+use rust_and_ruin::systems::*;
+use rust_and_ruin::ui::*;
+
+#[test]
+fn test_magazine_empties_then_reloads_from_reserve() {
+    let mut ammo = WeaponAmmo::new(2, 3, 1.0);
+
+    assert!(ammo.consume());
+    assert!(ammo.consume());
+    assert_eq!(ammo.magazine, 0);
+    assert!(ammo.is_reloading(), "An empty magazine starts a reload");
+    assert!(!ammo.consume(), "Cannot fire while reloading");
+
+    ammo.tick_reload(0.5);
+    assert!((ammo.reload_progress() - 0.5).abs() < 0.001);
+    ammo.tick_reload(0.5);
+    assert!(!ammo.is_reloading());
+    assert_eq!((ammo.magazine, ammo.reserve), (2, 1));
+
+    // The last reload only has one round left to load
+    ammo.consume();
+    ammo.consume();
+    ammo.tick_reload(1.0);
+    assert_eq!((ammo.magazine, ammo.reserve), (1, 0));
+
+    ammo.consume();
+    assert!(!ammo.is_reloading(), "Nothing left in reserve to reload");
+    assert!(!ammo.can_fire());
+}
+
+#[test]
+fn test_manual_reload_only_when_magazine_has_room() {
+    let mut ammo = WeaponAmmo::new(5, 10, 2.0);
+    assert!(!ammo.start_reload(), "A full magazine does not reload");
+
+    ammo.consume();
+    assert!(ammo.start_reload());
+    assert!(!ammo.start_reload(), "Already reloading");
+    ammo.tick_reload(2.0);
+    assert_eq!((ammo.magazine, ammo.reserve), (5, 9));
+}
+
+#[test]
+fn test_switching_ammo_type_unloads_the_magazine() {
+    let mut ammo = WeaponAmmo::new(5, 10, 2.0);
+    ammo.consume();
+
+    ammo.select_ammo_type(AmmoType::ArmorPiercing);
+    assert_eq!(ammo.ammo_type, AmmoType::ArmorPiercing);
+    assert_eq!((ammo.magazine, ammo.reserve), (0, 14));
+    assert!(ammo.is_reloading());
+
+    assert_eq!(AmmoType::Fragmentation.next(), AmmoType::ArmorPiercing);
+}
+
+fn create_firing_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.insert_resource(Input::<KeyCode>::default());
+    app.insert_resource(Input::<GamepadButton>::default());
+    app.init_resource::<Gamepads>();
+    app.init_resource::<MouseWorldPosition>();
+    app.add_event::<CameraShakeEvent>();
+    app.add_systems(Update, (
+        weapon_reload_system,
+        manual_reload_system,
+        cycle_ammo_type_system,
+        weapon_cooldown_system,
+        weapon_control_system,
+    ).chain());
+    app
+}
+
+fn spawn_armed_hero(app: &mut App, ammo: WeaponAmmo) -> Entity {
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 8.0)))).id();
+    let hero = app.world.spawn((Hero, AttackTarget { entity: enemy }, TransformBundle::default())).id();
+    let upper = app.world.spawn((
+        create_turret_upper(),
+        MechRotation { target_angle: 0.0, current_angle: 0.0 },
+        TransformBundle::default(),
+    )).id();
+    let weapon = app.world.spawn((
+        create_cannon_weapon("main".to_string()),
+        CannonWeapon::default(),
+        ammo,
+        TransformBundle::default(),
+    )).id();
+    app.world.entity_mut(hero).push_children(&[upper]);
+    app.world.entity_mut(upper).push_children(&[weapon]);
+    weapon
+}
+
+fn shells(app: &mut App) -> Vec<(f32, Option<AmmoType>, bool)> {
+    let mut query = app.world.query::<(&Projectile, Option<&AmmoType>, Option<&FragmentShell>)>();
+    query
+        .iter(&app.world)
+        .map(|(projectile, ammo_type, fragment)| (projectile.damage, ammo_type.copied(), fragment.is_some()))
+        .collect()
+}
+
+#[test]
+fn test_firing_is_blocked_during_reload() {
+    let mut app = create_firing_app();
+    // One round per magazine, 1.5s fire rate and 2s reload
+    let weapon = spawn_armed_hero(&mut app, WeaponAmmo::new(1, 5, 2.0));
+
+    for _ in 0..16 {
+        app.update();
+    }
+    assert_eq!(shells(&mut app).len(), 1);
+    assert!(app.world.get::<WeaponAmmo>(weapon).unwrap().is_reloading());
+
+    // Cooldown is long over but the reload still has time to run
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(shells(&mut app).len(), 1, "No shots while the magazine is being swapped");
+
+    for _ in 0..12 {
+        app.update();
+    }
+    assert_eq!(shells(&mut app).len(), 2, "Firing resumes once the reload completes");
+}
+
+#[test]
+fn test_cycle_key_changes_shell_type() {
+    let mut app = create_firing_app();
+    let weapon = spawn_armed_hero(&mut app, WeaponAmmo::new(5, 10, 0.5));
+
+    app.world.resource_mut::<Input<KeyCode>>().press(CYCLE_AMMO_KEY);
+    app.update();
+    app.world.resource_mut::<Input<KeyCode>>().clear();
+    assert_eq!(app.world.get::<WeaponAmmo>(weapon).unwrap().ammo_type, AmmoType::ArmorPiercing);
+
+    for _ in 0..15 {
+        app.update();
+    }
+    let fired = shells(&mut app);
+    assert_eq!(fired.len(), 1);
+    let (damage, ammo_type, fragmenting) = fired[0];
+    assert_eq!(ammo_type, Some(AmmoType::ArmorPiercing));
+    assert!((damage - 25.0 * AmmoType::ArmorPiercing.damage_multiplier()).abs() < 0.001);
+    assert!(!fragmenting, "Only fragmentation rounds break into fragments");
+}
+
+#[test]
+fn test_cycle_and_reload_only_touch_the_primary_weapon() {
+    let mut app = create_firing_app();
+    let primary = spawn_armed_hero(&mut app, WeaponAmmo::new(5, 10, 0.5));
+    let upper = app.world.get::<Parent>(primary).unwrap().get();
+    let secondary = app.world.spawn((
+        create_cannon_weapon("secondary".to_string()),
+        CannonWeapon::default(),
+        WeaponAmmo::new(5, 10, 0.5),
+        TransformBundle::default(),
+    )).id();
+    app.world.entity_mut(upper).push_children(&[secondary]);
+    // Hold fire so only the key presses touch the magazines
+    let hero = app.world.get::<Parent>(upper).unwrap().get();
+    app.world.entity_mut(hero).remove::<AttackTarget>();
+    app.world.get_mut::<WeaponAmmo>(primary).unwrap().magazine = 4;
+    app.world.get_mut::<WeaponAmmo>(secondary).unwrap().magazine = 4;
+
+    app.world.resource_mut::<Input<KeyCode>>().press(RELOAD_KEY);
+    app.update();
+    app.world.resource_mut::<Input<KeyCode>>().clear();
+    assert!(app.world.get::<WeaponAmmo>(primary).unwrap().is_reloading());
+    assert!(!app.world.get::<WeaponAmmo>(secondary).unwrap().is_reloading());
+
+    app.world.resource_mut::<Input<KeyCode>>().press(CYCLE_AMMO_KEY);
+    app.update();
+    app.world.resource_mut::<Input<KeyCode>>().clear();
+    assert_eq!(app.world.get::<WeaponAmmo>(primary).unwrap().ammo_type, AmmoType::ArmorPiercing);
+    assert_eq!(app.world.get::<WeaponAmmo>(secondary).unwrap().ammo_type, AmmoType::default());
+}
+
+#[test]
+fn test_unit_panel_shows_magazine_and_reload() {
+    let mut ammo = WeaponAmmo::new(5, 20, 2.0);
+    ammo.consume();
+    ammo.start_reload();
+    ammo.tick_reload(1.0);
+
+    let info = UnitInfo {
+        name: "Hero".to_string(),
+        weapons: vec![WeaponInfo {
+            name: "Cannon [main]".to_string(),
+            damage: 25.0,
+            range: 15.0,
+            cooldown_progress: 1.0,
+            ammo: Some(ammo),
+        }],
+        ..default()
+    };
+    let text = format_unit_info(&info);
+    assert!(text.contains("ammo 4/20 FRAG"), "{}", text);
+    assert!(text.contains("[#####-----] RELOADING"), "{}", text);
+}
@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{Collider, CollisionEvent, ExternalImpulse, Velocity};
+use bevy_rapier3d::rapier::geometry::CollisionEventFlags;
+use rust_and_ruin::camera::CameraShakeEvent;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::systems::*;
+
+const SHELL_SPEED: f32 = 20.0;
+
+fn create_armor_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.add_event::<CollisionEvent>();
+    app.add_event::<DamageEvent>();
+    app.add_event::<EnemyDestroyedEvent>();
+    app.add_event::<ExplosionEvent>();
+    app.add_event::<CameraShakeEvent>();
+    app.add_systems(Update, (collision_detection_system, mech_part_collision_system));
+    app
+}
+
+fn spawn_armored_enemy(app: &mut App) -> Entity {
+    let enemy = app.world.spawn((
+        Enemy,
+        Health::new(100.0),
+        Armor::new(ENEMY_ARMOR),
+        ExternalImpulse::default(),
+        Collider::cuboid(0.75, 0.75, 0.75),
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 0.75, 0.0)),
+    )).id();
+    // Propagate the global transform the face normal is taken from
+    app.update();
+    enemy
+}
+
+/// Shell at `position` flying along `direction`, fired at full muzzle velocity.
+fn spawn_shell(app: &mut App, ammo_type: AmmoType, position: Vec3, direction: Vec2) -> Entity {
+    let velocity = direction.normalize() * SHELL_SPEED;
+    app.world.spawn((
+        Projectile { damage: 25.0, speed: SHELL_SPEED },
+        TankShell { velocity, spawn_position: Vec2::ZERO, max_range: 15.0 },
+        ammo_type,
+        Velocity::linear(Vec3::new(velocity.x, 0.0, velocity.y)),
+        TransformBundle::from_transform(Transform::from_translation(position)),
+    )).id()
+}
+
+fn hit(app: &mut App, shell: Entity, target: Entity) {
+    app.world.send_event(CollisionEvent::Started(shell, target, CollisionEventFlags::empty()));
+    app.update();
+}
+
+/// Direction hitting a face with normal -X at `degrees` away from square-on.
+fn angled(degrees: f32) -> Vec2 {
+    let angle = degrees.to_radians();
+    Vec2::new(angle.cos(), angle.sin())
+}
+
+#[test]
+fn test_effective_armor_grows_with_impact_angle() {
+    assert!((effective_armor(30.0, Vec2::X, -Vec2::X) - 30.0).abs() < 0.001);
+    assert!((effective_armor(30.0, angled(60.0), -Vec2::X) - 60.0).abs() < 0.001);
+    assert_eq!(effective_armor(30.0, Vec2::Y, -Vec2::X), f32::INFINITY, "Grazing hits always glance off");
+}
+
+#[test]
+fn test_hit_face_normal_uses_the_struck_box_face() {
+    let collider = Collider::cuboid(0.5, 0.5, 2.0);
+    let target = GlobalTransform::default();
+    let side = hit_face_normal(&target, Some(&collider), Vec3::new(-0.6, 0.0, 1.0), Vec2::X);
+    assert!((side - Vec2::new(-1.0, 0.0)).length() < 0.001, "got {:?}", side);
+    let end = hit_face_normal(&target, Some(&collider), Vec3::new(0.4, 0.0, 2.1), -Vec2::Y);
+    assert!((end - Vec2::new(0.0, 1.0)).length() < 0.001, "got {:?}", end);
+
+    let turned = GlobalTransform::from(Transform::from_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)));
+    let rotated = hit_face_normal(&turned, Some(&collider), Vec3::new(-2.1, 0.0, 0.0), Vec2::X);
+    assert!((rotated - Vec2::new(-1.0, 0.0)).length() < 0.001, "got {:?}", rotated);
+}
+
+#[test]
+fn test_slowed_shells_lose_penetration() {
+    let shell = TankShell { velocity: Vec2::X * SHELL_SPEED, spawn_position: Vec2::ZERO, max_range: 15.0 };
+    let full = shell_penetration(Some(AmmoType::ArmorPiercing), &shell, Vec3::X * SHELL_SPEED);
+    let half = shell_penetration(Some(AmmoType::ArmorPiercing), &shell, Vec3::X * SHELL_SPEED * 0.5);
+    assert_eq!(full, AmmoType::ArmorPiercing.penetration());
+    assert!((half - full * 0.5).abs() < 0.001);
+}
+
+#[test]
+fn test_shell_penetrating_armor_deals_full_damage_and_is_spent() {
+    let mut app = create_armor_app();
+    let enemy = spawn_armored_enemy(&mut app);
+    let shell = spawn_shell(&mut app, AmmoType::ArmorPiercing, Vec3::new(-0.9, 0.75, 0.3), angled(60.0));
+
+    hit(&mut app, shell, enemy);
+
+    assert_eq!(app.world.get::<Health>(enemy).unwrap().current, 75.0);
+    assert!(app.world.get_entity(shell).is_none());
+}
+
+#[test]
+fn test_shell_ricochets_off_armor_it_cannot_penetrate() {
+    let mut app = create_armor_app();
+    let enemy = spawn_armored_enemy(&mut app);
+    let shell = spawn_shell(&mut app, AmmoType::HighExplosive, Vec3::new(-0.9, 0.75, 0.3), angled(60.0));
+
+    hit(&mut app, shell, enemy);
+
+    assert_eq!(app.world.get::<Health>(enemy).unwrap().current, 100.0, "Ricochets deal no damage");
+    assert!(app.world.get_entity(shell).is_some(), "The shell keeps flying");
+    let velocity = app.world.get::<Velocity>(shell).unwrap().linvel;
+    let expected = Vec3::new(-angled(60.0).x, 0.0, angled(60.0).y) * SHELL_SPEED * RICOCHET_SPEED_RETAINED;
+    assert!((velocity - expected).length() < 0.001, "got {:?}, expected {:?}", velocity, expected);
+
+    // The same shell fired square-on gets through
+    let square_on = spawn_shell(&mut app, AmmoType::HighExplosive, Vec3::new(-0.9, 0.75, 0.0), Vec2::X);
+    hit(&mut app, square_on, enemy);
+    assert_eq!(app.world.get::<Health>(enemy).unwrap().current, 75.0);
+}
+
+#[test]
+fn test_ricocheted_shell_can_still_hit_something_else() {
+    let mut app = create_armor_app();
+    let enemy = spawn_armored_enemy(&mut app);
+    let unarmored = app.world.spawn((
+        Enemy,
+        Health::new(100.0),
+        ExternalImpulse::default(),
+        TransformBundle::from_transform(Transform::from_xyz(-3.0, 0.75, 3.0)),
+    )).id();
+    let shell = spawn_shell(&mut app, AmmoType::HighExplosive, Vec3::new(-0.9, 0.75, 0.3), angled(60.0));
+
+    hit(&mut app, shell, enemy);
+    hit(&mut app, shell, unarmored);
+
+    assert_eq!(app.world.get::<Health>(unarmored).unwrap().current, 75.0);
+}
+
+#[test]
+fn test_shells_glance_off_armored_mech_parts() {
+    let mut app = create_armor_app();
+    let part = app.world.spawn((
+        PartHealth::upper(),
+        Armor::new(UPPER_BODY_ARMOR),
+        Collider::cuboid(0.5, 0.5, 0.5),
+        TransformBundle::default(),
+    )).id();
+    app.update();
+
+    let glancing = spawn_shell(&mut app, AmmoType::ArmorPiercing, Vec3::new(-0.6, 0.0, 0.0), angled(80.0));
+    hit(&mut app, glancing, part);
+    assert_eq!(app.world.get::<PartHealth>(part).unwrap().current, UPPER_BODY_HIT_POINTS);
+    assert!(app.world.get_entity(glancing).is_some());
+
+    let square_on = spawn_shell(&mut app, AmmoType::ArmorPiercing, Vec3::new(-0.6, 0.0, 0.0), Vec2::X);
+    hit(&mut app, square_on, part);
+    assert_eq!(app.world.get::<PartHealth>(part).unwrap().current, UPPER_BODY_HIT_POINTS - 25.0);
+    assert!(app.world.get_entity(square_on).is_none());
+}
+
+#[test]
+fn test_high_explosive_shells_burst_on_unarmored_targets() {
+    let mut app = create_armor_app();
+    let spawn_unarmored = |app: &mut App| app.world.spawn((
+        Enemy,
+        Health::new(100.0),
+        ExternalImpulse::default(),
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 0.75, 0.0)),
+    )).id();
+    let first = spawn_unarmored(&mut app);
+    let second = spawn_unarmored(&mut app);
+
+    let armor_piercing = spawn_shell(&mut app, AmmoType::ArmorPiercing, Vec3::new(-0.9, 0.75, 0.0), Vec2::X);
+    hit(&mut app, armor_piercing, first);
+    assert!(app.world.get_entity(armor_piercing).is_some(), "Kinetic shells punch through soft targets");
+
+    let high_explosive = spawn_shell(&mut app, AmmoType::HighExplosive, Vec3::new(-0.9, 0.75, 0.0), Vec2::X);
+    hit(&mut app, high_explosive, second);
+    assert_eq!(app.world.get::<Health>(second).unwrap().current, 75.0);
+    assert!(app.world.get_entity(high_explosive).is_none(), "HE shells burst on whatever they hit");
+}
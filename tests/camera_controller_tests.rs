@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+use bevy::input::InputPlugin;
+use bevy::input::ButtonState;
+use bevy::input::keyboard::KeyboardInput;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::camera::*;
+use rust_and_ruin::components::Selected;
+
+fn create_camera_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, TransformPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.add_plugins(CameraControllerPlugin);
+    app
+}
+
+fn spawn_controlled_camera(app: &mut App, controller: CameraController) -> Entity {
+    app.world.spawn((
+        Camera3dBundle {
+            projection: OrthographicProjection::default().into(),
+            ..default()
+        },
+        controller,
+    )).id()
+}
+
+fn send_key(app: &mut App, key_code: KeyCode, state: ButtonState) {
+    app.world.send_event(KeyboardInput {
+        scan_code: 0,
+        key_code: Some(key_code),
+        state,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+fn tap_key(app: &mut App, key_code: KeyCode) {
+    send_key(app, key_code, ButtonState::Pressed);
+    app.update();
+    send_key(app, key_code, ButtonState::Released);
+    app.update();
+}
+
+#[test]
+fn test_camera_transform_matches_orthographic_camera_at_default_yaw() {
+    let transform = calculate_camera_transform(Vec3::ZERO, 0.0, CAMERA_ANGLE_DEGREES, CAMERA_DISTANCE);
+    let expected = create_orthographic_camera_transform(CAMERA_ANGLE_DEGREES);
+
+    assert!((transform.translation - expected.translation).length() < 0.001);
+    assert!(transform.rotation.angle_between(expected.rotation) < 0.001);
+}
+
+#[test]
+fn test_camera_looks_at_focus() {
+    let focus = Vec3::new(5.0, 0.0, -3.0);
+    let transform = calculate_camera_transform(focus, 90.0, CAMERA_ANGLE_DEGREES, CAMERA_DISTANCE);
+
+    let picked = screen_to_world_position(Vec2::new(640.0, 360.0), &transform, 1280.0, 720.0, 0.02).unwrap();
+    assert!((picked - focus).length() < 0.001, "Screen center should stay on the focus, got {:?}", picked);
+}
+
+#[test]
+fn test_screen_pan_follows_camera_yaw() {
+    // Screen-up pans away from the camera
+    let up = screen_pan_to_world(Vec2::new(0.0, 1.0), 0.0);
+    assert!((up - Vec2::new(0.0, -1.0)).length() < 0.001);
+
+    // After a 90° turn the camera sits on +X, so screen-up points towards -X
+    let up = screen_pan_to_world(Vec2::new(0.0, 1.0), 90.0);
+    assert!((up - Vec2::new(-1.0, 0.0)).length() < 0.001, "Got {:?}", up);
+
+    let right = screen_pan_to_world(Vec2::new(1.0, 0.0), 90.0);
+    assert!((right - Vec2::new(0.0, -1.0)).length() < 0.001, "Got {:?}", right);
+}
+
+#[test]
+fn test_edge_scroll_direction() {
+    let window_size = Vec2::new(1280.0, 720.0);
+
+    assert_eq!(edge_scroll_direction(Vec2::new(640.0, 360.0), window_size, 10.0), Vec2::ZERO);
+    assert_eq!(edge_scroll_direction(Vec2::new(2.0, 360.0), window_size, 10.0), Vec2::new(-1.0, 0.0));
+    assert_eq!(edge_scroll_direction(Vec2::new(1279.0, 2.0), window_size, 10.0), Vec2::new(1.0, 1.0));
+    assert_eq!(edge_scroll_direction(Vec2::new(640.0, 715.0), window_size, 10.0), Vec2::new(0.0, -1.0));
+}
+
+#[test]
+fn test_zoom_keeps_point_under_cursor_fixed() {
+    let camera = calculate_camera_transform(Vec3::ZERO, 0.0, CAMERA_ANGLE_DEGREES, CAMERA_DISTANCE);
+    let cursor = Vec2::new(1000.0, 150.0);
+    let old_zoom = 0.02;
+    let new_zoom = 0.015;
+
+    let anchor = screen_to_world_position(cursor, &camera, 1280.0, 720.0, old_zoom).unwrap();
+    let new_focus = zoom_focus_towards(Vec3::ZERO, anchor, old_zoom, new_zoom);
+    let zoomed_camera = calculate_camera_transform(new_focus, 0.0, CAMERA_ANGLE_DEGREES, CAMERA_DISTANCE);
+    let after = screen_to_world_position(cursor, &zoomed_camera, 1280.0, 720.0, new_zoom).unwrap();
+
+    assert!((after - anchor).length() < 0.001, "Anchor {:?} drifted to {:?}", anchor, after);
+}
+
+#[test]
+fn test_keyboard_pan_moves_camera_and_clamps_to_bounds() {
+    let mut app = create_camera_app();
+    let camera = spawn_controlled_camera(&mut app, CameraController {
+        bounds: Rect::new(-5.0, -5.0, 5.0, 5.0),
+        ..default()
+    });
+
+    send_key(&mut app, KeyCode::D, ButtonState::Pressed);
+    app.update();
+    app.update();
+
+    let focus = app.world.get::<CameraController>(camera).unwrap().focus;
+    assert!(focus.x > 0.0 && focus.z.abs() < 0.001, "D should pan right, got {:?}", focus);
+
+    for _ in 0..100 {
+        app.update();
+    }
+
+    let controller = app.world.get::<CameraController>(camera).unwrap();
+    assert_eq!(controller.focus.x, 5.0, "Focus should be clamped to the map bounds");
+
+    // The camera transform follows the controller
+    let transform = app.world.get::<Transform>(camera).unwrap();
+    assert!((transform.translation - controller.camera_transform().translation).length() < 0.001);
+}
+
+#[test]
+fn test_rotation_steps_by_90_degrees() {
+    let mut app = create_camera_app();
+    let camera = spawn_controlled_camera(&mut app, CameraController::default());
+
+    tap_key(&mut app, ROTATE_RIGHT_KEY);
+    assert_eq!(app.world.get::<CameraController>(camera).unwrap().yaw, 90.0);
+
+    tap_key(&mut app, ROTATE_LEFT_KEY);
+    tap_key(&mut app, ROTATE_LEFT_KEY);
+    assert_eq!(app.world.get::<CameraController>(camera).unwrap().yaw, 270.0);
+
+    // Rotation is optional
+    app.world.get_mut::<CameraController>(camera).unwrap().allow_rotation = false;
+    tap_key(&mut app, ROTATE_RIGHT_KEY);
+    assert_eq!(app.world.get::<CameraController>(camera).unwrap().yaw, 270.0);
+}
+
+#[test]
+fn test_follow_selected_unit_toggle() {
+    let mut app = create_camera_app();
+    let camera = spawn_controlled_camera(&mut app, CameraController::default());
+    let unit = app.world.spawn((Selected, TransformBundle::from_transform(Transform::from_xyz(3.0, 0.5, -2.0)))).id();
+
+    tap_key(&mut app, FOLLOW_TOGGLE_KEY);
+    let controller = app.world.get::<CameraController>(camera).unwrap();
+    assert!(controller.follow_selected);
+    assert!((controller.focus - Vec3::new(3.0, 0.0, -2.0)).length() < 0.001, "Got {:?}", controller.focus);
+
+    app.world.get_mut::<Transform>(unit).unwrap().translation = Vec3::new(4.0, 0.5, 1.0);
+    app.update();
+    app.update();
+    let controller = app.world.get::<CameraController>(camera).unwrap();
+    assert!((controller.focus - Vec3::new(4.0, 0.0, 1.0)).length() < 0.001, "Camera should keep following, got {:?}", controller.focus);
+
+    tap_key(&mut app, FOLLOW_TOGGLE_KEY);
+    app.world.get_mut::<Transform>(unit).unwrap().translation = Vec3::new(-4.0, 0.5, 0.0);
+    app.update();
+    app.update();
+    let controller = app.world.get::<CameraController>(camera).unwrap();
+    assert!(!controller.follow_selected);
+    assert!((controller.focus - Vec3::new(4.0, 0.0, 1.0)).length() < 0.001);
+}
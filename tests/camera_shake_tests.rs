@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::camera::*;
+
+fn create_shake_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Input::<KeyCode>::default());
+    app.add_event::<bevy::input::mouse::MouseWheel>();
+    app.add_plugins((CameraControllerPlugin, CameraShakePlugin));
+    app
+}
+
+fn spawn_shake_camera(app: &mut App) -> Entity {
+    let controller = CameraController::default();
+    app.world.spawn((
+        Camera3dBundle {
+            transform: controller.camera_transform(),
+            projection: OrthographicProjection {
+                scale: controller.zoom,
+                ..default()
+            }.into(),
+            ..default()
+        },
+        controller,
+        CameraShake::default(),
+    )).id()
+}
+
+fn explosion_at(position: Vec3) -> CameraShakeEvent {
+    CameraShakeEvent {
+        source: CameraShakeSource::Explosion,
+        position,
+    }
+}
+
+#[test]
+fn test_explosion_trauma_falls_off_with_distance() {
+    let settings = CameraShakeSettings::default();
+
+    let close = trauma_for_event(&settings, &explosion_at(Vec3::ZERO), Vec3::ZERO);
+    let mid = trauma_for_event(&settings, &explosion_at(Vec3::new(settings.falloff_radius * 0.5, 0.0, 0.0)), Vec3::ZERO);
+    let far = trauma_for_event(&settings, &explosion_at(Vec3::new(0.0, 0.0, settings.falloff_radius + 1.0)), Vec3::ZERO);
+
+    assert!((close - settings.explosion_trauma).abs() < 0.001);
+    assert!((mid - settings.explosion_trauma * 0.5).abs() < 0.001);
+    assert_eq!(far, 0.0);
+}
+
+#[test]
+fn test_own_mech_hit_ignores_distance() {
+    let settings = CameraShakeSettings::default();
+    let event = CameraShakeEvent {
+        source: CameraShakeSource::OwnMechHit,
+        position: Vec3::new(100.0, 0.0, 100.0),
+    };
+
+    assert_eq!(trauma_for_event(&settings, &event, Vec3::ZERO), settings.own_mech_hit_trauma);
+}
+
+#[test]
+fn test_trauma_is_capped_and_decays_back_to_rest() {
+    let mut app = create_shake_app();
+    let camera = spawn_shake_camera(&mut app);
+    app.update();
+    let rest_transform = *app.world.get::<Transform>(camera).unwrap();
+
+    for _ in 0..5 {
+        app.world.send_event(explosion_at(Vec3::ZERO));
+    }
+    app.update();
+
+    let shake = app.world.get::<CameraShake>(camera).unwrap();
+    assert!(shake.trauma <= 1.0, "Trauma should be capped, got {}", shake.trauma);
+    assert!(shake.trauma > 0.8);
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let shake = app.world.get::<CameraShake>(camera).unwrap();
+    assert_eq!(shake.trauma, 0.0);
+
+    // One more frame to remove the last applied offset
+    app.update();
+    let transform = app.world.get::<Transform>(camera).unwrap();
+    assert!(transform.translation.distance(rest_transform.translation) < 0.001);
+    assert!(transform.rotation.angle_between(rest_transform.rotation) < 0.001);
+}
+
+#[test]
+fn test_shake_does_not_drift_camera_over_time() {
+    let mut app = create_shake_app();
+    let camera = spawn_shake_camera(&mut app);
+    app.update();
+    let rest_translation = app.world.get::<Transform>(camera).unwrap().translation;
+
+    let mut max_offset: f32 = 0.0;
+    for _ in 0..30 {
+        app.world.send_event(explosion_at(Vec3::ZERO));
+        app.update();
+        let translation = app.world.get::<Transform>(camera).unwrap().translation;
+        max_offset = max_offset.max(translation.distance(rest_translation));
+    }
+
+    let shake = app.world.get::<CameraShake>(camera).unwrap();
+    assert!(max_offset > 0.0, "Camera should visibly shake");
+    assert!(max_offset <= shake.max_offset * 2.0_f32.sqrt() + 0.001, "Offset {} exceeds the configured limit", max_offset);
+}
+
+#[test]
+fn test_disabled_setting_suppresses_shake() {
+    let mut app = create_shake_app();
+    app.world.resource_mut::<CameraShakeSettings>().enabled = false;
+    let camera = spawn_shake_camera(&mut app);
+    app.update();
+    let rest_translation = app.world.get::<Transform>(camera).unwrap().translation;
+
+    app.world.send_event(explosion_at(Vec3::ZERO));
+    app.update();
+
+    assert_eq!(app.world.get::<CameraShake>(camera).unwrap().trauma, 0.0);
+    assert_eq!(app.world.get::<Transform>(camera).unwrap().translation, rest_translation);
+}
+
+#[test]
+fn test_shake_offset_scales_with_trauma_squared() {
+    let mut shake = CameraShake { trauma: 1.0, ..default() };
+    let (full_offset, full_roll) = calculate_shake_offset(&shake, 0.37);
+
+    shake.trauma = 0.5;
+    let (half_offset, half_roll) = calculate_shake_offset(&shake, 0.37);
+
+    assert!((half_offset - full_offset * 0.25).length() < 0.0001);
+    assert!((half_roll - full_roll * 0.25).abs() < 0.0001);
+}
@@ -53,4 +53,81 @@ mod camera_transformation_tests {
         
         assert!((actual_angle - angle_degrees).abs() < 0.1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ray_plane_intersection() {
+        let hit = ray_plane_intersection(Vec3::new(1.0, 10.0, 2.0), Vec3::NEG_Y, 0.0);
+        assert_eq!(hit, Some(Vec3::new(1.0, 0.0, 2.0)));
+        
+        let hit = ray_plane_intersection(Vec3::new(0.0, 10.0, 0.0), Vec3::new(0.0, -1.0, 1.0).normalize(), 2.0).unwrap();
+        assert!((hit - Vec3::new(0.0, 2.0, 8.0)).length() < 0.001);
+        
+        // Parallel to the ground and pointing away from it never hit
+        assert!(ray_plane_intersection(Vec3::new(0.0, 10.0, 0.0), Vec3::X, 0.0).is_none());
+        assert!(ray_plane_intersection(Vec3::new(0.0, 10.0, 0.0), Vec3::Y, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_screen_center_hits_camera_look_at_point() {
+        let camera_transform = create_orthographic_camera_transform(CAMERA_ANGLE_DEGREES);
+        
+        let world_pos = screen_to_world_position(Vec2::new(640.0, 360.0), &camera_transform, 1280.0, 720.0, 0.01).unwrap();
+        
+        assert!(world_pos.length() < 0.001, "Screen center should hit the look-at point, got {:?}", world_pos);
+    }
+
+    #[test]
+    fn test_screen_to_world_accounts_for_camera_tilt() {
+        let camera_transform = create_orthographic_camera_transform(CAMERA_ANGLE_DEGREES);
+        let scale = 0.01;
+        
+        // Moving the cursor 100px down the screen moves the ground hit towards the camera (+Z),
+        // stretched by 1/sin(tilt) because the view plane is angled to the ground
+        let world_pos = screen_to_world_position(Vec2::new(640.0, 460.0), &camera_transform, 1280.0, 720.0, scale).unwrap();
+        let expected_z = 100.0 * scale / CAMERA_ANGLE_DEGREES.to_radians().sin();
+        
+        assert!(world_pos.x.abs() < 0.001);
+        assert!((world_pos.z - expected_z).abs() < 0.001, "Expected z={:.3}, got {:?}", expected_z, world_pos);
+        
+        // Horizontal cursor motion is not foreshortened
+        let world_pos = screen_to_world_position(Vec2::new(740.0, 360.0), &camera_transform, 1280.0, 720.0, scale).unwrap();
+        assert!((world_pos.x - 100.0 * scale).abs() < 0.001);
+        assert!(world_pos.z.abs() < 0.001);
+    }
+
+    #[test]
+    fn test_screen_to_world_follows_camera_pan() {
+        let pan = Vec3::new(5.0, 0.0, -3.0);
+        let mut camera_transform = create_orthographic_camera_transform(CAMERA_ANGLE_DEGREES);
+        camera_transform.translation += pan;
+        
+        let world_pos = screen_to_world_position(Vec2::new(640.0, 360.0), &camera_transform, 1280.0, 720.0, 0.01).unwrap();
+        
+        assert!((world_pos - pan).length() < 0.001, "Panned camera center should hit {:?}, got {:?}", pan, world_pos);
+    }
+
+    #[test]
+    fn test_screen_to_world_follows_camera_zoom() {
+        let camera_transform = create_orthographic_camera_transform(CAMERA_ANGLE_DEGREES);
+        let cursor = Vec2::new(900.0, 200.0);
+        
+        let near = screen_to_world_position(cursor, &camera_transform, 1280.0, 720.0, 0.01).unwrap();
+        let far = screen_to_world_position(cursor, &camera_transform, 1280.0, 720.0, 0.02).unwrap();
+        
+        // Zooming out doubles the distance from the look-at point for the same pixel
+        assert!((far - near * 2.0).length() < 0.001, "near={:?}, far={:?}", near, far);
+    }
+
+    #[test]
+    fn test_screen_world_round_trip() {
+        let mut camera_transform = create_orthographic_camera_transform(CAMERA_ANGLE_DEGREES);
+        camera_transform.translation += Vec3::new(-2.0, 0.0, 4.0);
+        
+        for world_pos in [Vec3::ZERO, Vec3::new(3.0, 0.0, -2.0), Vec3::new(-6.5, 0.0, 1.25)] {
+            let screen_pos = world_to_screen_position(world_pos, &camera_transform, 1280.0, 720.0, 0.01);
+            let picked = screen_to_world_position(screen_pos, &camera_transform, 1280.0, 720.0, 0.01).unwrap();
+            
+            assert!((picked - world_pos).length() < 0.01, "Round trip of {:?} gave {:?}", world_pos, picked);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+use bevy_rapier3d::rapier::geometry::CollisionEventFlags;
+use rust_and_ruin::camera::CameraShakeEvent;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::systems::*;
+
+fn create_explosion_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    // Rapier's async collider systems expect mesh and scene assets to exist
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<Scene>::default());
+    app.init_resource::<bevy::scene::SceneSpawner>();
+    app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
+    app.add_event::<DamageEvent>();
+    app.add_event::<EnemyDestroyedEvent>();
+    app.add_event::<ExplosionEvent>();
+    app.add_event::<CameraShakeEvent>();
+    app.add_systems(Update, explosion_system);
+    app
+}
+
+fn spawn_enemy(app: &mut App, position: Vec3) -> Entity {
+    app.world.spawn((
+        Enemy,
+        Health::new(100.0),
+        TransformBundle::from_transform(Transform::from_translation(position)),
+        Collider::cuboid(0.5, 0.5, 0.5),
+        ExternalImpulse::default(),
+    )).id()
+}
+
+fn explode(app: &mut App, explosion: ExplosionEvent) {
+    // Let Rapier pick up any colliders spawned since the last frame first
+    app.update();
+    app.world.send_event(explosion);
+    app.update();
+}
+
+fn health(app: &App, entity: Entity) -> f32 {
+    app.world.get::<Health>(entity).unwrap().current
+}
+
+#[test]
+fn test_falloff_curves() {
+    assert_eq!(DamageFalloff::Constant.scale(2.0, 4.0), 1.0);
+    assert_eq!(DamageFalloff::Linear.scale(1.0, 4.0), 0.75);
+    assert_eq!(DamageFalloff::Quadratic.scale(2.0, 4.0), 0.75);
+    assert_eq!(DamageFalloff::Linear.scale(0.0, 4.0), 1.0);
+    assert_eq!(DamageFalloff::Quadratic.scale(4.5, 4.0), 0.0, "Nothing outside the radius");
+
+    let push = blast_impulse(Vec3::ZERO, Vec3::new(3.0, 5.0, 4.0), 10.0);
+    assert!((push - Vec3::new(6.0, 0.0, 8.0)).length() < 0.001, "got {:?}", push);
+}
+
+#[test]
+fn test_explosion_damages_everything_in_radius_with_falloff() {
+    let mut app = create_explosion_app();
+    let near = spawn_enemy(&mut app, Vec3::new(1.0, 0.0, 0.0));
+    let far = spawn_enemy(&mut app, Vec3::new(0.0, 0.0, -3.0));
+    let outside = spawn_enemy(&mut app, Vec3::new(10.0, 0.0, 0.0));
+
+    explode(&mut app, ExplosionEvent::new(Vec3::ZERO, 4.0, 40.0, 20.0));
+
+    assert!((health(&app, near) - 70.0).abs() < 0.01, "got {}", health(&app, near));
+    assert!((health(&app, far) - 90.0).abs() < 0.01, "got {}", health(&app, far));
+    assert_eq!(health(&app, outside), 100.0);
+
+    let impulse = app.world.get::<ExternalImpulse>(near).unwrap().impulse;
+    assert!(impulse.x > 0.0 && impulse.z.abs() < 0.001, "Pushed away from the blast, got {:?}", impulse);
+    assert_eq!(app.world.get::<ExternalImpulse>(outside).unwrap().impulse, Vec3::ZERO);
+}
+
+#[test]
+fn test_walls_shelter_targets_behind_them() {
+    let mut app = create_explosion_app();
+    let exposed = spawn_enemy(&mut app, Vec3::new(-2.0, 0.0, 0.0));
+    let sheltered = spawn_enemy(&mut app, Vec3::new(2.5, 0.0, 0.0));
+    app.world.spawn((
+        Wall,
+        TransformBundle::from_transform(Transform::from_xyz(1.2, 0.0, 0.0)),
+        Collider::cuboid(0.2, 1.0, 2.0),
+    ));
+
+    explode(&mut app, ExplosionEvent::new(Vec3::ZERO, 4.0, 40.0, 20.0));
+    assert!(health(&app, exposed) < 100.0);
+    assert_eq!(health(&app, sheltered), 100.0);
+
+    let mut unoccluded = ExplosionEvent::new(Vec3::ZERO, 4.0, 40.0, 20.0);
+    unoccluded.occluded_by_walls = false;
+    explode(&mut app, unoccluded);
+    assert!(health(&app, sheltered) < 100.0, "Occlusion can be switched off");
+}
+
+#[test]
+fn test_explosion_damages_mech_parts_but_spares_its_source() {
+    let mut app = create_explosion_app();
+    let mech = app.world.spawn(TransformBundle::default()).id();
+    let upper = app.world.spawn((
+        PartHealth::upper(),
+        TransformBundle::from_transform(Transform::from_xyz(1.0, 0.0, 0.0)),
+        Collider::ball(0.5),
+        Sensor,
+    )).id();
+    app.world.entity_mut(mech).push_children(&[upper]);
+
+    explode(&mut app, ExplosionEvent::new(Vec3::ZERO, 2.0, 40.0, 0.0).with_source(mech));
+    assert_eq!(app.world.get::<PartHealth>(upper).unwrap().current, UPPER_BODY_HIT_POINTS);
+
+    explode(&mut app, ExplosionEvent::new(Vec3::ZERO, 2.0, 40.0, 0.0).with_falloff(DamageFalloff::Constant));
+    assert_eq!(app.world.get::<PartHealth>(upper).unwrap().current, UPPER_BODY_HIT_POINTS - 40.0);
+}
+
+#[test]
+fn test_lethal_blast_destroys_enemy() {
+    let mut app = create_explosion_app();
+    let enemy = spawn_enemy(&mut app, Vec3::new(0.5, 0.0, 0.0));
+
+    explode(&mut app, ExplosionEvent::new(Vec3::ZERO, 3.0, 500.0, 0.0));
+    assert!(app.world.get_entity(enemy).is_none());
+}
+
+#[test]
+fn test_fragment_shell_damages_struck_enemy_once() {
+    let mut app = create_explosion_app();
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.add_systems(Update, collision_detection_system.before(explosion_system));
+
+    let struck = spawn_enemy(&mut app, Vec3::ZERO);
+    let bystander = spawn_enemy(&mut app, Vec3::new(2.5, 0.0, 0.0));
+    let shell = app.world.spawn((
+        Projectile { damage: 20.0, speed: 15.0 },
+        TankShell { velocity: Vec2::NEG_X * 15.0, spawn_position: Vec2::new(5.0, 0.0), max_range: 15.0 },
+        FragmentShell,
+        Velocity::linear(Vec3::NEG_X * 15.0),
+        TransformBundle::from_transform(Transform::from_xyz(1.5, 0.0, 0.0)),
+    )).id();
+
+    // Let Rapier pick up the colliders before the shell lands
+    app.update();
+    app.world.send_event(CollisionEvent::Started(shell, struck, CollisionEventFlags::empty()));
+    app.update();
+    app.update();
+
+    assert_eq!(health(&app, struck), 80.0, "The struck enemy takes the direct hit and no splash");
+    // 1.0 from the blast: 20 * 0.5 at the centre, scaled by 0.6 with linear falloff
+    assert!((health(&app, bystander) - 94.0).abs() < 0.01, "got {}", health(&app, bystander));
+}
+
+#[test]
+fn test_high_explosive_shell_splashes_nearby_units() {
+    let mut app = create_explosion_app();
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.add_systems(Update, collision_detection_system.before(explosion_system));
+
+    let struck = spawn_enemy(&mut app, Vec3::ZERO);
+    let bystander = spawn_enemy(&mut app, Vec3::new(2.5, 0.0, 0.0));
+    let shell = app.world.spawn((
+        Projectile { damage: 20.0, speed: 15.0 },
+        TankShell { velocity: Vec2::NEG_X * 15.0, spawn_position: Vec2::new(5.0, 0.0), max_range: 15.0 },
+        AmmoType::HighExplosive,
+        Velocity::linear(Vec3::NEG_X * 15.0),
+        TransformBundle::from_transform(Transform::from_xyz(1.5, 0.0, 0.0)),
+    )).id();
+
+    app.update();
+    app.world.send_event(CollisionEvent::Started(shell, struck, CollisionEventFlags::empty()));
+    app.update();
+    app.update();
+
+    assert_eq!(health(&app, struck), 80.0, "The struck enemy takes the direct hit and no splash");
+    // 20 * ARTILLERY_BLAST_DAMAGE at the centre, scaled by 0.75 with linear falloff
+    assert!((health(&app, bystander) - 85.0).abs() < 0.01, "got {}", health(&app, bystander));
+    assert!(app.world.get_entity(shell).is_none());
+}
+
+#[test]
+fn test_artillery_blast_ignores_walls() {
+    let explosion = ExplosionEvent::artillery(Vec3::ZERO, 50.0);
+    assert_eq!(explosion.radius, ARTILLERY_BLAST_RADIUS);
+    assert_eq!(explosion.damage, 50.0 * ARTILLERY_BLAST_DAMAGE);
+
+    let mut app = create_explosion_app();
+    let sheltered = spawn_enemy(&mut app, Vec3::new(2.5, 0.0, 0.0));
+    app.world.spawn((
+        Wall,
+        TransformBundle::from_transform(Transform::from_xyz(1.2, 0.0, 0.0)),
+        Collider::cuboid(0.2, 1.0, 2.0),
+    ));
+
+    explode(&mut app, explosion);
+    assert!(health(&app, sheltered) < 100.0, "Shells falling from above land behind cover");
+}
@@ -0,0 +1,211 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_rapier3d::prelude::{Collider, NoUserData, RapierPhysicsPlugin};
+use std::time::Duration;
+use rust_and_ruin::camera::CameraShakeEvent;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::resources::MouseWorldPosition;
+use rust_and_ruin::systems::*;
+
+const MAP_SIZE: f32 = 40.0;
+
+fn create_fog_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    // Rapier's async collider systems expect mesh and scene assets to exist
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<Scene>::default());
+    app.init_resource::<bevy::scene::SceneSpawner>();
+    app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
+    app.init_resource::<SpatialIndex>();
+    app.insert_resource(FogOfWar::new(Vec2::splat(MAP_SIZE), FOG_CELL_SIZE));
+    app.add_systems(PreUpdate, spatial_index_system);
+    app.add_systems(Update, (fog_of_war_system, enemy_fog_visibility_system).chain());
+    app
+}
+
+fn spawn_hero(app: &mut App, position: Vec2, vision: f32) -> Entity {
+    app.world.spawn((
+        Hero,
+        VisionRange(vision),
+        TransformBundle::from_transform(Transform::from_xyz(position.x, 0.0, position.y)),
+    )).id()
+}
+
+fn spawn_enemy(app: &mut App, position: Vec2) -> Entity {
+    app.world.spawn((
+        Enemy,
+        VisionRange(ENEMY_VISION_RANGE),
+        SpatialBundle::from_transform(Transform::from_xyz(position.x, 0.75, position.y)),
+    )).id()
+}
+
+fn spawn_wall(app: &mut App, position: Vec2, half_extents: Vec3) -> Entity {
+    app.world.spawn((
+        Wall,
+        Collider::cuboid(half_extents.x, half_extents.y, half_extents.z),
+        TransformBundle::from_transform(Transform::from_xyz(position.x, half_extents.y, position.y)),
+    )).id()
+}
+
+/// Runs two frames so Rapier has picked up freshly spawned colliders
+/// and the spatial index has the new units.
+fn settle(app: &mut App) {
+    app.update();
+    app.update();
+}
+
+fn player_fog(app: &App) -> &FogGrid {
+    app.world.resource::<FogOfWar>().grid(UnitSide::Friendly)
+}
+
+#[test]
+fn test_units_reveal_cells_within_their_vision_range() {
+    let mut app = create_fog_app();
+    spawn_hero(&mut app, Vec2::ZERO, 6.0);
+    settle(&mut app);
+
+    assert_eq!(player_fog(&app).state_at(Vec2::new(3.0, 0.0)), FogState::Visible);
+    assert_eq!(player_fog(&app).state_at(Vec2::new(0.0, -5.0)), FogState::Visible);
+    assert_eq!(player_fog(&app).state_at(Vec2::new(10.0, 0.0)), FogState::Unseen);
+    assert_eq!(player_fog(&app).state_at(Vec2::new(30.0, 0.0)), FogState::Unseen, "Off the map");
+    let enemy_fog = app.world.resource::<FogOfWar>().grid(UnitSide::Enemy);
+    assert_eq!(enemy_fog.state_at(Vec2::ZERO), FogState::Unseen, "Each side has its own fog");
+}
+
+#[test]
+fn test_ground_left_behind_stays_explored() {
+    let mut app = create_fog_app();
+    let hero = spawn_hero(&mut app, Vec2::ZERO, 6.0);
+    settle(&mut app);
+
+    app.world.get_mut::<Transform>(hero).unwrap().translation = Vec3::new(12.0, 0.0, 0.0);
+    app.update();
+
+    assert_eq!(player_fog(&app).state_at(Vec2::new(-4.0, 0.0)), FogState::Explored);
+    assert_eq!(player_fog(&app).state_at(Vec2::new(15.0, 0.0)), FogState::Visible);
+    assert_eq!(player_fog(&app).state_at(Vec2::new(-15.0, 0.0)), FogState::Unseen);
+}
+
+/// Frames in which the fog was marked changed.
+#[derive(Resource, Default)]
+struct FogChanges(usize);
+
+fn count_fog_changes(fog: Res<FogOfWar>, mut changes: ResMut<FogChanges>) {
+    if fog.is_changed() {
+        changes.0 += 1;
+    }
+}
+
+#[test]
+fn test_fog_only_changes_when_the_view_does() {
+    let mut app = create_fog_app();
+    app.init_resource::<FogChanges>();
+    app.add_systems(Update, count_fog_changes.after(fog_of_war_system));
+    let hero = spawn_hero(&mut app, Vec2::ZERO, 6.0);
+    settle(&mut app);
+
+    app.world.resource_mut::<FogChanges>().0 = 0;
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(app.world.resource::<FogChanges>().0, 0, "A still scene leaves the overlay alone");
+
+    app.world.get_mut::<Transform>(hero).unwrap().translation = Vec3::new(5.0, 0.0, 0.0);
+    app.update();
+    app.update();
+    assert_eq!(app.world.resource::<FogChanges>().0, 1);
+}
+
+#[test]
+fn test_walls_block_line_of_sight() {
+    let mut app = create_fog_app();
+    spawn_wall(&mut app, Vec2::new(3.0, 0.0), Vec3::new(0.25, 1.5, 3.0));
+    settle(&mut app);
+    let hero = spawn_hero(&mut app, Vec2::ZERO, 12.0);
+    let hidden = spawn_enemy(&mut app, Vec2::new(8.0, 0.0));
+    let exposed = spawn_enemy(&mut app, Vec2::new(0.0, 8.0));
+    settle(&mut app);
+
+    let fog = app.world.resource::<FogOfWar>();
+    assert!(fog.can_see(UnitSide::Friendly, exposed));
+    assert!(!fog.can_see(UnitSide::Friendly, hidden), "The wall is between the hero and this enemy");
+    assert!(fog.can_see(UnitSide::Enemy, hero), "Enemies see the hero through the same rules");
+    assert!(!fog.can_see(UnitSide::Friendly, hero), "Sides only track opposing units");
+    assert_eq!(player_fog(&app).state_at(Vec2::new(8.0, 0.0)), FogState::Unseen);
+    assert_eq!(player_fog(&app).state_at(Vec2::new(4.0, 8.0)), FogState::Visible, "Sight goes around the wall's end");
+
+    assert_eq!(app.world.get::<Visibility>(hidden), Some(&Visibility::Hidden));
+    assert_eq!(app.world.get::<Visibility>(exposed), Some(&Visibility::Inherited));
+}
+
+#[test]
+fn test_ridges_block_line_of_sight() {
+    let mut app = create_fog_app();
+    // Ridge along x = 4 on an otherwise flat map
+    let samples = 21;
+    let heights = (0..samples * samples)
+        .map(|i| if i % samples == 12 { 5.0 } else { 0.0 })
+        .collect();
+    app.insert_resource(Heightmap::new(samples, samples, Vec2::splat(MAP_SIZE), heights));
+    spawn_hero(&mut app, Vec2::ZERO, 12.0);
+    let behind_ridge = spawn_enemy(&mut app, Vec2::new(8.0, 0.0));
+    let in_valley = spawn_enemy(&mut app, Vec2::new(-8.0, 0.0));
+    settle(&mut app);
+
+    let fog = app.world.resource::<FogOfWar>();
+    assert!(!fog.can_see(UnitSide::Friendly, behind_ridge));
+    assert!(fog.can_see(UnitSide::Friendly, in_valley));
+    assert_eq!(player_fog(&app).state_at(Vec2::new(9.0, 0.0)), FogState::Unseen);
+}
+
+#[test]
+fn test_overlay_pixels_follow_fog_state() {
+    let mut grid = FogGrid::new(Vec2::splat(4.0), 2.0);
+    grid.reveal(UVec2::new(0, 0));
+    grid.begin_update();
+    grid.reveal(UVec2::new(1, 0));
+
+    let alphas: Vec<u8> = grid.overlay_pixels().chunks(4).map(|pixel| pixel[3]).collect();
+    let alpha = |value: f32| (value * 255.0).round() as u8;
+    assert_eq!(alphas, vec![alpha(EXPLORED_FOG_ALPHA), 0, alpha(UNSEEN_FOG_ALPHA), alpha(UNSEEN_FOG_ALPHA)]);
+}
+
+#[test]
+fn test_weapons_hold_fire_on_targets_in_the_fog() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.init_resource::<MouseWorldPosition>();
+    app.insert_resource(FogOfWar::new(Vec2::splat(MAP_SIZE), FOG_CELL_SIZE));
+    app.add_event::<CameraShakeEvent>();
+    app.add_systems(Update, weapon_control_system);
+
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 8.0)))).id();
+    let hero = app.world.spawn((Hero, AttackTarget { entity: enemy }, TransformBundle::default())).id();
+    let upper = app.world.spawn((
+        create_turret_upper(),
+        MechRotation { target_angle: 0.0, current_angle: 0.0 },
+        TransformBundle::default(),
+    )).id();
+    let mut weapon = create_cannon_weapon("main".to_string());
+    weapon.last_fire_time = weapon.weapon_stats.fire_rate;
+    let weapon = app.world.spawn((weapon, CannonWeapon::default(), TransformBundle::default())).id();
+    app.world.entity_mut(hero).push_children(&[upper]);
+    app.world.entity_mut(upper).push_children(&[weapon]);
+
+    let shells = |app: &mut App| app.world.query::<&TankShell>().iter(&app.world).count();
+    app.update();
+    app.update();
+    assert_eq!(shells(&mut app), 0, "The target is in range and in the arc, but unseen");
+
+    app.world.resource_mut::<FogOfWar>().grid_mut(UnitSide::Friendly).reveal_unit(enemy);
+    app.update();
+    assert_eq!(shells(&mut app), 1);
+    assert!(can_target(None, UnitSide::Friendly, enemy), "Without fog of war everything can be targeted");
+}
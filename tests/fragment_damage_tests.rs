@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::{CollisionEvent, ExternalImpulse, Velocity};
+use bevy_rapier3d::rapier::geometry::CollisionEventFlags;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::systems::*;
+
+fn create_fragment_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.add_event::<CollisionEvent>();
+    app.add_event::<DamageEvent>();
+    app.add_event::<EnemyDestroyedEvent>();
+    app.add_event::<ExplosionEvent>();
+    app.add_systems(Update, (collision_detection_system, fragment_collision_system));
+    app
+}
+
+fn spawn_enemy(app: &mut App, x: f32) -> Entity {
+    app.world.spawn((
+        Enemy,
+        Health::new(100.0),
+        ExternalImpulse::default(),
+        TransformBundle::from_transform(Transform::from_xyz(x, 0.75, 0.0)),
+    )).id()
+}
+
+fn spawn_fragment(app: &mut App, struck_target: Option<Entity>) -> Entity {
+    app.world.spawn((
+        Projectile { damage: 10.0, speed: 10.0 },
+        ShellFragment {
+            parent_velocity: Vec2::X * 15.0,
+            lifetime: Timer::from_seconds(1.0, TimerMode::Once),
+            max_distance: 3.0,
+            spawn_position: Vec2::ZERO,
+            fragment_index: 0,
+            struck_target,
+            penetrations_left: FRAGMENT_PENETRATIONS,
+        },
+        TransformBundle::default(),
+    )).id()
+}
+
+fn touch(app: &mut App, a: Entity, b: Entity) {
+    app.world.send_event(CollisionEvent::Started(a, b, CollisionEventFlags::SENSOR));
+    app.update();
+}
+
+fn health(app: &App, entity: Entity) -> f32 {
+    app.world.get::<Health>(entity).unwrap().current
+}
+
+#[test]
+fn test_fragment_damage_comes_from_parent_shell() {
+    assert!((calculate_fragment_damage(30.0) - 10.0).abs() < 0.001);
+}
+
+#[test]
+fn test_fragment_ignores_struck_target_and_penetrates_one_more() {
+    let mut app = create_fragment_app();
+    let struck = spawn_enemy(&mut app, 0.0);
+    let first = spawn_enemy(&mut app, 2.0);
+    let second = spawn_enemy(&mut app, 4.0);
+    let third = spawn_enemy(&mut app, 6.0);
+    let fragment = spawn_fragment(&mut app, Some(struck));
+
+    touch(&mut app, fragment, struck);
+    assert_eq!(health(&app, struck), 100.0, "Fragments fly clear of the enemy their shell hit");
+
+    touch(&mut app, first, fragment);
+    assert_eq!(health(&app, first), 90.0);
+    assert!(app.world.get_entity(fragment).is_some(), "A fragment can pass through one target");
+
+    touch(&mut app, fragment, second);
+    assert_eq!(health(&app, second), 90.0);
+    assert!(app.world.get_entity(fragment).is_none(), "...and is spent on the next");
+
+    app.world.send_event(CollisionEvent::Started(fragment, third, CollisionEventFlags::SENSOR));
+    app.update();
+    assert_eq!(health(&app, third), 100.0);
+}
+
+#[test]
+fn test_walls_stop_fragments() {
+    let mut app = create_fragment_app();
+    let wall = app.world.spawn((Wall, TransformBundle::default())).id();
+    let fragment = spawn_fragment(&mut app, None);
+
+    touch(&mut app, fragment, wall);
+    assert!(app.world.get_entity(fragment).is_none());
+}
+
+#[test]
+fn test_fragments_damage_enemy_mech_parts_but_not_their_own() {
+    let mut app = create_fragment_app();
+    let shooter = app.world.spawn(TransformBundle::default()).id();
+    let own_weapon = app.world.spawn((PartHealth::weapon(), TransformBundle::default())).id();
+    app.world.entity_mut(shooter).push_children(&[own_weapon]);
+    let enemy_weapon = app.world.spawn((PartHealth::weapon(), TransformBundle::default())).id();
+
+    let fragment = spawn_fragment(&mut app, None);
+    app.world.entity_mut(fragment).insert(FiredBy(shooter));
+
+    touch(&mut app, fragment, own_weapon);
+    touch(&mut app, fragment, enemy_weapon);
+    assert_eq!(app.world.get::<PartHealth>(own_weapon).unwrap().current, WEAPON_HIT_POINTS);
+    assert_eq!(app.world.get::<PartHealth>(enemy_weapon).unwrap().current, WEAPON_HIT_POINTS - 10.0);
+}
+
+#[test]
+fn test_fragment_shell_impact_spawns_fragments_that_skip_the_struck_enemy() {
+    let mut app = create_fragment_app();
+    let enemy = spawn_enemy(&mut app, 3.0);
+    let shooter = app.world.spawn_empty().id();
+    let shell = app.world.spawn((
+        Projectile { damage: 30.0, speed: 15.0 },
+        TankShell { velocity: Vec2::X * 15.0, spawn_position: Vec2::ZERO, max_range: 15.0 },
+        FragmentShell,
+        FiredBy(shooter),
+        Velocity::linear(Vec3::X * 15.0),
+        TransformBundle::from_transform(Transform::from_xyz(2.3, 0.75, 0.0)),
+    )).id();
+
+    touch(&mut app, shell, enemy);
+
+    let mut fragments = app.world.query::<(&Projectile, &ShellFragment, &FiredBy)>();
+    let fragments: Vec<_> = fragments.iter(&app.world).collect();
+    assert_eq!(fragments.len(), 3);
+    for (projectile, fragment, fired_by) in fragments {
+        assert!((projectile.damage - 10.0).abs() < 0.001);
+        assert_eq!(fragment.struck_target, Some(enemy));
+        assert_eq!(fired_by.0, shooter);
+    }
+}
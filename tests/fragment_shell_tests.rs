@@ -21,6 +21,8 @@ mod fragment_shell_tests {
             max_distance: 2.0,
             spawn_position: Vec2::ZERO,
             fragment_index: 0,
+            struck_target: None,
+            penetrations_left: 1,
         };
         
         assert_eq!(fragment.parent_velocity, Vec2::new(10.0, 0.0));
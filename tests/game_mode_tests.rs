@@ -0,0 +1,322 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::components::*;
+use rust_and_ruin::game_mode::*;
+use rust_and_ruin::map::{MapDefinition, MapPlugin, MapTag, ObjectiveDefinition, ObjectiveKind, TriggerVolume};
+use rust_and_ruin::mech::PartHealth;
+use rust_and_ruin::resources::{GameOutcome, GameState};
+use rust_and_ruin::systems::Heightmap;
+use rust_and_ruin::systems::projectile_pool::{PoolKind, Pooled, ProjectilePool};
+
+const SPAWN_POINT: Vec2 = Vec2::new(10.0, 0.0);
+
+const SURVIVAL_MAP: &str = r#"(
+    name: "Survival",
+    size: (40.0, 40.0),
+    spawns: [
+        (side: Player, position: (-5.0, 0.0)),
+        (side: Enemy, position: (10.0, 0.0)),
+    ],
+)"#;
+
+fn single_wave(enemies: u32) -> WaveDirector {
+    let wave = WaveDefinition { delay: 0.5, enemies, enemy_health: 150.0 };
+    WaveDirector::new(vec![wave], vec![SPAWN_POINT])
+}
+
+fn create_game_app(waves: WaveDirector) -> App {
+    create_mission_app(waves, ObjectiveTracker::default())
+}
+
+fn create_mission_app(waves: WaveDirector, objectives: ObjectiveTracker) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.insert_resource(Input::<KeyCode>::default());
+    app.add_plugins(GameModePlugin { waves, objectives });
+    app.world.spawn((Hero, TransformBundle::default()));
+    app.update();
+    app
+}
+
+/// Taps `key` and runs the frame that applies the resulting state change.
+fn press(app: &mut App, key: KeyCode) {
+    app.world.resource_mut::<Input<KeyCode>>().press(key);
+    app.update();
+    let mut input = app.world.resource_mut::<Input<KeyCode>>();
+    input.release(key);
+    input.clear();
+    app.update();
+}
+
+fn state(app: &App) -> AppState {
+    *app.world.resource::<State<AppState>>().get()
+}
+
+fn game_state(app: &App) -> &GameState {
+    app.world.resource::<GameState>()
+}
+
+fn count<T: Component>(app: &mut App) -> usize {
+    app.world.query_filtered::<(), With<T>>().iter(&app.world).count()
+}
+
+#[test]
+fn test_waves_escalate_on_a_schedule() {
+    let mut director = WaveDirector::escalating(3, vec![SPAWN_POINT]);
+    let waves = director.waves().to_vec();
+    assert_eq!(waves.iter().map(|wave| wave.enemies).collect::<Vec<_>>(), vec![2, 3, 4]);
+    assert!(waves[1].enemy_health > waves[0].enemy_health && waves[2].enemy_health > waves[1].enemy_health);
+
+    assert_eq!(director.tick(FIRST_WAVE_DELAY - 1.0), None);
+    assert_eq!(director.tick(1.5), Some(0));
+    assert_eq!(director.tick(WAVE_INTERVAL - 1.0), None);
+    assert_eq!(director.tick(1.0), Some(1), "The overshoot of the first wave counts towards the second");
+    assert!(!director.is_finished());
+    assert_eq!(director.tick(WAVE_INTERVAL), Some(2));
+    assert!(director.is_finished());
+    assert_eq!(director.tick(WAVE_INTERVAL), None);
+    assert_eq!(director.time_to_next_wave(), None);
+
+    director.reset();
+    assert_eq!(director.waves_spawned(), 0);
+    assert_eq!(director.time_to_next_wave(), Some(FIRST_WAVE_DELAY));
+}
+
+#[test]
+fn test_wave_enemies_are_spread_around_the_spawn_points() {
+    let director = WaveDirector::new(
+        vec![WaveDefinition { delay: 0.0, enemies: 4, enemy_health: 100.0 }],
+        vec![SPAWN_POINT, -SPAWN_POINT],
+    );
+    let positions = director.spawn_positions(0);
+    assert_eq!(positions.len(), 4);
+    assert_eq!(positions.iter().filter(|position| position.distance(SPAWN_POINT) < 5.0).count(), 2);
+    assert_eq!(positions.iter().filter(|position| position.distance(-SPAWN_POINT) < 5.0).count(), 2);
+    for (i, a) in positions.iter().enumerate() {
+        assert!(positions[i + 1..].iter().all(|b| a.distance(*b) > 1.5), "Wave enemies don't overlap");
+    }
+    assert!(WaveDirector::escalating(1, Vec::new()).spawn_positions(0).is_empty());
+}
+
+#[test]
+fn test_game_starts_in_the_menu_and_pauses() {
+    let mut app = create_game_app(single_wave(1));
+    app.update();
+    assert_eq!(state(&app), AppState::MainMenu);
+    assert_eq!(game_state(&app).game_time, 0.0, "The clock waits for the player");
+
+    press(&mut app, START_KEY);
+    assert_eq!(state(&app), AppState::Playing);
+    app.update();
+    let started = game_state(&app).game_time;
+    assert!(started > 0.0);
+
+    press(&mut app, PAUSE_KEY);
+    assert_eq!(state(&app), AppState::Paused);
+    let paused = game_state(&app).game_time;
+    app.update();
+    app.update();
+    assert_eq!(game_state(&app).game_time, paused, "Time stands still while paused");
+    assert_eq!(count::<WaveEnemy>(&mut app), 0, "Waves wait too");
+
+    press(&mut app, PAUSE_KEY);
+    assert_eq!(state(&app), AppState::Playing);
+    app.update();
+    assert!(game_state(&app).game_time > paused);
+}
+
+#[test]
+fn test_destroyed_enemies_score_points() {
+    let mut app = create_game_app(single_wave(1));
+    app.world.send_event(EnemyDestroyedEvent { enemy: Entity::PLACEHOLDER, position: Vec3::ZERO });
+    app.update();
+    assert_eq!(game_state(&app).score, 0, "Nothing scores from the menu");
+
+    press(&mut app, START_KEY);
+    app.world.send_event(EnemyDestroyedEvent { enemy: Entity::PLACEHOLDER, position: Vec3::ZERO });
+    app.world.send_event(EnemyDestroyedEvent { enemy: Entity::PLACEHOLDER, position: Vec3::ZERO });
+    app.update();
+    assert_eq!(game_state(&app).score, 2 * KILL_SCORE);
+}
+
+#[test]
+fn test_losing_the_hero_is_a_defeat() {
+    let mut app = create_game_app(single_wave(1));
+    press(&mut app, START_KEY);
+
+    let hero = app.world.query_filtered::<Entity, With<Hero>>().single(&app.world);
+    app.world.despawn(hero);
+    app.update();
+    app.update();
+
+    assert_eq!(state(&app), AppState::GameOver);
+    assert_eq!(game_state(&app).outcome, Some(GameOutcome::Defeat));
+}
+
+#[test]
+fn test_clearing_the_final_wave_is_a_victory() {
+    let mut app = create_game_app(single_wave(3));
+    press(&mut app, START_KEY);
+    for _ in 0..6 {
+        app.update();
+    }
+
+    let healths: Vec<f32> = app.world.query_filtered::<&Health, With<WaveEnemy>>()
+        .iter(&app.world)
+        .map(|health| health.max)
+        .collect();
+    assert_eq!(healths, vec![150.0; 3], "Wave enemies get the wave's health");
+    assert_eq!(state(&app), AppState::Playing, "The final wave is still in the field");
+
+    let enemies: Vec<Entity> = app.world.query_filtered::<Entity, With<Enemy>>().iter(&app.world).collect();
+    for enemy in enemies {
+        app.world.despawn(enemy);
+    }
+    app.update();
+    app.update();
+
+    assert_eq!(state(&app), AppState::GameOver);
+    assert_eq!(game_state(&app).outcome, Some(GameOutcome::Victory));
+}
+
+#[test]
+fn test_new_run_starts_fresh() {
+    let mut app = create_game_app(single_wave(2));
+    app.insert_resource(MapDefinition::parse(SURVIVAL_MAP).unwrap());
+    app.insert_resource(Heightmap::flat(Vec2::splat(40.0)));
+    press(&mut app, START_KEY);
+    for _ in 0..6 {
+        app.update();
+    }
+    app.world.send_event(EnemyDestroyedEvent { enemy: Entity::PLACEHOLDER, position: Vec3::ZERO });
+    let hero = app.world.query_filtered::<Entity, With<Hero>>().single(&app.world);
+    app.world.despawn(hero);
+    app.update();
+    app.update();
+    assert_eq!(state(&app), AppState::GameOver);
+    assert_eq!(game_state(&app).score, KILL_SCORE);
+
+    press(&mut app, START_KEY);
+    assert_eq!(state(&app), AppState::MainMenu);
+    press(&mut app, START_KEY);
+
+    assert_eq!(state(&app), AppState::Playing);
+    assert_eq!(game_state(&app).score, 0);
+    assert_eq!(game_state(&app).outcome, None);
+    assert_eq!(count::<WaveEnemy>(&mut app), 0, "The last run's waves are cleared away");
+    assert_eq!(app.world.resource::<WaveDirector>().waves_spawned(), 0);
+    let heroes: Vec<Vec3> = app.world.query_filtered::<&Transform, With<Hero>>()
+        .iter(&app.world)
+        .map(|transform| transform.translation)
+        .collect();
+    assert_eq!(heroes, vec![Vec3::new(-5.0, 0.0, 0.0)], "The hero is back at the player spawn");
+}
+
+#[test]
+fn test_mission_objectives_decide_the_run() {
+    let survive = ObjectiveDefinition {
+        id: "survive".to_string(),
+        description: "Survive".to_string(),
+        kind: ObjectiveKind::Survive { seconds: 1.0 },
+        after: Vec::new(),
+    };
+    let mut app = create_mission_app(WaveDirector::new(Vec::new(), Vec::new()), ObjectiveTracker::new(vec![survive]));
+    press(&mut app, START_KEY);
+    app.update();
+    assert_eq!(state(&app), AppState::Playing, "No waves and no enemies, but the mission isn't done");
+    for _ in 0..12 {
+        app.update();
+    }
+    assert_eq!(state(&app), AppState::GameOver);
+    assert_eq!(game_state(&app).outcome, Some(GameOutcome::Victory));
+
+    let defend = ObjectiveDefinition {
+        id: "hold".to_string(),
+        description: "Hold".to_string(),
+        kind: ObjectiveKind::Defend { tag: "depot".to_string(), seconds: 60.0 },
+        after: Vec::new(),
+    };
+    let mut app = create_mission_app(single_wave(1), ObjectiveTracker::new(vec![defend]));
+    let depot = app.world.spawn((MapTag("depot".to_string()), Health::new(100.0), TransformBundle::default())).id();
+    press(&mut app, START_KEY);
+    app.update();
+    assert_eq!(state(&app), AppState::Playing);
+
+    app.world.despawn(depot);
+    app.update();
+    app.update();
+    assert_eq!(state(&app), AppState::GameOver);
+    assert_eq!(game_state(&app).outcome, Some(GameOutcome::Defeat), "A failed objective loses the run");
+}
+
+const MISSION_MAP: &str = r#"(
+    name: "Raid",
+    size: (40.0, 40.0),
+    spawns: [
+        (side: Player, position: (0.0, 0.0)),
+        (side: Enemy, position: (10.0, 10.0), tag: Some("commander")),
+    ],
+    objectives: [
+        (id: "kill", description: "Take out the commander", kind: DestroyTarget(tag: "commander")),
+    ],
+    triggers: [
+        (id: "flank", center: (5.0, 5.0), radius: 2.0, actions: []),
+    ],
+)"#;
+
+#[test]
+fn test_restart_rebuilds_the_map() {
+    let map = MapDefinition::parse(MISSION_MAP).unwrap();
+    let objectives = ObjectiveTracker::new(map.objectives.clone());
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.insert_resource(Input::<KeyCode>::default());
+    app.init_resource::<ProjectilePool>();
+    app.add_plugins(MapPlugin { map });
+    app.add_plugins(GameModePlugin { waves: WaveDirector::new(Vec::new(), Vec::new()), objectives });
+    app.update();
+    press(&mut app, START_KEY);
+
+    // Trip the trigger, wear down the hero, leave rubble and a shell in flight, then take
+    // out the commander to end the run
+    let hero = app.world.query_filtered::<Entity, With<Hero>>().single(&app.world);
+    app.world.get_mut::<Transform>(hero).unwrap().translation = Vec3::new(5.0, 0.0, 5.0);
+    app.update();
+    assert!(app.world.query::<&TriggerVolume>().single(&app.world).fired);
+    for mut part_health in app.world.query::<&mut PartHealth>().iter_mut(&mut app.world) {
+        part_health.current = 1.0;
+    }
+    app.world.spawn((Rubble, TransformBundle::default()));
+    let shell = app.world.spawn((Pooled(PoolKind::Shell), Projectile { damage: 25.0, speed: 20.0 }, TransformBundle::default())).id();
+    let commander = app.world.query_filtered::<Entity, With<MapTag>>().single(&app.world);
+    app.world.despawn(commander);
+    app.update();
+    app.update();
+    assert_eq!(state(&app), AppState::GameOver);
+    assert_eq!(game_state(&app).outcome, Some(GameOutcome::Victory));
+
+    press(&mut app, START_KEY);
+    press(&mut app, START_KEY);
+    assert_eq!(state(&app), AppState::Playing);
+
+    assert!(!app.world.query::<&TriggerVolume>().single(&app.world).fired, "The trigger is armed again");
+    let tags: Vec<String> = app.world.query_filtered::<&MapTag, With<Enemy>>()
+        .iter(&app.world)
+        .map(|tag| tag.0.clone())
+        .collect();
+    assert_eq!(tags, vec!["commander".to_string()], "The tagged target is back");
+    assert_eq!(app.world.resource::<ObjectiveTracker>().status("kill"), Some(ObjectiveStatus::Active));
+    assert_eq!(count::<Hero>(&mut app), 1);
+    assert!(app.world.query::<&PartHealth>().iter(&app.world).all(|part_health| part_health.current == part_health.max));
+    assert_eq!(count::<Rubble>(&mut app), 0);
+    assert_eq!(count::<Terrain>(&mut app), 1);
+    assert!(app.world.get::<Projectile>(shell).is_none(), "Shells in flight go back to the pool");
+    assert_eq!(app.world.resource::<ProjectilePool>().parked(PoolKind::Shell), 1);
+}
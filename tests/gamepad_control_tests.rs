@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use bevy::input::InputPlugin;
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadInfo};
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::camera::CameraShakeEvent;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::gamepad_control::*;
+use rust_and_ruin::systems::projectile_pool::{ProjectileAssets, ProjectilePool};
+use rust_and_ruin::systems::weapon_control::weapon_cooldown_system;
+
+const GAMEPAD: Gamepad = Gamepad { id: 0 };
+
+fn create_gamepad_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.init_resource::<ControlScheme>();
+    app.add_event::<CameraShakeEvent>();
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+
+    app.world.send_event(GamepadConnectionEvent::new(
+        GAMEPAD,
+        GamepadConnection::Connected(GamepadInfo { name: "Test Pad".to_string() }),
+    ));
+    app.update();
+    app
+}
+
+fn set_axis(app: &mut App, axis_type: GamepadAxisType, value: f32) {
+    app.world
+        .resource_mut::<Axis<GamepadAxis>>()
+        .set(GamepadAxis::new(GAMEPAD, axis_type), value);
+}
+
+fn spawn_hero_with_turret(app: &mut App) -> (Entity, Entity) {
+    let hero = app.world.spawn((
+        Hero,
+        MechMovement::default(),
+        create_tank_treads_lower(),
+        TransformBundle::default(),
+    )).id();
+
+    let turret_upper = TurretUpper::new(360.0);
+    let turret = app.world.spawn((
+        MechUpperPart,
+        MechRotation { target_angle: 0.0, current_angle: 0.0 },
+        MechUpperBody {
+            rotation_capability: turret_upper.rotation_capability,
+            hardpoints: turret_upper.hardpoints,
+            cooling_rating: turret_upper.cooling_rating,
+        },
+        TransformBundle::default(),
+    )).id();
+
+    let mut weapon = create_cannon_weapon("main".to_string());
+    weapon.last_fire_time = weapon.weapon_stats.fire_rate;
+    let cannon = app.world.spawn((weapon, CannonWeapon::default(), TransformBundle::default())).id();
+
+    app.world.entity_mut(hero).push_children(&[turret]);
+    app.world.entity_mut(turret).push_children(&[cannon]);
+    (hero, turret)
+}
+
+#[test]
+fn test_stick_to_world_direction() {
+    assert!(stick_to_world_direction(Vec2::new(0.1, 0.1), AIM_DEADZONE).is_none());
+
+    let up = stick_to_world_direction(Vec2::new(0.0, 1.0), AIM_DEADZONE).unwrap();
+    assert!((up - Vec2::new(0.0, -1.0)).length() < 0.001, "Stick up should aim towards -Z");
+
+    let right = stick_to_world_direction(Vec2::new(0.5, 0.0), AIM_DEADZONE).unwrap();
+    assert!((right - Vec2::new(1.0, 0.0)).length() < 0.001, "Direction should be normalized");
+}
+
+#[test]
+fn test_left_stick_throttle_drives_chassis_forward() {
+    let mut app = create_gamepad_app();
+    app.add_systems(Update, gamepad_drive_system);
+    let (hero, _) = spawn_hero_with_turret(&mut app);
+
+    set_axis(&mut app, GamepadAxisType::LeftStickY, 1.0);
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let transform = app.world.get::<Transform>(hero).unwrap();
+    let movement = app.world.get::<MechMovement>(hero).unwrap();
+    let max_speed = app.world.get::<MechLowerBody>(hero).unwrap().movement_stats.max_speed;
+    assert!(transform.translation.z > 1.0, "Hero should drive forward along +Z, got {:?}", transform.translation);
+    assert!(transform.translation.x.abs() < 0.001);
+    assert!(movement.current_speed > 0.0 && movement.current_speed <= max_speed);
+    assert_eq!(movement.movement_state, MechMovementState::Idle);
+}
+
+#[test]
+fn test_left_stick_turn_rotates_chassis_in_place() {
+    let mut app = create_gamepad_app();
+    app.add_systems(Update, gamepad_drive_system);
+    let (hero, _) = spawn_hero_with_turret(&mut app);
+
+    set_axis(&mut app, GamepadAxisType::LeftStickX, 1.0);
+    for _ in 0..5 {
+        app.update();
+    }
+
+    let transform = app.world.get::<Transform>(hero).unwrap();
+    let yaw = transform.rotation.to_euler(EulerRot::YXZ).0.to_degrees();
+    assert!(yaw < -10.0, "Stick right should turn clockwise on screen (decreasing yaw), got {:.1}", yaw);
+    assert!(transform.translation.length() < 0.001, "Turning alone should not move the hero");
+}
+
+#[test]
+fn test_right_stick_aims_turret_independently_of_chassis() {
+    let mut app = create_gamepad_app();
+    app.add_systems(Update, gamepad_turret_aim_system);
+    let (hero, turret) = spawn_hero_with_turret(&mut app);
+
+    // Chassis faces +X, stick points right (+X): the turret should stay centered
+    app.world.get_mut::<Transform>(hero).unwrap().rotation = Quat::from_rotation_y(90.0_f32.to_radians());
+    set_axis(&mut app, GamepadAxisType::RightStickX, 1.0);
+    for _ in 0..5 {
+        app.update();
+    }
+    let rotation = app.world.get::<MechRotation>(turret).unwrap();
+    assert!(rotation.current_angle.abs() < 0.1 || (rotation.current_angle - 360.0).abs() < 0.1,
+        "Turret local angle should be 0 when chassis already faces the aim, got {:.1}", rotation.current_angle);
+
+    // Stick up aims towards -Z, which is 90° to the chassis' left
+    set_axis(&mut app, GamepadAxisType::RightStickX, 0.0);
+    set_axis(&mut app, GamepadAxisType::RightStickY, 1.0);
+    for _ in 0..5 {
+        app.update();
+    }
+    let rotation = app.world.get::<MechRotation>(turret).unwrap();
+    assert!((rotation.target_angle - 180.0).abs() < 0.1, "World aim should be 180°, got {:.1}", rotation.target_angle);
+    assert!((rotation.current_angle - 90.0).abs() < 0.1, "Local turret angle should be 90°, got {:.1}", rotation.current_angle);
+}
+
+#[test]
+fn test_trigger_fires_main_weapon_with_cooldown() {
+    let mut app = create_gamepad_app();
+    app.add_systems(Update, (weapon_cooldown_system, gamepad_fire_system).chain());
+    spawn_hero_with_turret(&mut app);
+
+    app.world
+        .resource_mut::<Input<GamepadButton>>()
+        .press(GamepadButton::new(GAMEPAD, FIRE_BUTTON));
+
+    app.update();
+    let shells = app.world.query::<&TankShell>().iter(&app.world).count();
+    assert_eq!(shells, 1, "Pulling the trigger should fire immediately");
+
+    // Default fire rate is 1.5s, 100ms per frame
+    for _ in 0..5 {
+        app.update();
+    }
+    let shells = app.world.query::<&TankShell>().iter(&app.world).count();
+    assert_eq!(shells, 1, "Weapon should respect its fire rate");
+
+    let shell = app.world.query::<&TankShell>().iter(&app.world).next().unwrap();
+    assert!(shell.velocity.y > 0.0, "Shell should fly along the turret's forward (+Z)");
+}
+
+#[test]
+fn test_toggle_switches_scheme_and_clears_orders() {
+    // No InputPlugin here: it would clear just_pressed before our systems see it
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HierarchyPlugin));
+    app.init_resource::<ControlScheme>();
+    app.init_resource::<Gamepads>();
+    app.insert_resource(Input::<KeyCode>::default());
+    app.insert_resource(Input::<GamepadButton>::default());
+    app.add_systems(Update, control_scheme_toggle_system);
+    let (hero, turret) = spawn_hero_with_turret(&mut app);
+
+    let enemy = app.world.spawn(Enemy).id();
+    app.world.entity_mut(hero).insert((
+        MoveTarget { position: Vec2::new(5.0, 5.0) },
+        AttackTarget { entity: enemy },
+    ));
+    app.world.entity_mut(turret).insert(AttackTarget { entity: enemy });
+
+    app.world.resource_mut::<Input<KeyCode>>().press(CONTROL_SCHEME_TOGGLE_KEY);
+    app.update();
+
+    assert_eq!(*app.world.resource::<ControlScheme>(), ControlScheme::Gamepad);
+    assert!(app.world.get::<MoveTarget>(hero).is_none());
+    assert!(app.world.get::<AttackTarget>(hero).is_none());
+    assert!(app.world.get::<AttackTarget>(turret).is_none());
+
+    app.world.resource_mut::<Input<KeyCode>>().clear();
+    app.update();
+    assert_eq!(*app.world.resource::<ControlScheme>(), ControlScheme::Gamepad, "Holding the key should not toggle again");
+
+    app.world.resource_mut::<Input<KeyCode>>().release(CONTROL_SCHEME_TOGGLE_KEY);
+    app.world.resource_mut::<Input<KeyCode>>().press(CONTROL_SCHEME_TOGGLE_KEY);
+    app.update();
+    assert_eq!(*app.world.resource::<ControlScheme>(), ControlScheme::Rts);
+}
@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::components::*;
+use rust_and_ruin::ui::*;
+
+fn create_health_bar_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Input::<KeyCode>::default());
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.add_plugins(HealthBarPlugin);
+    app
+}
+
+fn bar_for(app: &mut App, target: Entity) -> Option<(Entity, Transform, Visibility)> {
+    let mut query = app.world.query::<(Entity, &HealthBar, &Transform, &Visibility)>();
+    query
+        .iter(&app.world)
+        .find(|(_, bar, _, _)| bar.target == target)
+        .map(|(entity, _, transform, visibility)| (entity, *transform, *visibility))
+}
+
+#[test]
+fn test_health_bar_visibility_rules() {
+    let full = Health::new(100.0);
+    let damaged = Health { current: 40.0, max: 100.0 };
+
+    assert!(!is_health_bar_visible(&full, false, false));
+    assert!(is_health_bar_visible(&full, true, false));
+    assert!(is_health_bar_visible(&full, false, true));
+    assert!(is_health_bar_visible(&damaged, false, false));
+}
+
+#[test]
+fn test_health_fraction_is_clamped() {
+    assert_eq!(health_fraction(&Health { current: -20.0, max: 100.0 }), 0.0);
+    assert_eq!(health_fraction(&Health { current: 25.0, max: 100.0 }), 0.25);
+    assert_eq!(health_fraction(&Health { current: 10.0, max: 0.0 }), 0.0);
+}
+
+#[test]
+fn test_bar_fill_stays_left_aligned() {
+    let half = health_bar_fill_transform(0.5);
+    let left_edge = half.translation.x - HEALTH_BAR_WIDTH * 0.5 * half.scale.x;
+    assert!((left_edge + HEALTH_BAR_WIDTH * 0.5).abs() < 0.0001);
+}
+
+#[test]
+fn test_bar_tracks_target_and_shows_once_damaged() {
+    let mut app = create_health_bar_app();
+    let enemy = app.world.spawn((
+        Enemy,
+        Health::new(100.0),
+        TransformBundle::from_transform(Transform::from_xyz(3.0, 0.75, -2.0)),
+    )).id();
+    app.update();
+    app.update();
+
+    let (_, transform, visibility) = bar_for(&mut app, enemy).expect("Health should get a bar");
+    assert_eq!(transform.translation, Vec3::new(3.0, 0.75 + HEALTH_BAR_OFFSET, -2.0));
+    assert_eq!(visibility, Visibility::Hidden, "Full health bars are hidden");
+
+    app.world.get_mut::<Health>(enemy).unwrap().current = 60.0;
+    app.update();
+
+    let (bar_entity, _, visibility) = bar_for(&mut app, enemy).unwrap();
+    assert_eq!(visibility, Visibility::Inherited);
+
+    let children = app.world.get::<Children>(bar_entity).unwrap().to_vec();
+    let fill_scale = children
+        .iter()
+        .find_map(|child| app.world.get::<HealthBarFill>(*child).and(app.world.get::<Transform>(*child)))
+        .map(|transform| transform.scale.x);
+    assert_eq!(fill_scale, Some(0.6));
+}
+
+#[test]
+fn test_bars_share_assets() {
+    let mut app = create_health_bar_app();
+    app.update();
+    let mesh_count = app.world.resource::<Assets<Mesh>>().len();
+    let material_count = app.world.resource::<Assets<StandardMaterial>>().len();
+
+    for x in 0..5 {
+        app.world.spawn((Health::new(100.0), TransformBundle::from_transform(Transform::from_xyz(x as f32, 0.0, 0.0))));
+        app.world.spawn((Enemy, Health::new(100.0), TransformBundle::from_transform(Transform::from_xyz(x as f32, 0.0, 2.0))));
+    }
+    app.update();
+
+    let mut fills = app.world.query_filtered::<&Handle<StandardMaterial>, With<HealthBarFill>>();
+    assert_eq!(fills.iter(&app.world).count(), 10);
+    assert_eq!(app.world.resource::<Assets<Mesh>>().len(), mesh_count, "Bars reuse the shared quad");
+    assert_eq!(app.world.resource::<Assets<StandardMaterial>>().len(), material_count, "Bars reuse the shared materials");
+
+    let assets = app.world.resource::<HealthBarAssets>().clone();
+    let enemy_fills = fills.iter(&app.world).filter(|material| **material == assets.enemy_fill_material).count();
+    assert_eq!(enemy_fills, 5);
+}
+
+#[test]
+fn test_hidden_targets_show_no_bar() {
+    let mut app = create_health_bar_app();
+    let enemy = app.world.spawn((
+        Enemy,
+        Health { current: 50.0, max: 100.0 },
+        SpatialBundle::from_transform(Transform::from_xyz(3.0, 0.75, -2.0)),
+    )).id();
+    app.update();
+    app.update();
+    assert_eq!(bar_for(&mut app, enemy).unwrap().2, Visibility::Inherited);
+
+    // Enemies in the fog of war are hidden, and their bar with them
+    *app.world.get_mut::<Visibility>(enemy).unwrap() = Visibility::Hidden;
+    app.update();
+    assert_eq!(bar_for(&mut app, enemy).unwrap().2, Visibility::Hidden);
+}
+
+#[test]
+fn test_show_all_toggle_and_bar_cleanup() {
+    let mut app = create_health_bar_app();
+    let enemy = app.world.spawn((Enemy, Health::new(100.0), TransformBundle::default())).id();
+    app.update();
+
+    app.world.resource_mut::<Input<KeyCode>>().press(HEALTH_BAR_TOGGLE_KEY);
+    app.update();
+
+    assert!(app.world.resource::<HealthBarSettings>().show_all);
+    assert_eq!(bar_for(&mut app, enemy).unwrap().2, Visibility::Inherited);
+
+    app.world.despawn(enemy);
+    app.update();
+    assert!(bar_for(&mut app, enemy).is_none());
+}
+
+#[test]
+fn test_damage_event_spawns_fading_number() {
+    let mut app = create_health_bar_app();
+    let enemy = app.world.spawn((Enemy, Health::new(100.0), TransformBundle::default())).id();
+    app.update();
+
+    app.world.send_event(DamageEvent { target: enemy, amount: 25.0, position: Vec3::ZERO });
+    app.update();
+
+    let mut query = app.world.query::<(&DamageNumber, &Text)>();
+    let texts: Vec<String> = query.iter(&app.world).map(|(_, text)| text.sections[0].value.clone()).collect();
+    assert_eq!(texts, vec!["25".to_string()]);
+
+    for _ in 0..12 {
+        app.update();
+    }
+    assert_eq!(app.world.query::<&DamageNumber>().iter(&app.world).count(), 0);
+}
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::camera::CameraShakeEvent;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::resources::MouseWorldPosition;
+use rust_and_ruin::systems::*;
+use rust_and_ruin::ui::*;
+
+#[test]
+fn test_heat_levels_follow_thresholds() {
+    let mut heat = Heat::new(100.0);
+    assert_eq!(heat.level(), HeatLevel::Normal);
+    assert_eq!(heat.accuracy_spread(), 0.0);
+
+    heat.add(65.0);
+    assert_eq!(heat.level(), HeatLevel::Hot);
+    assert!(heat.can_fire());
+    assert!((heat.accuracy_spread() - MAX_HEAT_SPREAD / 2.0).abs() < 0.001);
+
+    heat.add(15.0);
+    assert_eq!(heat.level(), HeatLevel::Lockout);
+    assert!(!heat.can_fire());
+    assert!(heat.can_move());
+
+    heat.add(50.0);
+    assert_eq!(heat.current, 100.0, "Heat is capped at capacity");
+    assert_eq!(heat.level(), HeatLevel::Shutdown);
+    assert!(!heat.can_move());
+}
+
+#[test]
+fn test_shutdown_lasts_until_restart_threshold() {
+    let mut heat = Heat::new(100.0);
+    heat.add(100.0);
+
+    // Cooling below lockout is not enough to restart
+    heat.dissipate(40.0);
+    assert_eq!(heat.level(), HeatLevel::Shutdown);
+
+    heat.dissipate(30.0);
+    assert!(!heat.shutdown);
+    assert_eq!(heat.level(), HeatLevel::Normal);
+}
+
+fn create_heat_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.init_resource::<MouseWorldPosition>();
+    app.add_event::<CameraShakeEvent>();
+    app.add_systems(Update, (
+        heat_system,
+        weapon_cooldown_system,
+        weapon_control_system,
+        mech_movement_system,
+    ).chain());
+    app
+}
+
+fn spawn_hot_hero(app: &mut App, heat: Heat) -> (Entity, Entity) {
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 8.0)))).id();
+    let hero = app.world.spawn((
+        Hero,
+        heat,
+        MechMovement::default(),
+        create_tank_treads_lower(),
+        AttackTarget { entity: enemy },
+        TransformBundle::default(),
+    )).id();
+    let upper = app.world.spawn((
+        create_turret_upper(),
+        MechRotation { target_angle: 0.0, current_angle: 0.0 },
+        TransformBundle::default(),
+    )).id();
+    let mut weapon = create_cannon_weapon("main".to_string());
+    weapon.last_fire_time = weapon.weapon_stats.fire_rate;
+    let weapon = app.world.spawn((weapon, CannonWeapon::default(), TransformBundle::default())).id();
+    app.world.entity_mut(hero).push_children(&[upper]);
+    app.world.entity_mut(upper).push_children(&[weapon]);
+    (hero, weapon)
+}
+
+fn shell_count(app: &mut App) -> usize {
+    app.world.query::<&TankShell>().iter(&app.world).count()
+}
+
+#[test]
+fn test_shots_heat_the_mech() {
+    let mut app = create_heat_app();
+    let (hero, weapon) = spawn_hot_hero(&mut app, Heat::default());
+
+    app.update();
+    assert_eq!(shell_count(&mut app), 1);
+
+    let heat_per_shot = app.world.get::<MechWeapon>(weapon).unwrap().weapon_stats.heat_per_shot;
+    let heat = app.world.get::<Heat>(hero).unwrap().current;
+    assert!(heat > 0.0 && heat <= heat_per_shot, "got {}", heat);
+}
+
+#[test]
+fn test_weapons_locked_out_until_mech_cools() {
+    let mut app = create_heat_app();
+    let mut heat = Heat::default();
+    heat.add(82.0);
+    let (hero, _) = spawn_hot_hero(&mut app, heat);
+
+    app.update();
+    assert_eq!(shell_count(&mut app), 0, "Weapons are locked out above the lockout threshold");
+
+    // The turret upper sheds 5 heat per second, so 82 drops below 80 within half a second
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(shell_count(&mut app), 1);
+    assert!(app.world.get::<Heat>(hero).unwrap().level() >= HeatLevel::Lockout);
+}
+
+#[test]
+fn test_cooling_comes_from_the_upper_body() {
+    let mut app = create_heat_app();
+    let mut heat = Heat::default();
+    heat.add(40.0);
+    let (hero, _) = spawn_hot_hero(&mut app, heat);
+    // Nothing to shoot at, so only cooling applies
+    app.world.entity_mut(hero).remove::<AttackTarget>();
+
+    // The first update has no elapsed time, so this is one second of cooling
+    for _ in 0..11 {
+        app.update();
+    }
+    let cooling = create_turret_upper().cooling_rating;
+    let current = app.world.get::<Heat>(hero).unwrap().current;
+    assert!((current - (40.0 - cooling)).abs() < 0.01, "got {}", current);
+}
+
+#[test]
+fn test_sprinting_is_faster_and_builds_heat() {
+    let mut app = create_heat_app();
+    let (hero, _) = spawn_hot_hero(&mut app, Heat::default());
+    app.world.entity_mut(hero).remove::<AttackTarget>();
+    app.world.entity_mut(hero).insert(MoveTarget { position: Vec2::new(0.0, 100.0) });
+    app.world.get_mut::<MechMovement>(hero).unwrap().sprinting = true;
+
+    for _ in 0..40 {
+        app.update();
+    }
+    let movement = app.world.get::<MechMovement>(hero).unwrap();
+    let max_speed = create_tank_treads_lower().movement_stats.max_speed;
+    assert!(movement.current_speed > max_speed, "Sprinting should exceed {}, got {}", max_speed, movement.current_speed);
+    assert!(app.world.get::<Heat>(hero).unwrap().current > 0.0);
+}
+
+#[test]
+fn test_shutdown_stops_movement() {
+    let mut app = create_heat_app();
+    let mut heat = Heat::default();
+    heat.add(100.0);
+    let (hero, _) = spawn_hot_hero(&mut app, heat);
+    app.world.entity_mut(hero).insert(MoveTarget { position: Vec2::new(0.0, 10.0) });
+
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(app.world.get::<Transform>(hero).unwrap().translation, Vec3::ZERO);
+    assert_eq!(shell_count(&mut app), 0);
+    assert!(app.world.get::<MoveTarget>(hero).is_some(), "Orders survive the shutdown");
+}
+
+#[test]
+fn test_heat_gauge_label() {
+    let mut heat = Heat::default();
+    heat.add(30.0);
+    assert_eq!(heat_gauge_label(&heat), "HEAT 30%");
+
+    heat.add(55.0);
+    assert_eq!(heat_gauge_label(&heat), "HEAT 85% WEAPONS LOCKED");
+    assert_eq!(heat_gauge_color(HeatLevel::Lockout), heat_gauge_color(heat.level()));
+}
@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use rust_and_ruin::camera::CameraController;
+use rust_and_ruin::components::*;
+use rust_and_ruin::map::*;
+use rust_and_ruin::systems::{FogOfWar, Heightmap};
+use rust_and_ruin::ui::minimap::Minimap;
+
+const TEST_MAP: &str = r#"(
+    name: "Test",
+    size: (50.0, 50.0),
+    terrain: Heightmap(path: "assets/terrain/heightmap.png", max_height: 4.0),
+    obstacles: [
+        (kind: Wall, position: (2.0, 5.0), size: (4.0, 1.5, 0.5), rotation: 90.0),
+    ],
+    spawns: [
+        (side: Player, position: (0.0, 0.0), facing: 90.0),
+        (side: Enemy, position: (-14.0, -16.0)),
+        (side: Enemy, position: (4.0, 0.0)),
+    ],
+    objectives: [
+        (id: "reach", description: "Reach the ridge", kind: ReachZone(center: (-14.0, -16.0), radius: 3.0)),
+    ],
+    triggers: [
+        (id: "ambush", center: (5.0, 5.0), radius: 2.0, actions: [SpawnEnemy(position: (8.0, 8.0)), Message("Ambush!")]),
+        (id: "checkpoint", center: (-5.0, 5.0), radius: 2.0, repeat: true, actions: []),
+    ],
+)"#;
+
+fn create_map_app(map: MapDefinition) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HierarchyPlugin));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.add_plugins(MapPlugin { map });
+    app.update();
+    app
+}
+
+fn count<T: Component>(app: &mut App) -> usize {
+    app.world.query_filtered::<(), With<T>>().iter(&app.world).count()
+}
+
+fn trigger_ids(app: &mut App) -> Vec<String> {
+    let mut events = app.world.resource_mut::<Events<MapTriggerEvent>>();
+    events.drain().map(|event| event.id).collect()
+}
+
+fn move_hero(app: &mut App, x: f32, z: f32) {
+    let mut heroes = app.world.query_filtered::<&mut Transform, With<Hero>>();
+    heroes.single_mut(&mut app.world).translation = Vec3::new(x, 0.0, z);
+    app.update();
+}
+
+#[test]
+fn test_shipped_map_parses() {
+    let map = MapDefinition::load(DEFAULT_MAP_PATH).unwrap();
+    assert!(map.spawns.iter().any(|spawn| spawn.side == SpawnSide::Player));
+    assert!(map.spawns.iter().any(|spawn| spawn.side == SpawnSide::Enemy));
+    assert!(matches!(map.terrain, TerrainDefinition::Heightmap { .. }));
+    assert_eq!(map.heightmap().size(), map.size());
+}
+
+#[test]
+fn test_optional_sections_default_and_errors_are_reported() {
+    let map = MapDefinition::parse(r#"(name: "Bare", size: (20.0, 30.0), spawns: [(side: Player, position: (1.0, 2.0))])"#).unwrap();
+    assert_eq!(map.terrain, TerrainDefinition::Flat);
+    assert!(map.obstacles.is_empty() && map.objectives.is_empty() && map.triggers.is_empty());
+    assert_eq!(map.spawns[0].facing, 0.0);
+
+    assert!(matches!(MapDefinition::parse("(name: \"Broken\""), Err(MapLoadError::Parse(_))));
+    assert!(matches!(MapDefinition::load("assets/maps/missing.ron"), Err(MapLoadError::Io(_))));
+}
+
+#[test]
+fn test_map_argument_selects_the_map_file() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+    assert_eq!(map_path_from_args(args(&[])), DEFAULT_MAP_PATH);
+    assert_eq!(map_path_from_args(args(&["--map", "maps/a.ron"])), "maps/a.ron");
+    assert_eq!(map_path_from_args(args(&["--verbose", "--map=maps/b.ron"])), "maps/b.ron");
+    assert_eq!(map_path_from_args(args(&["--map"])), DEFAULT_MAP_PATH);
+}
+
+#[test]
+fn test_loader_instantiates_map_content_on_the_terrain() {
+    let map = MapDefinition::parse(TEST_MAP).unwrap();
+    let heightmap = map.heightmap();
+    let mut app = create_map_app(map);
+
+    assert_eq!(count::<Terrain>(&mut app), 1);
+    assert_eq!(count::<Wall>(&mut app), 1);
+    assert_eq!(count::<Hero>(&mut app), 1);
+    assert_eq!(count::<Enemy>(&mut app), 2);
+    assert_eq!(count::<TriggerVolume>(&mut app), 2);
+
+    let hero = app.world.query_filtered::<&Transform, With<Hero>>().single(&app.world);
+    assert!((hero.rotation * Vec3::Z - Vec3::X).length() < 0.001, "Player spawn faces +X");
+    assert!(app.world.contains_resource::<FogOfWar>());
+    assert_eq!(app.world.query_filtered::<(), (With<VisionRange>, Or<(With<Hero>, With<Enemy>)>)>().iter(&app.world).count(), 3,
+               "Every spawned unit can see");
+
+    let ridge_height = heightmap.height_at(Vec2::new(-14.0, -16.0));
+    assert!(ridge_height > 3.0);
+    let on_ridge = app.world.query_filtered::<&Transform, With<Enemy>>()
+        .iter(&app.world)
+        .any(|transform| (transform.translation - Vec3::new(-14.0, ridge_height + 0.75, -16.0)).length() < 0.001);
+    assert!(on_ridge, "Enemies stand on the sampled ground height");
+}
+
+#[test]
+fn test_triggers_fire_on_entry_once_unless_repeating() {
+    let mut app = create_map_app(MapDefinition::parse(TEST_MAP).unwrap());
+
+    move_hero(&mut app, 5.5, 5.0);
+    assert_eq!(trigger_ids(&mut app), vec!["ambush".to_string()]);
+    assert_eq!(count::<Enemy>(&mut app), 3, "The ambush brings in an enemy");
+
+    move_hero(&mut app, 5.0, 5.5);
+    move_hero(&mut app, 0.0, 0.0);
+    move_hero(&mut app, 5.0, 5.0);
+    assert_eq!(count::<Enemy>(&mut app), 3, "One-shot triggers don't fire again");
+
+    for _ in 0..2 {
+        move_hero(&mut app, -5.0, 5.0);
+        assert_eq!(trigger_ids(&mut app), vec!["checkpoint".to_string()]);
+        move_hero(&mut app, 0.0, 0.0);
+    }
+}
+
+#[test]
+fn test_flat_map_spawns_units_at_ground_level() {
+    let map = MapDefinition::parse(r#"(name: "Flat", size: (20.0, 20.0), spawns: [(side: Player, position: (1.0, 2.0)), (side: Enemy, position: (3.0, 4.0))])"#).unwrap();
+    let mut app = create_map_app(map);
+    assert_eq!(app.world.resource::<Heightmap>().height_at(Vec2::new(3.0, 4.0)), 0.0);
+
+    let hero = app.world.query_filtered::<&Transform, With<Hero>>().single(&app.world);
+    assert_eq!(hero.translation, Vec3::new(1.0, 0.0, 2.0));
+    let enemy = app.world.query_filtered::<&Transform, With<Enemy>>().single(&app.world);
+    assert_eq!(enemy.translation, Vec3::new(3.0, 0.75, 4.0));
+}
+
+#[test]
+fn test_camera_and_minimap_are_fitted_to_the_map() {
+    let map = MapDefinition::parse(r#"(name: "Wide", size: (80.0, 30.0), spawns: [(side: Player, position: (0.0, 0.0))])"#).unwrap();
+    let mut app = create_map_app(map);
+    let camera = app.world.spawn(CameraController { focus: Vec3::new(60.0, 0.0, 0.0), ..default() }).id();
+    let minimap = app.world.spawn(Minimap::default()).id();
+    app.update();
+
+    let expected = Rect::new(-40.0, -15.0, 40.0, 15.0);
+    let controller = app.world.get::<CameraController>(camera).unwrap();
+    assert_eq!(controller.bounds, expected);
+    assert_eq!(controller.focus.x, 40.0, "Focus is pulled back inside the map");
+    assert_eq!(app.world.get::<Minimap>(minimap).unwrap().world_bounds, expected);
+}
@@ -213,6 +213,8 @@ fn test_mech_rotation_speed_calculation() {
                 max_speed: 5.0,
                 turn_rate,
                 acceleration: 2.0,
+                climb_slope: TANK_TREADS_CLIMB_SLOPE,
+                max_slope: TANK_TREADS_MAX_SLOPE,
             }
         },
         Hero,
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::camera::CameraShakeEvent;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::resources::MouseWorldPosition;
+use rust_and_ruin::systems::*;
+
+#[test]
+fn test_shortest_angle_difference_handles_unnormalized_input() {
+    assert_eq!(shortest_angle_difference(350.0, 10.0), 20.0);
+    assert_eq!(shortest_angle_difference(-600.0, 10.0), -110.0);
+    assert_eq!(shortest_angle_difference(720.0, 90.0), 90.0);
+}
+
+fn create_runtime_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.init_resource::<MouseWorldPosition>();
+    app.add_event::<CameraShakeEvent>();
+    app.add_systems(Update, (
+        upper_body_control_system,
+        weapon_cooldown_system,
+        weapon_control_system,
+    ).chain());
+    app
+}
+
+/// Builds a hero the way `main.rs` does: drive stats on the root, a turret upper body
+/// and a single cannon on its main hardpoint.
+fn spawn_hierarchy_hero(app: &mut App, transform: Transform) -> (Entity, Entity) {
+    let hero = app.world.spawn((
+        Hero,
+        MechMovement::default(),
+        create_tank_treads_lower(),
+        TransformBundle::from_transform(transform),
+    )).id();
+    let upper = app.world.spawn((
+        MechUpperPart,
+        create_turret_upper(),
+        MechRotation { target_angle: 0.0, current_angle: 0.0 },
+        TransformBundle::default(),
+    )).id();
+    let weapon = app.world.spawn((
+        create_cannon_weapon("main".to_string()),
+        CannonWeapon::default(),
+        TransformBundle::default(),
+    )).id();
+
+    app.world.entity_mut(hero).push_children(&[upper]);
+    app.world.entity_mut(upper).push_children(&[weapon]);
+    let mut hierarchy = MechHierarchy::new();
+    hierarchy.upper = Some(upper);
+    hierarchy.weapons = vec![weapon];
+    app.world.entity_mut(hero).insert(hierarchy);
+    (hero, upper)
+}
+
+fn shell_count(app: &mut App) -> usize {
+    app.world.query::<&TankShell>().iter(&app.world).count()
+}
+
+#[test]
+fn test_hierarchy_hero_aims_and_fires_on_cooldown() {
+    let mut app = create_runtime_app();
+    // Chassis faces +X and the enemy sits behind it, so the upper has to turn 180°
+    let (hero, upper) = spawn_hierarchy_hero(&mut app, Transform::from_rotation(Quat::from_rotation_y(90f32.to_radians())));
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(-8.0, 0.0, 0.0)))).id();
+    app.world.entity_mut(hero).insert(AttackTarget { entity: enemy });
+
+    app.update();
+    assert_eq!(shell_count(&mut app), 0, "Upper body should not fire before it faces the target");
+
+    for _ in 0..20 {
+        app.update();
+    }
+    let rotation = app.world.get::<MechRotation>(upper).unwrap();
+    assert!((rotation.current_angle - 180.0).abs() < 0.1, "got {}", rotation.current_angle);
+
+    // 2.1s elapsed with a 1.5s fire rate: exactly one shot so far
+    assert_eq!(shell_count(&mut app), 1);
+    let shell = app.world.query::<&TankShell>().iter(&app.world).next().unwrap();
+    assert!(shell.velocity.x < 0.0, "Shell should fly towards the enemy at -X, got {:?}", shell.velocity);
+
+    let fragmenting = app.world.query_filtered::<(), With<FragmentShell>>().iter(&app.world).count();
+    assert_eq!(fragmenting, 1, "Mech cannons fire the same fragmenting shells as before");
+}
+
+#[test]
+fn test_weapon_holds_fire_outside_its_range() {
+    let mut app = create_runtime_app();
+    let (hero, _) = spawn_hierarchy_hero(&mut app, Transform::IDENTITY);
+    // Default cannon range is 15
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 20.0)))).id();
+    app.world.entity_mut(hero).insert(AttackTarget { entity: enemy });
+
+    for _ in 0..30 {
+        app.update();
+    }
+    assert_eq!(shell_count(&mut app), 0);
+}
@@ -292,6 +292,7 @@ mod mech_assembly_tests {
 mod turret_control_tests {
     use super::*;
     use rust_and_ruin::mech::*;
+    use rust_and_ruin::systems::angles::*;
     use rust_and_ruin::systems::turret_control::*;
 
     #[test]
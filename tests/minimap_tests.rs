@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use rust_and_ruin::camera::*;
+use rust_and_ruin::components::*;
+use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::input::click_to_move_system;
+use rust_and_ruin::systems::projectile_pool::{ProjectileAssets, ProjectilePool};
+use rust_and_ruin::ui::*;
+
+fn world_bounds() -> Rect {
+    Rect::new(-25.0, -25.0, 25.0, 25.0)
+}
+
+#[test]
+fn test_world_to_minimap_maps_corners_and_center() {
+    let size = Vec2::splat(200.0);
+
+    assert_eq!(world_to_minimap(Vec2::new(-25.0, -25.0), world_bounds(), size), Vec2::ZERO);
+    assert_eq!(world_to_minimap(Vec2::new(25.0, 25.0), world_bounds(), size), size);
+    assert_eq!(world_to_minimap(Vec2::ZERO, world_bounds(), size), Vec2::splat(100.0));
+
+    // -Z is the top of the minimap
+    let north = world_to_minimap(Vec2::new(0.0, -20.0), world_bounds(), size);
+    assert!(north.y < 100.0);
+}
+
+#[test]
+fn test_minimap_mapping_round_trips() {
+    let size = Vec2::new(240.0, 160.0);
+    let bounds = Rect::new(-40.0, -10.0, 20.0, 30.0);
+
+    for world in [Vec2::new(-40.0, -10.0), Vec2::new(3.5, 7.25), Vec2::new(19.0, 29.0)] {
+        let minimap = world_to_minimap(world, bounds, size);
+        let back = minimap_to_world(minimap, bounds, size);
+        assert!((back - world).length() < 0.001, "{:?} round-tripped to {:?}", world, back);
+    }
+}
+
+#[test]
+fn test_top_down_camera_footprint_matches_view_size() {
+    let camera_transform = calculate_camera_transform(Vec3::new(5.0, 0.0, -3.0), 0.0, 89.9, 15.0);
+    let footprint = camera_ground_footprint(&camera_transform, Vec2::new(1280.0, 720.0), 0.02).unwrap();
+
+    assert!((footprint.width() - 1280.0 * 0.02).abs() < 0.1, "width {}", footprint.width());
+    assert!((footprint.height() - 720.0 * 0.02).abs() < 0.1, "height {}", footprint.height());
+    assert!((footprint.center() - Vec2::new(5.0, -3.0)).length() < 0.1);
+}
+
+#[test]
+fn test_blips_follow_units_and_despawn_with_them() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.add_systems(Update, minimap_blip_system);
+
+    app.world.spawn((NodeBundle::default(), Minimap::default()));
+    let hero = app.world.spawn((Hero, TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 0.0)))).id();
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(10.0, 0.75, -10.0)))).id();
+    app.update();
+    app.update();
+
+    let mut query = app.world.query::<(&MinimapBlip, &Style)>();
+    let blips: Vec<_> = query.iter(&app.world).map(|(blip, style)| (blip.target, blip.kind, style.left, style.top)).collect();
+    assert_eq!(blips.len(), 2);
+
+    let enemy_blip = blips.iter().find(|blip| blip.0 == enemy).unwrap();
+    assert_eq!(enemy_blip.1, BlipKind::Enemy);
+    let expected = world_to_minimap(Vec2::new(10.0, -10.0), world_bounds(), Vec2::splat(MINIMAP_SIZE)) - BlipKind::Enemy.size() * 0.5;
+    assert_eq!(enemy_blip.2, Val::Px(expected.x));
+    assert_eq!(enemy_blip.3, Val::Px(expected.y));
+
+    assert!(blips.iter().any(|blip| blip.0 == hero && blip.1 == BlipKind::Friendly));
+
+    app.world.despawn(enemy);
+    app.update();
+
+    let mut query = app.world.query::<&MinimapBlip>();
+    let targets: Vec<_> = query.iter(&app.world).map(|blip| blip.target).collect();
+    assert_eq!(targets, vec![hero]);
+}
+
+fn create_click_app(normalized_cursor: Vec2) -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(Input::<MouseButton>::default());
+    app.add_systems(Update, minimap_click_system);
+
+    app.world.spawn((
+        Minimap::default(),
+        RelativeCursorPosition {
+            normalized_visible_node_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+            normalized: Some(normalized_cursor),
+        },
+    ));
+    let camera = app.world.spawn(CameraController::default()).id();
+    let hero = app.world.spawn(Hero).id();
+    (app, camera, hero)
+}
+
+#[test]
+fn test_left_click_on_minimap_moves_camera() {
+    let (mut app, camera, hero) = create_click_app(Vec2::new(0.75, 0.25));
+
+    app.world.resource_mut::<Input<MouseButton>>().press(MouseButton::Left);
+    app.update();
+
+    let focus = app.world.get::<CameraController>(camera).unwrap().focus;
+    assert!((focus.x - 12.5).abs() < 0.001 && (focus.z + 12.5).abs() < 0.001, "focus {:?}", focus);
+    assert!(app.world.get::<MoveTarget>(hero).is_none());
+}
+
+#[test]
+fn test_right_click_on_minimap_orders_move() {
+    let (mut app, camera, hero) = create_click_app(Vec2::new(0.5, 1.0));
+
+    app.world.resource_mut::<Input<MouseButton>>().press(MouseButton::Right);
+    app.update();
+
+    let target = app.world.get::<MoveTarget>(hero).map(|target| target.position);
+    assert_eq!(target, Some(Vec2::new(0.0, 25.0)));
+    assert_eq!(app.world.get::<CameraController>(camera).unwrap().focus, Vec3::ZERO);
+}
+
+#[test]
+fn test_world_click_is_ignored_while_pointer_is_over_ui() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(MouseWorldPosition { position: Vec2::new(3.0, 3.0) });
+    app.insert_resource(Input::<MouseButton>::default());
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.insert_resource(PickingState { pointer_over_ui: true, ..default() });
+    app.add_systems(Update, click_to_move_system);
+
+    let hero = app.world.spawn(Hero).id();
+    app.world.resource_mut::<Input<MouseButton>>().press(MouseButton::Left);
+    app.update();
+
+    assert!(app.world.get::<MoveTarget>(hero).is_none());
+}
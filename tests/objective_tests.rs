@@ -0,0 +1,230 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::components::*;
+use rust_and_ruin::game_mode::*;
+use rust_and_ruin::map::*;
+
+const MISSION_MAP: &str = r#"(
+    name: "Convoy",
+    size: (40.0, 40.0),
+    obstacles: [
+        (kind: Building, position: (-10.0, 0.0), size: (4.0, 3.0, 4.0), health: Some(200.0), tag: Some("depot")),
+    ],
+    spawns: [
+        (side: Player, position: (0.0, 0.0)),
+        (side: Enemy, position: (10.0, 10.0), tag: Some("commander")),
+    ],
+    objectives: [
+        (id: "kill", description: "Take out the commander", kind: DestroyTarget(tag: "commander")),
+        (id: "hold", description: "Hold the depot", kind: Defend(tag: "depot", seconds: 60.0)),
+        (id: "extract", description: "Reach the extraction point", kind: ReachZone(center: (15.0, -15.0), radius: 3.0), after: ["kill", "hold"]),
+    ],
+)"#;
+
+fn objective(id: &str, kind: ObjectiveKind, after: &[&str]) -> ObjectiveDefinition {
+    ObjectiveDefinition {
+        id: id.to_string(),
+        description: id.to_string(),
+        kind,
+        after: after.iter().map(|id| id.to_string()).collect(),
+    }
+}
+
+fn create_objective_app(objectives: Vec<ObjectiveDefinition>) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(ObjectiveTracker::new(objectives));
+    app.add_event::<MapTriggerEvent>();
+    app.add_event::<ObjectiveCompletedEvent>();
+    app.add_event::<ObjectiveFailedEvent>();
+    app.add_systems(Update, objective_system);
+    app
+}
+
+fn spawn_tagged(app: &mut App, tag: &str, position: Vec2) -> Entity {
+    app.world.spawn((
+        MapTag(tag.to_string()),
+        Health::new(100.0),
+        TransformBundle::from_transform(Transform::from_xyz(position.x, 0.0, position.y)),
+    )).id()
+}
+
+fn status(app: &App, id: &str) -> Option<ObjectiveStatus> {
+    app.world.resource::<ObjectiveTracker>().status(id)
+}
+
+fn completed(app: &mut App) -> Vec<String> {
+    app.world.resource_mut::<Events<ObjectiveCompletedEvent>>().drain().map(|event| event.id).collect()
+}
+
+fn failed(app: &mut App) -> Vec<String> {
+    app.world.resource_mut::<Events<ObjectiveFailedEvent>>().drain().map(|event| event.id).collect()
+}
+
+#[test]
+fn test_mission_script_chains_objectives() {
+    let mut tracker = ObjectiveTracker::new(vec![
+        objective("first", ObjectiveKind::DestroyAllEnemies, &[]),
+        objective("second", ObjectiveKind::Survive { seconds: 10.0 }, &["first"]),
+        objective("last", ObjectiveKind::Survive { seconds: 10.0 }, &["first", "second"]),
+    ]);
+    assert_eq!(tracker.status("first"), Some(ObjectiveStatus::Active));
+    assert_eq!(tracker.status("second"), Some(ObjectiveStatus::Pending));
+    assert!(!tracker.complete("second"), "Pending objectives can't be completed");
+
+    assert!(tracker.complete("first"));
+    assert!(!tracker.complete("first"), "Completing twice is a no-op");
+    assert_eq!(tracker.status("second"), Some(ObjectiveStatus::Active));
+    assert_eq!(tracker.status("last"), Some(ObjectiveStatus::Pending), "Waits on every objective in `after`");
+
+    assert!(tracker.complete("second"));
+    assert!(tracker.complete("last"));
+    assert!(tracker.is_complete() && !tracker.has_failed());
+
+    tracker.reset();
+    assert_eq!(tracker.active().map(|objective| objective.definition.id.as_str()).collect::<Vec<_>>(), vec!["first"]);
+    assert!(ObjectiveTracker::default().is_complete(), "A map without objectives has nothing to do");
+}
+
+#[test]
+fn test_map_defines_objectives_and_tags() {
+    let map = MapDefinition::parse(MISSION_MAP).unwrap();
+    assert_eq!(map.objectives[1].kind, ObjectiveKind::Defend { tag: "depot".to_string(), seconds: 60.0 });
+    assert_eq!(map.objectives[2].after, vec!["kill".to_string(), "hold".to_string()]);
+    assert!(map.objectives[0].after.is_empty());
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HierarchyPlugin));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.add_plugins(MapPlugin { map });
+    app.update();
+
+    let commander = app.world.query_filtered::<&MapTag, With<Enemy>>().single(&app.world);
+    assert_eq!(commander.0, "commander");
+    let depot = app.world.query_filtered::<&MapTag, With<Destructible>>().single(&app.world);
+    assert_eq!(depot.0, "depot");
+    assert_eq!(app.world.query::<&MapTag>().iter(&app.world).count(), 2, "Untagged spawns get no tag");
+}
+
+#[test]
+fn test_map_reports_objectives_with_unknown_references() {
+    assert!(MapDefinition::parse(MISSION_MAP).unwrap().unresolved_references().is_empty());
+    assert!(MapDefinition::load(DEFAULT_MAP_PATH).unwrap().unresolved_references().is_empty());
+
+    let map = MapDefinition::parse(r#"(
+        name: "Typos",
+        size: (40.0, 40.0),
+        spawns: [(side: Enemy, position: (10.0, 10.0), tag: Some("commander"))],
+        objectives: [
+            (id: "kill", description: "", kind: DestroyTarget(tag: "comander")),
+            (id: "hold", description: "", kind: Defend(tag: "depot", seconds: 60.0)),
+            (id: "escort", description: "", kind: Escort(tag: "commander", center: (0.0, 0.0), radius: 3.0)),
+            (id: "scout", description: "", kind: ReachTrigger(trigger: "flank")),
+            (id: "extract", description: "", kind: ReachZone(center: (0.0, 0.0), radius: 3.0), after: ["kil"]),
+        ],
+        triggers: [(id: "flnk", center: (5.0, 5.0), radius: 2.0, actions: [])],
+    )"#).unwrap();
+    assert_eq!(map.unresolved_references(), vec![
+        "objective 'kill' refers to unknown tag 'comander'".to_string(),
+        "objective 'hold' refers to unknown tag 'depot'".to_string(),
+        "objective 'scout' waits on unknown trigger 'flank'".to_string(),
+        "objective 'extract' waits on unknown objective 'kil'".to_string(),
+    ]);
+}
+
+#[test]
+fn test_destroying_the_target_completes_and_starts_the_next_step() {
+    let mut app = create_objective_app(vec![
+        objective("kill", ObjectiveKind::DestroyTarget { tag: "commander".to_string() }, &[]),
+        objective("extract", ObjectiveKind::ReachZone { center: (10.0, 0.0), radius: 2.0 }, &["kill"]),
+    ]);
+    let commander = spawn_tagged(&mut app, "commander", Vec2::ZERO);
+    let hero = app.world.spawn((Hero, TransformBundle::default())).id();
+    app.update();
+    assert_eq!(status(&app, "kill"), Some(ObjectiveStatus::Active));
+
+    app.world.despawn(commander);
+    app.update();
+    assert_eq!(completed(&mut app), vec!["kill".to_string()]);
+    assert_eq!(status(&app, "extract"), Some(ObjectiveStatus::Active));
+
+    app.world.get_mut::<Transform>(hero).unwrap().translation = Vec3::new(9.0, 0.0, 0.5);
+    app.update();
+    assert_eq!(completed(&mut app), vec!["extract".to_string()]);
+    assert!(app.world.resource::<ObjectiveTracker>().is_complete());
+}
+
+#[test]
+fn test_survive_completes_after_its_time() {
+    let mut app = create_objective_app(vec![objective("survive", ObjectiveKind::Survive { seconds: 0.5 }, &[])]);
+    app.update();
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(status(&app, "survive"), Some(ObjectiveStatus::Active));
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(status(&app, "survive"), Some(ObjectiveStatus::Completed));
+}
+
+#[test]
+fn test_trigger_reached_before_its_objective_starts_still_counts() {
+    let mut app = create_objective_app(vec![
+        objective("wait", ObjectiveKind::Survive { seconds: 0.05 }, &[]),
+        objective("scout", ObjectiveKind::ReachTrigger { trigger: "flank".to_string() }, &["wait"]),
+        objective("regroup", ObjectiveKind::ReachTrigger { trigger: "rally".to_string() }, &["wait"]),
+    ]);
+    // The flank trigger doesn't repeat, so this early visit is the only one the mission gets
+    app.world.send_event(MapTriggerEvent { id: "flank".to_string() });
+    app.update();
+    assert_eq!(status(&app, "scout"), Some(ObjectiveStatus::Pending));
+
+    app.world.send_event(MapTriggerEvent { id: "other".to_string() });
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(status(&app, "scout"), Some(ObjectiveStatus::Completed));
+    assert_eq!(status(&app, "regroup"), Some(ObjectiveStatus::Active), "Only the fired trigger counts");
+
+    app.world.send_event(MapTriggerEvent { id: "rally".to_string() });
+    app.update();
+    assert_eq!(status(&app, "regroup"), Some(ObjectiveStatus::Completed));
+}
+
+#[test]
+fn test_defended_entity_lost_fails_the_objective() {
+    let mut app = create_objective_app(vec![
+        objective("hold", ObjectiveKind::Defend { tag: "depot".to_string(), seconds: 60.0 }, &[]),
+    ]);
+    let depot = spawn_tagged(&mut app, "depot", Vec2::ZERO);
+    app.update();
+    app.update();
+    assert_eq!(status(&app, "hold"), Some(ObjectiveStatus::Active));
+
+    app.world.get_mut::<Health>(depot).unwrap().current = 0.0;
+    app.update();
+    assert_eq!(failed(&mut app), vec!["hold".to_string()], "Zero health counts as lost before the despawn");
+    assert!(app.world.resource::<ObjectiveTracker>().has_failed());
+    app.update();
+    assert!(failed(&mut app).is_empty(), "Failure is reported once");
+}
+
+#[test]
+fn test_escort_completes_when_the_entity_reaches_the_zone() {
+    let mut app = create_objective_app(vec![
+        objective("escort", ObjectiveKind::Escort { tag: "convoy".to_string(), center: (20.0, 0.0), radius: 3.0 }, &[]),
+        objective("escort_lost", ObjectiveKind::Escort { tag: "truck".to_string(), center: (20.0, 0.0), radius: 3.0 }, &[]),
+    ]);
+    let convoy = spawn_tagged(&mut app, "convoy", Vec2::ZERO);
+    app.update();
+    assert_eq!(status(&app, "escort"), Some(ObjectiveStatus::Active));
+    assert_eq!(status(&app, "escort_lost"), Some(ObjectiveStatus::Failed), "Nothing left to escort");
+
+    app.world.get_mut::<Transform>(convoy).unwrap().translation = Vec3::new(18.0, 0.0, 1.0);
+    app.update();
+    assert_eq!(completed(&mut app), vec!["escort".to_string()]);
+}
@@ -0,0 +1,168 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_rapier3d::prelude::CollisionEvent;
+use bevy_rapier3d::rapier::geometry::CollisionEventFlags;
+use std::time::Duration;
+use rust_and_ruin::camera::{CameraShakeEvent, CameraShakeSource};
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::systems::*;
+
+struct TestMech {
+    root: Entity,
+    lower: Entity,
+    upper: Entity,
+    weapon: Entity,
+}
+
+fn create_damage_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.add_event::<CollisionEvent>();
+    app.add_event::<DamageEvent>();
+    app.add_event::<PartDestroyedEvent>();
+    app.add_event::<CameraShakeEvent>();
+    app.add_systems(Update, (
+        mech_part_collision_system,
+        mech_part_destruction_system,
+        mech_movement_system,
+    ).chain());
+    app
+}
+
+fn spawn_damageable_mech(app: &mut App) -> TestMech {
+    let root = app.world.spawn((
+        Mech::new("Target"),
+        MechMovement::default(),
+        create_tank_treads_lower(),
+        TransformBundle::default(),
+    )).id();
+    let lower = app.world.spawn((MechLowerPart, PartHealth::lower(), TransformBundle::default())).id();
+    let weapon = app.world.spawn((
+        create_cannon_weapon("main".to_string()),
+        CannonWeapon::default(),
+        PartHealth::weapon(),
+        TransformBundle::default(),
+    )).id();
+    let mut upper_body = create_turret_upper();
+    upper_body.hardpoints[0].occupied_by = Some(weapon);
+    let upper = app.world.spawn((
+        MechUpperPart,
+        upper_body,
+        PartHealth::upper(),
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 0.75, 0.0)),
+    )).id();
+
+    app.world.entity_mut(root).push_children(&[lower, upper]);
+    app.world.entity_mut(upper).push_children(&[weapon]);
+    app.world.entity_mut(root).insert(MechHierarchy {
+        lower: Some(lower),
+        upper: Some(upper),
+        weapons: vec![weapon],
+    });
+    app.update();
+    TestMech { root, lower, upper, weapon }
+}
+
+fn hit(app: &mut App, part: Entity, damage: f32, fired_by: Option<Entity>) -> Entity {
+    let projectile = app.world.spawn(Projectile { damage, speed: 15.0 }).id();
+    if let Some(shooter) = fired_by {
+        app.world.entity_mut(projectile).insert(FiredBy(shooter));
+    }
+    app.world.send_event(CollisionEvent::Started(projectile, part, CollisionEventFlags::empty()));
+    app.update();
+    projectile
+}
+
+#[test]
+fn test_hit_damages_only_the_struck_part() {
+    let mut app = create_damage_app();
+    let mech = spawn_damageable_mech(&mut app);
+
+    let projectile = hit(&mut app, mech.weapon, 20.0, None);
+
+    assert_eq!(app.world.get::<PartHealth>(mech.weapon).unwrap().current, WEAPON_HIT_POINTS - 20.0);
+    assert_eq!(app.world.get::<PartHealth>(mech.upper).unwrap().current, UPPER_BODY_HIT_POINTS);
+    assert_eq!(app.world.get::<PartHealth>(mech.lower).unwrap().current, LOWER_BODY_HIT_POINTS);
+    assert!(app.world.get_entity(projectile).is_none(), "Projectiles are spent on the part they hit");
+
+    let events = app.world.resource::<Events<DamageEvent>>();
+    let damage: Vec<_> = events.get_reader().read(events).map(|event| (event.target, event.amount)).collect();
+    assert_eq!(damage, vec![(mech.weapon, 20.0)]);
+}
+
+#[test]
+fn test_hits_on_the_hero_shake_the_camera() {
+    let mut app = create_damage_app();
+    let enemy_mech = spawn_damageable_mech(&mut app);
+    let hero_mech = spawn_damageable_mech(&mut app);
+    app.world.entity_mut(hero_mech.root).insert(Hero);
+
+    hit(&mut app, enemy_mech.upper, 10.0, None);
+    hit(&mut app, hero_mech.lower, 10.0, None);
+
+    let events = app.world.resource::<Events<CameraShakeEvent>>();
+    let sources: Vec<_> = events.get_reader().read(events).map(|event| event.source).collect();
+    assert_eq!(sources, vec![CameraShakeSource::OwnMechHit]);
+}
+
+#[test]
+fn test_mech_cannot_hit_its_own_parts() {
+    let mut app = create_damage_app();
+    let mech = spawn_damageable_mech(&mut app);
+
+    let projectile = hit(&mut app, mech.upper, 30.0, Some(mech.root));
+
+    assert_eq!(app.world.get::<PartHealth>(mech.upper).unwrap().current, UPPER_BODY_HIT_POINTS);
+    assert!(app.world.get_entity(projectile).is_some());
+}
+
+#[test]
+fn test_destroyed_weapon_frees_its_hardpoint() {
+    let mut app = create_damage_app();
+    let mech = spawn_damageable_mech(&mut app);
+
+    hit(&mut app, mech.weapon, WEAPON_HIT_POINTS, None);
+    app.update();
+
+    assert!(app.world.get_entity(mech.weapon).is_none());
+    let upper_body = app.world.get::<MechUpperBody>(mech.upper).unwrap();
+    assert_eq!(upper_body.hardpoints[0].occupied_by, None);
+    assert!(app.world.get::<MechHierarchy>(mech.root).unwrap().weapons.is_empty());
+    assert!(app.world.get_entity(mech.root).is_some(), "Losing a weapon does not kill the mech");
+}
+
+#[test]
+fn test_destroyed_lower_body_immobilises_the_mech() {
+    let mut app = create_damage_app();
+    let mech = spawn_damageable_mech(&mut app);
+    app.world.entity_mut(mech.root).insert(MoveTarget { position: Vec2::new(0.0, 10.0) });
+
+    hit(&mut app, mech.lower, LOWER_BODY_HIT_POINTS + 5.0, None);
+    for _ in 0..10 {
+        app.update();
+    }
+
+    assert!(app.world.get_entity(mech.lower).is_none());
+    assert!(app.world.get::<MechHierarchy>(mech.root).unwrap().lower.is_none());
+    assert!(app.world.get::<MechLowerBody>(mech.root).is_none());
+    let translation = app.world.get::<Transform>(mech.root).unwrap().translation;
+    assert!(translation.z < 0.5, "An immobilised mech should stay put, moved to {:?}", translation);
+}
+
+#[test]
+fn test_destroyed_upper_body_kills_the_mech() {
+    let mut app = create_damage_app();
+    let mech = spawn_damageable_mech(&mut app);
+
+    hit(&mut app, mech.upper, UPPER_BODY_HIT_POINTS, None);
+    app.update();
+
+    assert!(app.world.get_entity(mech.root).is_none());
+    assert!(app.world.get_entity(mech.weapon).is_none());
+
+    let events = app.world.resource::<Events<PartDestroyedEvent>>();
+    let destroyed: Vec<_> = events.get_reader().read(events).map(|event| (event.mech, event.slot)).collect();
+    assert_eq!(destroyed, vec![(mech.root, MechPartSlot::Upper)]);
+}
@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use rust_and_ruin::components::*;
+use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::input::*;
+use rust_and_ruin::systems::projectile_pool::{ProjectileAssets, ProjectilePool};
+use rust_and_ruin::systems::picking::*;
+use rust_and_ruin::systems::spatial_index::SpatialIndex;
+
+fn create_picking_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    // Rapier's async collider systems expect mesh and scene assets to exist
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<Scene>::default());
+    app.init_resource::<bevy::scene::SceneSpawner>();
+    app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
+    app.init_resource::<PickingState>();
+    app.add_systems(Update, entity_picking_system);
+    app
+}
+
+fn downward_ray_at(x: f32, z: f32) -> Ray {
+    Ray {
+        origin: Vec3::new(x, 50.0, z),
+        direction: Vec3::NEG_Y,
+    }
+}
+
+#[test]
+fn test_ray_picks_entity_under_cursor() {
+    let mut app = create_picking_app();
+    let enemy = app.world.spawn((
+        Enemy,
+        TransformBundle::from_transform(Transform::from_xyz(4.0, 0.75, 0.0)),
+        Collider::cuboid(0.75, 0.75, 0.75),
+    )).id();
+    app.update();
+
+    app.world.resource_mut::<PickingState>().ray = Some(downward_ray_at(4.5, 0.5));
+    app.update();
+
+    let picking = app.world.resource::<PickingState>();
+    assert_eq!(picking.hovered, Some(enemy));
+    let hit_point = picking.hit_point.unwrap();
+    assert!((hit_point.y - 1.5).abs() < 0.01, "Ray should hit the top face, got {:?}", hit_point);
+
+    app.world.resource_mut::<PickingState>().ray = Some(downward_ray_at(10.0, 10.0));
+    app.update();
+    assert_eq!(app.world.resource::<PickingState>().hovered, None);
+}
+
+#[test]
+fn test_ray_picks_topmost_of_overlapping_units() {
+    let mut app = create_picking_app();
+    let _short = app.world.spawn((
+        Enemy,
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 0.5, 0.0)),
+        Collider::cuboid(1.0, 0.5, 1.0),
+    )).id();
+    let tall = app.world.spawn((
+        Enemy,
+        TransformBundle::from_transform(Transform::from_xyz(0.5, 2.0, 0.0)),
+        Collider::cuboid(0.5, 2.0, 0.5),
+    )).id();
+    app.update();
+
+    app.world.resource_mut::<PickingState>().ray = Some(downward_ray_at(0.4, 0.0));
+    app.update();
+
+    assert_eq!(app.world.resource::<PickingState>().hovered, Some(tall));
+}
+
+#[test]
+fn test_pick_resolves_child_collider_to_root() {
+    let mut app = create_picking_app();
+    let hero = app.world.spawn((Hero, TransformBundle::default())).id();
+    let lower_body = app.world.spawn((
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 0.25, 0.0)),
+        Collider::cuboid(0.75, 0.25, 1.0),
+    )).id();
+    app.world.entity_mut(hero).push_children(&[lower_body]);
+    app.update();
+
+    app.world.resource_mut::<PickingState>().ray = Some(downward_ray_at(0.0, 0.0));
+    app.update();
+
+    assert_eq!(app.world.resource::<PickingState>().hovered, Some(hero));
+}
+
+#[test]
+fn test_projectiles_do_not_block_picking() {
+    let mut app = create_picking_app();
+    let enemy = app.world.spawn((
+        Enemy,
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 0.75, 0.0)),
+        Collider::cuboid(0.75, 0.75, 0.75),
+    )).id();
+    app.world.spawn((
+        Projectile { damage: 10.0, speed: 15.0 },
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 5.0, 0.0)),
+        Collider::ball(0.5),
+    ));
+    app.update();
+
+    app.world.resource_mut::<PickingState>().ray = Some(downward_ray_at(0.0, 0.0));
+    app.update();
+
+    assert_eq!(app.world.resource::<PickingState>().hovered, Some(enemy));
+}
+
+#[test]
+fn test_q_targets_hovered_enemy_far_from_ground_point() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HierarchyPlugin));
+    // Ground point is nowhere near the enemy, as happens when clicking the top of a tall unit
+    app.insert_resource(MouseWorldPosition { position: Vec2::new(0.0, -8.0) });
+    app.insert_resource(Input::<KeyCode>::default());
+    app.insert_resource(Input::<MouseButton>::default());
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.init_resource::<PickingState>();
+    app.init_resource::<SpatialIndex>();
+    app.init_resource::<SelectionRingAssets>();
+    app.add_systems(Update, enemy_selection_system);
+
+    let hero = app.world.spawn((Hero, TransformBundle::default())).id();
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 5.0)))).id();
+
+    app.world.resource_mut::<PickingState>().hovered = Some(enemy);
+    app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::Q);
+    app.update();
+
+    assert_eq!(app.world.get::<AttackTarget>(hero).map(|target| target.entity), Some(enemy));
+
+    // Reselecting swaps the indicator without adding ring assets
+    let mesh_count = app.world.resource::<Assets<Mesh>>().len();
+    let mut input = app.world.resource_mut::<Input<KeyCode>>();
+    input.release(KeyCode::Q);
+    input.clear();
+    input.press(KeyCode::Q);
+    app.update();
+
+    assert_eq!(app.world.resource::<Assets<Mesh>>().len(), mesh_count);
+    let ring_mesh = app.world.resource::<SelectionRingAssets>().target_indicator_mesh.clone();
+    let mut indicators = app.world.query_filtered::<&Handle<Mesh>, With<TargetIndicator>>();
+    assert_eq!(indicators.iter(&app.world).collect::<Vec<_>>(), vec![&ring_mesh]);
+}
+
+#[test]
+fn test_click_selects_friendly_unit_and_moves_only_selection() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HierarchyPlugin));
+    app.insert_resource(MouseWorldPosition { position: Vec2::new(3.0, 3.0) });
+    app.insert_resource(Input::<MouseButton>::default());
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.init_resource::<PickingState>();
+    app.add_systems(Update, click_to_move_system);
+
+    let hero_a = app.world.spawn((Hero, TransformBundle::default())).id();
+    let hero_b = app.world.spawn((Hero, TransformBundle::default())).id();
+
+    // Click on hero A selects it without moving anyone
+    app.world.resource_mut::<PickingState>().hovered = Some(hero_a);
+    app.world.resource_mut::<Input<MouseButton>>().press(MouseButton::Left);
+    app.update();
+
+    assert!(app.world.get::<Selected>(hero_a).is_some());
+    assert!(app.world.get::<Selected>(hero_b).is_none());
+    assert!(app.world.get::<MoveTarget>(hero_a).is_none());
+
+    // Click on the ground moves only the selected hero
+    app.world.resource_mut::<PickingState>().hovered = None;
+    app.world.resource_mut::<Input<MouseButton>>().release(MouseButton::Left);
+    app.world.resource_mut::<Input<MouseButton>>().clear();
+    app.world.resource_mut::<Input<MouseButton>>().press(MouseButton::Left);
+    app.update();
+
+    assert_eq!(app.world.get::<MoveTarget>(hero_a).map(|target| target.position), Some(Vec2::new(3.0, 3.0)));
+    assert!(app.world.get::<MoveTarget>(hero_b).is_none());
+}
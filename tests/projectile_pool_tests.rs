@@ -0,0 +1,107 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_rapier3d::prelude::{ColliderDisabled, RigidBodyDisabled};
+use std::time::Duration;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::AmmoType;
+use rust_and_ruin::systems::*;
+
+fn create_pool_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app
+}
+
+fn fire(mut commands: Commands, assets: Res<ProjectileAssets>, mut pool: ResMut<ProjectilePool>) {
+    spawn_tank_shell(&mut commands, &assets, &mut pool, Vec2::ZERO, Vec2::X, 10.0, AmmoType::ArmorPiercing);
+}
+
+fn recycle_shells(mut commands: Commands, shells: Query<Entity, With<TankShell>>) {
+    for shell in shells.iter() {
+        commands.add(Recycle(shell));
+    }
+}
+
+fn asset_counts(app: &App) -> (usize, usize) {
+    (app.world.resource::<Assets<Mesh>>().len(), app.world.resource::<Assets<StandardMaterial>>().len())
+}
+
+#[test]
+fn test_asset_counts_stay_flat_over_many_shots() {
+    let mut app = create_pool_app();
+    app.add_systems(Update, (recycle_shells, fire).chain());
+    let before = asset_counts(&app);
+
+    for _ in 0..2000 {
+        app.update();
+    }
+
+    assert_eq!(asset_counts(&app), before);
+    let mut pooled = app.world.query_filtered::<(), With<Pooled>>();
+    assert!(pooled.iter(&app.world).count() <= 2, "Spent shells are reused rather than respawned");
+}
+
+#[test]
+fn test_recycled_shell_is_parked_and_reused() {
+    let mut app = create_pool_app();
+    app.world.run_system_once(fire);
+    let shell = app.world.query_filtered::<Entity, With<TankShell>>().single(&app.world);
+
+    app.world.run_system_once(recycle_shells);
+    assert!(app.world.get::<Projectile>(shell).is_none());
+    assert!(app.world.get::<RigidBodyDisabled>(shell).is_some());
+    assert_eq!(app.world.get::<Visibility>(shell), Some(&Visibility::Hidden));
+    assert_eq!(app.world.resource::<ProjectilePool>().parked(PoolKind::Shell), 1);
+
+    app.world.run_system_once(fire);
+    assert_eq!(app.world.query_filtered::<Entity, With<TankShell>>().single(&app.world), shell);
+    assert!(app.world.get::<RigidBodyDisabled>(shell).is_none());
+    assert!(app.world.get::<ColliderDisabled>(shell).is_none());
+    assert_eq!(app.world.get::<Visibility>(shell), Some(&Visibility::Inherited));
+    assert_eq!(app.world.resource::<ProjectilePool>().parked(PoolKind::Shell), 0);
+}
+
+#[test]
+fn test_recycling_twice_parks_once_and_unpooled_entities_are_despawned() {
+    let mut app = create_pool_app();
+    app.world.run_system_once(fire);
+    let shell = app.world.query_filtered::<Entity, With<TankShell>>().single(&app.world);
+    let stray = app.world.spawn(Projectile { damage: 1.0, speed: 1.0 }).id();
+
+    app.world.run_system_once(move |mut commands: Commands| {
+        commands.add(Recycle(shell));
+        commands.add(Recycle(shell));
+        commands.add(Recycle(stray));
+    });
+
+    assert_eq!(app.world.resource::<ProjectilePool>().parked(PoolKind::Shell), 1);
+    assert!(app.world.get_entity(stray).is_none());
+}
+
+#[test]
+fn test_click_markers_expire_into_the_pool() {
+    let mut app = create_pool_app();
+    app.add_systems(Update, click_marker_lifetime_system);
+    let place = |position: Vec2| move |mut commands: Commands, assets: Res<ProjectileAssets>, mut pool: ResMut<ProjectilePool>| {
+        spawn_click_marker(&mut commands, &assets, &mut pool, position);
+    };
+    app.world.run_system_once(place(Vec2::new(3.0, 4.0)));
+    let marker = app.world.query_filtered::<Entity, With<ClickMarker>>().single(&app.world);
+
+    // The first update has no elapsed time
+    for _ in 0..=(CLICK_MARKER_LIFETIME * 10.0) as usize {
+        app.update();
+    }
+    assert!(app.world.get::<ClickMarker>(marker).is_none());
+    assert_eq!(app.world.resource::<ProjectilePool>().parked(PoolKind::ClickMarker), 1);
+
+    app.world.run_system_once(place(Vec2::new(-1.0, 2.0)));
+    assert!(app.world.get::<ClickMarker>(marker).is_some());
+    assert_eq!(app.world.get::<Transform>(marker).unwrap().translation, Vec3::new(-1.0, 0.5, 2.0));
+}
@@ -0,0 +1,115 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::Collider;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rust_and_ruin::components::*;
+use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::*;
+
+fn entry(index: u32, x: f32, z: f32, side: UnitSide) -> SpatialEntry {
+    SpatialEntry {
+        entity: Entity::from_raw(index),
+        position: Vec2::new(x, z),
+        radius: DEFAULT_UNIT_RADIUS,
+        side,
+    }
+}
+
+fn scattered_index(count: u32) -> (SpatialIndex, Vec<SpatialEntry>) {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut index = SpatialIndex::default();
+    let mut entries = Vec::new();
+    for i in 0..count {
+        let side = if i % 3 == 0 { UnitSide::Friendly } else { UnitSide::Enemy };
+        let unit = entry(i, rng.gen_range(-50.0..50.0), rng.gen_range(-50.0..50.0), side);
+        index.insert(unit);
+        entries.push(unit);
+    }
+    (index, entries)
+}
+
+fn sorted_entities<'a>(entries: impl IntoIterator<Item = &'a SpatialEntry>) -> Vec<Entity> {
+    let mut entities: Vec<_> = entries.into_iter().map(|entry| entry.entity).collect();
+    entities.sort();
+    entities
+}
+
+#[test]
+fn test_radius_and_rect_queries_match_brute_force() {
+    let (index, entries) = scattered_index(500);
+
+    for center in [Vec2::ZERO, Vec2::new(-42.0, 17.5), Vec2::new(60.0, 60.0)] {
+        let expected = entries.iter().filter(|e| e.position.distance(center) <= 6.0 + e.radius);
+        assert_eq!(sorted_entities(index.query_radius(center, 6.0)), sorted_entities(expected));
+    }
+
+    let (min, max) = (Vec2::new(-10.0, 5.0), Vec2::new(12.0, -20.0));
+    let expected = entries.iter().filter(|e| {
+        (-10.0..=12.0).contains(&e.position.x) && (-20.0..=5.0).contains(&e.position.y)
+    });
+    assert_eq!(sorted_entities(index.query_rect(min, max)), sorted_entities(expected));
+}
+
+#[test]
+fn test_nearest_honours_max_distance() {
+    let mut index = SpatialIndex::default();
+    index.insert(entry(1, 3.0, 0.0, UnitSide::Enemy));
+    index.insert(entry(2, 1.0, 0.0, UnitSide::Friendly));
+
+    assert_eq!(index.nearest(Vec2::ZERO, 5.0, |e| e.side == UnitSide::Enemy).map(|e| e.entity), Some(Entity::from_raw(1)));
+    assert!(index.nearest(Vec2::ZERO, 2.0, |e| e.side == UnitSide::Enemy).is_none());
+    assert_eq!(index.nearest(Vec2::ZERO, 2.0, |_| true).map(|e| e.entity), Some(Entity::from_raw(2)));
+}
+
+#[test]
+fn test_rebuild_tracks_heroes_and_enemies_with_collider_footprints() {
+    let mut world = World::new();
+    world.init_resource::<SpatialIndex>();
+    let hero = world.spawn((Hero, Transform::from_xyz(1.0, 0.5, 2.0))).id();
+    let enemy = world.spawn((Enemy, Transform::from_xyz(-4.0, 0.75, 6.0), Collider::cuboid(1.5, 0.75, 1.0))).id();
+    world.spawn(Transform::from_xyz(0.0, 0.0, 0.0)); // Scenery is not indexed
+
+    world.run_system_once(spatial_index_system);
+    let index = world.resource::<SpatialIndex>();
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.get(hero).map(|e| (e.position, e.side)), Some((Vec2::new(1.0, 2.0), UnitSide::Friendly)));
+    let enemy_entry = index.get(enemy).unwrap();
+    assert_eq!(enemy_entry.side, UnitSide::Enemy);
+    assert!((enemy_entry.radius - 1.5).abs() < 0.001);
+
+    world.entity_mut(hero).insert(Transform::from_xyz(9.0, 0.5, 9.0));
+    world.despawn(enemy);
+    world.run_system_once(spatial_index_system);
+    let index = world.resource::<SpatialIndex>();
+    assert_eq!(index.len(), 1);
+    assert_eq!(index.get(hero).unwrap().position, Vec2::new(9.0, 9.0));
+    assert!(index.get(enemy).is_none());
+}
+
+#[test]
+fn test_q_selects_closest_enemy_from_index() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HierarchyPlugin));
+    app.insert_resource(MouseWorldPosition { position: Vec2::new(5.0, 0.5) });
+    app.insert_resource(Input::<KeyCode>::default());
+    app.insert_resource(Input::<MouseButton>::default());
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<PickingState>();
+    app.init_resource::<SpatialIndex>();
+    app.add_systems(PreUpdate, spatial_index_system);
+    app.init_resource::<SelectionRingAssets>();
+    app.add_systems(Update, enemy_selection_system);
+
+    let hero = app.world.spawn((Hero, TransformBundle::default())).id();
+    // The friendly unit is nearest the cursor but is never picked as a target
+    app.world.spawn((Hero, TransformBundle::from_transform(Transform::from_xyz(5.0, 0.0, 0.5))));
+    app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(6.5, 0.0, 0.5))));
+    let closest = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(4.0, 0.0, 0.0)))).id();
+
+    app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::Q);
+    app.update();
+
+    assert_eq!(app.world.get::<AttackTarget>(hero).map(|target| target.entity), Some(closest));
+}
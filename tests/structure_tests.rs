@@ -0,0 +1,237 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use bevy_rapier3d::prelude::{Collider, CollisionEvent, ExternalImpulse, NoUserData, RapierPhysicsPlugin, Velocity};
+use bevy_rapier3d::rapier::geometry::CollisionEventFlags;
+use rust_and_ruin::camera::CameraShakeEvent;
+use rust_and_ruin::components::*;
+use rust_and_ruin::map::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::systems::*;
+
+const WALL_COLOR: Color = Color::rgb(0.4, 0.35, 0.3);
+
+fn create_structure_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.insert_resource(NavigationGrid::new(Vec2::splat(20.0), NAV_CELL_SIZE));
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.init_resource::<RubbleAssets>();
+    app.add_event::<CollisionEvent>();
+    app.add_event::<DamageEvent>();
+    app.add_event::<EnemyDestroyedEvent>();
+    app.add_event::<ExplosionEvent>();
+    app.add_systems(Update, (
+        collision_detection_system,
+        hit_flash_system,
+        structure_damage_stage_system,
+        structure_destruction_system,
+    ).chain());
+    app
+}
+
+fn spawn_wall(app: &mut App, health: f32) -> Entity {
+    let obstacle = ObstacleDefinition {
+        kind: ObstacleKind::Wall,
+        position: (2.0, 0.0),
+        size: (4.0, 1.5, 1.0),
+        rotation: 0.0,
+        health: Some(health),
+        tag: None,
+    };
+    let heightmap = Heightmap::flat(Vec2::splat(20.0));
+    app.world.run_system_once(move |mut commands: Commands,
+                                              mut meshes: ResMut<Assets<Mesh>>,
+                                              mut materials: ResMut<Assets<StandardMaterial>>,
+                                              mut navigation: ResMut<NavigationGrid>| {
+        spawn_obstacle(&mut commands, &mut meshes, &mut materials, &heightmap, Some(&mut navigation), &obstacle)
+    })
+}
+
+fn spawn_shell(app: &mut App, damage: f32) -> Entity {
+    app.world.spawn((
+        Projectile { damage, speed: 20.0 },
+        TankShell { velocity: Vec2::X * 20.0, spawn_position: Vec2::ZERO, max_range: 15.0 },
+        Velocity::linear(Vec3::X * 20.0),
+        TransformBundle::from_transform(Transform::from_xyz(-0.5, 0.75, 0.0)),
+    )).id()
+}
+
+fn hit(app: &mut App, shell: Entity, target: Entity) {
+    app.world.send_event(CollisionEvent::Started(shell, target, CollisionEventFlags::empty()));
+    app.update();
+}
+
+fn wall_color(app: &App, wall: Entity) -> Color {
+    let material = app.world.get::<Handle<StandardMaterial>>(wall).unwrap();
+    app.world.resource::<Assets<StandardMaterial>>().get(material).unwrap().base_color
+}
+
+#[test]
+fn test_damage_stage_follows_health_thresholds() {
+    let mut health = Health::new(100.0);
+    assert_eq!(damage_stage(&health), 0);
+    health.current = 60.0;
+    assert_eq!(damage_stage(&health), 1);
+    health.current = 20.0;
+    assert_eq!(damage_stage(&health), 2);
+}
+
+#[test]
+fn test_obstacle_footprint_blocks_navigation() {
+    let mut app = create_structure_app();
+    spawn_wall(&mut app, 100.0);
+
+    let navigation = app.world.resource::<NavigationGrid>();
+    assert_eq!(navigation.get(Vec2::new(1.5, 0.0)), Some(NavCell::Blocked));
+    assert_eq!(navigation.get(Vec2::new(3.5, 0.0)), Some(NavCell::Blocked));
+    assert!(navigation.is_passable(Vec2::new(2.0, 2.0)));
+    assert!(navigation.is_passable(Vec2::new(-1.0, 0.0)));
+    assert_eq!(navigation.get(Vec2::new(15.0, 0.0)), None, "Off the map");
+}
+
+#[test]
+fn test_rotated_footprint_is_marked_along_its_length() {
+    let mut navigation = NavigationGrid::new(Vec2::splat(20.0), NAV_CELL_SIZE);
+    let transform = Transform::from_xyz(0.5, 0.0, 0.5).with_rotation(Quat::from_rotation_y(90.0_f32.to_radians()));
+    navigation.mark_footprint(&transform, Vec2::new(2.0, 0.5), NavCell::Blocked);
+
+    assert!(!navigation.is_passable(Vec2::new(0.5, 2.0)), "A quarter turn lays the wall along Z");
+    assert!(navigation.is_passable(Vec2::new(2.5, 0.5)));
+}
+
+#[test]
+fn test_shell_hit_damages_stops_and_shoves_a_structure() {
+    let mut app = create_structure_app();
+    let wall = spawn_wall(&mut app, 100.0);
+    let shell = spawn_shell(&mut app, 25.0);
+
+    hit(&mut app, shell, wall);
+
+    assert_eq!(app.world.get::<Health>(wall).unwrap().current, 75.0);
+    assert!(app.world.get::<ExternalImpulse>(wall).unwrap().impulse.x > 0.0, "Same knockback impulse as enemies");
+    let damage: Vec<_> = app.world.resource_mut::<Events<DamageEvent>>().drain().collect();
+    assert_eq!(damage.len(), 1);
+    assert_eq!(damage[0].target, wall);
+    assert!(app.world.get_entity(shell).is_none(), "Structures stop the shells that hit them");
+}
+
+#[test]
+fn test_structure_darkens_through_damage_stages() {
+    let mut app = create_structure_app();
+    let wall = spawn_wall(&mut app, 100.0);
+    assert_eq!(wall_color(&app, wall), WALL_COLOR);
+
+    let shell = spawn_shell(&mut app, 40.0);
+    hit(&mut app, shell, wall);
+    assert_eq!(app.world.get::<Destructible>(wall).unwrap().stage, 1);
+    let damaged = wall_color(&app, wall);
+    assert!(damaged.r() < WALL_COLOR.r());
+
+    let shell = spawn_shell(&mut app, 40.0);
+    hit(&mut app, shell, wall);
+    assert_eq!(app.world.get::<Destructible>(wall).unwrap().stage, 2);
+    assert!(wall_color(&app, wall).r() < damaged.r());
+}
+
+#[test]
+fn test_destroyed_structure_leaves_passable_rubble() {
+    let mut app = create_structure_app();
+    let wall = spawn_wall(&mut app, 20.0);
+    let shell = spawn_shell(&mut app, 25.0);
+
+    let mesh_count = app.world.resource::<Assets<Mesh>>().len();
+    hit(&mut app, shell, wall);
+    app.update();
+
+    assert!(app.world.get_entity(wall).is_none());
+    let (rubble, mesh) = app.world.query_filtered::<(&Transform, &Handle<Mesh>), With<Rubble>>().single(&app.world);
+    assert!((rubble.translation - Vec3::new(2.0, RUBBLE_HEIGHT / 2.0, 0.0)).length() < 0.001, "Rubble rests on the ground");
+    assert_eq!(rubble.scale, Vec3::new(4.0, RUBBLE_HEIGHT, 1.0), "Shared rubble mesh is scaled to the footprint");
+    assert_eq!(*mesh, app.world.resource::<RubbleAssets>().mesh);
+    assert_eq!(app.world.resource::<Assets<Mesh>>().len(), mesh_count, "Rubble adds no new meshes");
+    assert_eq!(app.world.query_filtered::<(), (With<Rubble>, With<Collider>)>().iter(&app.world).count(), 0);
+
+    let navigation = app.world.resource::<NavigationGrid>();
+    assert_eq!(navigation.get(Vec2::new(2.0, 0.0)), Some(NavCell::Rough));
+    assert!(navigation.movement_cost(Vec2::new(2.0, 0.0)).unwrap() > navigation.movement_cost(Vec2::new(2.0, 3.0)).unwrap());
+}
+
+#[test]
+fn test_explosions_damage_structures() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<Scene>::default());
+    app.init_resource::<bevy::scene::SceneSpawner>();
+    app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
+    app.add_event::<DamageEvent>();
+    app.add_event::<EnemyDestroyedEvent>();
+    app.add_event::<ExplosionEvent>();
+    app.add_event::<CameraShakeEvent>();
+    app.add_systems(Update, explosion_system);
+
+    let crate_entity = app.world.spawn((
+        Destructible { half_extents: Vec3::splat(0.5), base_color: Color::WHITE, stage: 0 },
+        Health::new(40.0),
+        ExternalImpulse::default(),
+        Collider::cuboid(0.5, 0.5, 0.5),
+        TransformBundle::from_transform(Transform::from_xyz(1.0, 0.5, 0.0)),
+    )).id();
+    app.update();
+
+    app.world.send_event(ExplosionEvent::new(Vec3::new(0.0, 0.5, 0.0), 3.0, 30.0, 10.0));
+    app.update();
+
+    assert!(app.world.get::<Health>(crate_entity).unwrap().current < 40.0);
+    assert!(app.world.get::<ExternalImpulse>(crate_entity).unwrap().impulse.x > 0.0);
+
+    app.world.send_event(ExplosionEvent::new(Vec3::new(0.0, 0.5, 0.0), 3.0, 100.0, 10.0));
+    app.update();
+    assert!(app.world.get_entity(crate_entity).is_some(), "Destroyed structures are left for the rubble system");
+}
+
+/// Distance a mech drives along +X in two seconds over `navigation`.
+fn drive_across(navigation: NavigationGrid) -> f32 {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(navigation);
+    app.add_systems(Update, mech_movement_system);
+    let mech = app.world.spawn((
+        Transform::from_rotation(Quat::from_rotation_y(90.0_f32.to_radians())),
+        MechMovement::default(),
+        create_tank_treads_lower(),
+        MoveTarget { position: Vec2::new(9.0, 0.0) },
+    )).id();
+
+    for _ in 0..20 {
+        app.update();
+    }
+    app.world.get::<Transform>(mech).unwrap().translation.x
+}
+
+#[test]
+fn test_mechs_slow_on_rough_ground_and_stop_short_of_blocked_cells() {
+    let open = NavigationGrid::new(Vec2::splat(20.0), NAV_CELL_SIZE);
+    let mut rough = open.clone();
+    rough.mark_footprint(&Transform::IDENTITY, Vec2::splat(10.0), NavCell::Rough);
+    let mut walled = open.clone();
+    walled.mark_footprint(&Transform::from_xyz(5.0, 0.0, 0.0), Vec2::new(0.5, 2.0), NavCell::Blocked);
+
+    let open_distance = drive_across(open);
+    let rough_distance = drive_across(rough);
+    let walled_distance = drive_across(walled.clone());
+    assert!(open_distance > 4.5);
+    assert!(rough_distance < open_distance * 0.75, "Rough ground should slow the mech: {} vs {}", rough_distance, open_distance);
+    assert!(walled_distance > 3.0 && walled_distance < 4.5, "Mech should stop before the wall, got to {}", walled_distance);
+
+    let treads = create_tank_treads_lower().movement_stats;
+    assert!(!is_valid_move_target(None, Some(&walled), Vec2::new(5.0, 0.0), &treads));
+    assert!(is_valid_move_target(None, Some(&walled), Vec2::new(7.0, 0.0), &treads));
+}
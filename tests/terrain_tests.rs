@@ -0,0 +1,257 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy::render::mesh::VertexAttributeValues;
+use bevy::time::TimeUpdateStrategy;
+use bevy_rapier3d::prelude::*;
+use std::time::Duration;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::*;
+
+fn shipped_heightmap() -> Heightmap {
+    let bytes = std::fs::read("assets/terrain/heightmap.png").unwrap();
+    Heightmap::from_png(&bytes, Vec2::splat(50.0), 4.0).unwrap()
+}
+
+/// 100x100 plane rising along +X at `degrees`, flat along Z.
+fn ramp(degrees: f32) -> Heightmap {
+    let rise = 50.0 * degrees.to_radians().tan();
+    Heightmap::new(2, 2, Vec2::splat(100.0), vec![-rise, rise, -rise, rise])
+}
+
+#[test]
+fn test_shipped_heightmap_is_flat_around_spawns_with_a_steep_ridge() {
+    let heightmap = shipped_heightmap();
+    assert_eq!(heightmap.size(), Vec2::splat(50.0));
+    for spawn in [Vec2::new(-4.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(2.0, 5.0)] {
+        assert_eq!(heightmap.height_at(spawn), 0.0);
+    }
+    assert!(heightmap.height_at(Vec2::new(-14.0, -16.0)) > 3.0);
+
+    let treads = create_tank_treads_lower().movement_stats;
+    assert!(heightmap.is_walkable(Vec2::ZERO, &treads));
+    assert!(!heightmap.is_walkable(Vec2::new(-14.0, -13.5), &treads), "Ridge flanks are too steep for treads");
+    assert!(!heightmap.is_walkable(Vec2::new(30.0, 0.0), &treads), "Off the map is never walkable");
+}
+
+#[test]
+fn test_heightmap_needs_at_least_two_by_two_samples() {
+    // 1x1 8-bit grayscale PNG holding a single white pixel
+    let single_pixel: [u8; 67] = [
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x00, 0x00, 0x00, 0x00, 0x3a, 0x7e, 0x9b,
+        0x55, 0x00, 0x00, 0x00, 0x0a, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0xf8, 0x0f, 0x00, 0x01,
+        0x01, 0x01, 0x00, 0xb1, 0x38, 0xf6, 0x14, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae,
+        0x42, 0x60, 0x82,
+    ];
+    let image = Image::from_buffer(
+        &single_pixel,
+        bevy::render::texture::ImageType::Extension("png"),
+        bevy::render::texture::CompressedImageFormats::NONE,
+        false,
+        bevy::render::texture::ImageSampler::Default,
+    ).unwrap();
+    assert_eq!(image.size(), UVec2::ONE);
+
+    assert!(Heightmap::from_image(&image, Vec2::splat(10.0), 4.0).is_none());
+    assert!(Heightmap::from_png(&single_pixel, Vec2::splat(10.0), 4.0).is_none());
+}
+
+#[test]
+fn test_height_is_interpolated_between_samples_and_clamped_at_edges() {
+    let heightmap = Heightmap::new(2, 2, Vec2::splat(2.0), vec![0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(heightmap.height_at(Vec2::new(-1.0, -1.0)), 0.0);
+    assert_eq!(heightmap.height_at(Vec2::new(1.0, 1.0)), 3.0);
+    assert!((heightmap.height_at(Vec2::ZERO) - 1.5).abs() < 0.001);
+    assert!((heightmap.height_at(Vec2::new(0.5, -1.0)) - 0.75).abs() < 0.001);
+    assert_eq!(heightmap.height_at(Vec2::new(-5.0, 5.0)), 2.0);
+}
+
+#[test]
+fn test_slope_and_incline_follow_the_gradient() {
+    let heightmap = ramp(20.0);
+    assert!((heightmap.slope_at(Vec2::ZERO) - 20.0).abs() < 0.01);
+    assert!((heightmap.incline_along(Vec2::ZERO, Vec2::X) - 20.0).abs() < 0.01);
+    assert!((heightmap.incline_along(Vec2::ZERO, Vec2::NEG_X) + 20.0).abs() < 0.01);
+    assert!(heightmap.incline_along(Vec2::ZERO, Vec2::Y).abs() < 0.01);
+    assert!(heightmap.normal_at(Vec2::ZERO).x < 0.0, "Normals lean away from the rise");
+}
+
+#[test]
+fn test_slope_speed_factor_by_lower_body() {
+    let treads = create_tank_treads_lower().movement_stats;
+    assert_eq!(slope_speed_factor(-40.0, &treads), 1.0);
+    assert_eq!(slope_speed_factor(treads.climb_slope, &treads), 1.0);
+    let halfway = (treads.climb_slope + treads.max_slope) / 2.0;
+    assert!((slope_speed_factor(halfway, &treads) - (1.0 + MIN_SLOPE_SPEED_FACTOR) / 2.0).abs() < 0.001);
+    assert_eq!(slope_speed_factor(treads.max_slope, &treads), 0.0);
+}
+
+#[test]
+fn test_mesh_and_collider_match_the_heightmap() {
+    let heightmap = shipped_heightmap();
+    let mesh = heightmap.mesh();
+    let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+        panic!("Terrain mesh needs positions");
+    };
+    assert_eq!(positions.len(), 65 * 65);
+    assert_eq!(mesh.indices().unwrap().len(), 64 * 64 * 6);
+
+    let collider = heightmap.collider();
+    for &[x, y, z] in positions.iter().step_by(97) {
+        assert!((heightmap.height_at(Vec2::new(x, z)) - y).abs() < 0.001);
+        let toi = collider.cast_local_ray(Vec3::new(x, 10.0, z), Vec3::NEG_Y, 20.0, true).unwrap();
+        assert!((10.0 - toi - y).abs() < 0.01, "Collider height at ({}, {}) was {}, mesh has {}", x, z, 10.0 - toi, y);
+    }
+}
+
+#[test]
+fn test_ray_lands_on_the_terrain_surface() {
+    let heightmap = ramp(30.0);
+    let hit = heightmap.ray_intersection(Vec3::new(10.0, 40.0, 0.0), Vec3::NEG_Y).unwrap();
+    assert!((hit.y - heightmap.height_at(Vec2::new(10.0, 0.0))).abs() < 0.01);
+
+    // Looking down the slope at 45 degrees from above the ground at x = 0
+    let origin = Vec3::new(-10.0, 20.0, 0.0);
+    let hit = heightmap.ray_intersection(origin, Vec3::new(1.0, -1.0, 0.0)).unwrap();
+    assert!((hit.y - heightmap.height_at(Vec2::new(hit.x, hit.z))).abs() < 0.01);
+    assert!((hit.x + hit.y - (origin.x + origin.y)).abs() < 0.01, "Hit point should lie on the ray");
+    assert!(heightmap.ray_intersection(origin, Vec3::Y).is_none());
+}
+
+fn drive_up(heightmap: Heightmap, heading: Vec2) -> f32 {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(heightmap);
+    app.add_systems(Update, mech_movement_system);
+    let facing = heading.x.atan2(heading.y);
+    let mech = app.world.spawn((
+        Transform::from_rotation(Quat::from_rotation_y(facing)),
+        MechMovement::default(),
+        create_tank_treads_lower(),
+        MoveTarget { position: heading * 40.0 },
+    )).id();
+
+    for _ in 0..30 {
+        app.update();
+    }
+    let translation = app.world.get::<Transform>(mech).unwrap().translation;
+    Vec2::new(translation.x, translation.z).length()
+}
+
+#[test]
+fn test_slopes_slow_or_block_treads_but_not_downhill() {
+    let flat = drive_up(Heightmap::flat(Vec2::splat(100.0)), Vec2::X);
+    let moderate = drive_up(ramp(22.5), Vec2::X);
+    let steep = drive_up(ramp(35.0), Vec2::X);
+    let downhill = drive_up(ramp(35.0), Vec2::NEG_X);
+
+    assert!(flat > 5.0);
+    assert!(moderate > 1.0 && moderate < flat * 0.8, "Climbing should be slower: {} vs {}", moderate, flat);
+    assert!(steep < 0.01, "Treads can't climb past their max slope, moved {}", steep);
+    assert!((downhill - flat).abs() < 0.01);
+}
+
+#[test]
+fn test_units_follow_the_ground_at_their_clearance() {
+    let mut world = World::new();
+    world.insert_resource(ramp(30.0));
+    let enemy = world.spawn((GroundClearance(0.75), Transform::from_xyz(10.0, 0.0, 3.0))).id();
+    let scenery = world.spawn(Transform::from_xyz(10.0, 0.0, 3.0)).id();
+
+    world.run_system_once(terrain_follow_system);
+
+    let expected = 10.0 * 30.0_f32.to_radians().tan() + 0.75;
+    assert!((world.get::<Transform>(enemy).unwrap().translation.y - expected).abs() < 0.001);
+    assert_eq!(world.get::<Transform>(scenery).unwrap().translation.y, 0.0);
+}
+
+#[test]
+fn test_picking_through_terrain_records_ground_but_hovers_nothing() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<Scene>::default());
+    app.init_resource::<bevy::scene::SceneSpawner>();
+    app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
+    app.init_resource::<PickingState>();
+    app.add_systems(Update, entity_picking_system);
+
+    let heightmap = ramp(30.0);
+    app.world.spawn((Terrain, TransformBundle::default(), RigidBody::Fixed, heightmap.collider(), TERRAIN_COLLISION_GROUPS));
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(-20.0, 0.0, 0.0)), Collider::ball(0.5))).id();
+    app.update();
+
+    app.world.resource_mut::<PickingState>().ray = Some(Ray { origin: Vec3::new(10.0, 50.0, 0.0), direction: Vec3::NEG_Y });
+    app.update();
+    let picking = app.world.resource::<PickingState>();
+    assert_eq!(picking.hovered, None);
+    assert!((picking.hit_point.unwrap().y - heightmap.height_at(Vec2::new(10.0, 0.0))).abs() < 0.01);
+
+    // Units standing on the terrain are still picked first
+    app.world.resource_mut::<PickingState>().ray = Some(Ray { origin: Vec3::new(-20.0, 50.0, 0.0), direction: Vec3::NEG_Y });
+    app.update();
+    assert_eq!(app.world.resource::<PickingState>().hovered, Some(enemy));
+}
+
+#[test]
+fn test_terrain_stops_projectiles_but_not_units() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<Scene>::default());
+    app.init_resource::<bevy::scene::SceneSpawner>();
+    app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
+
+    let heightmap = Heightmap::flat(Vec2::splat(20.0));
+    app.world.spawn((Terrain, TransformBundle::default(), RigidBody::Fixed, heightmap.collider(), TERRAIN_COLLISION_GROUPS));
+    // Shells keep the default groups
+    let shell = app.world.spawn((
+        TransformBundle::from_transform(Transform::from_xyz(-2.0, 0.5, 0.0)),
+        RigidBody::Dynamic,
+        Collider::ball(0.2),
+    )).id();
+    let unit = app.world.spawn((
+        TransformBundle::from_transform(Transform::from_xyz(2.0, 0.5, 0.0)),
+        RigidBody::Dynamic,
+        Collider::ball(0.2),
+        UNIT_COLLISION_GROUPS,
+    )).id();
+
+    for _ in 0..60 {
+        app.update();
+    }
+    let shell_height = app.world.get::<Transform>(shell).unwrap().translation.y;
+    let unit_height = app.world.get::<Transform>(unit).unwrap().translation.y;
+    assert!((shell_height - 0.2).abs() < 0.05, "Shell should rest on the ground, was at {}", shell_height);
+    assert!(unit_height < -0.5, "Units should fall through the terrain collider, was at {}", unit_height);
+}
+
+#[test]
+fn test_move_orders_to_ground_too_steep_are_refused() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(Input::<MouseButton>::default());
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.init_resource::<PickingState>();
+    app.insert_resource(shipped_heightmap());
+    app.add_systems(Update, click_to_move_system);
+    let hero = app.world.spawn((Hero, TransformBundle::default(), create_tank_treads_lower())).id();
+
+    for (target, accepted) in [(Vec2::new(-14.0, -13.5), false), (Vec2::new(30.0, 0.0), false), (Vec2::new(2.0, 5.0), true)] {
+        app.world.insert_resource(MouseWorldPosition { position: target });
+        let mut mouse = app.world.resource_mut::<Input<MouseButton>>();
+        mouse.clear();
+        mouse.release(MouseButton::Left);
+        mouse.press(MouseButton::Left);
+        app.update();
+        let order = app.world.get::<MoveTarget>(hero).map(|order| order.position);
+        assert_eq!(order == Some(target), accepted, "Order to {:?}", target);
+    }
+}
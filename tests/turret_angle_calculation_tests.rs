@@ -1,8 +1,17 @@
 use bevy::prelude::*;
+use rust_and_ruin::systems::angles::*;
 use rust_and_ruin::systems::turret_control::*;
 use rust_and_ruin::mech::*;
 use rust_and_ruin::components::*;
 use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::upper_body_control::*;
+
+/// Full-traverse turret upper body turning at `rotation_speed` degrees per second.
+fn turret_upper(rotation_speed: f32) -> MechUpperBody {
+    let mut upper = create_turret_upper();
+    upper.rotation_capability.rotation_speed = rotation_speed;
+    upper
+}
 
 #[test]
 fn test_calculate_turret_angle_basic() {
@@ -272,7 +281,7 @@ fn test_turret_control_simple() {
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create parent at origin with NO rotation
@@ -286,16 +295,11 @@ fn test_turret_control_simple() {
     let turret_entity = app.world.spawn((
         Transform::default(),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 0.0,
             target_angle: 0.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 3600.0,
-            barrel_length: 2.0,
-        },
+        turret_upper(3600.0),
         MechUpperPart,
     )).id();
     
@@ -315,7 +319,7 @@ fn test_turret_control_simple() {
     let mut reached_target = false;
     for i in 0..2000 {
         app.update();
-        let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+        let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
         
         if (turret_rotation.current_angle - 90.0).abs() < 1.0 {
             println!("Turret reached target at update {}: current_angle = {}°", i, turret_rotation.current_angle);
@@ -328,7 +332,7 @@ fn test_turret_control_simple() {
         }
     }
     
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     println!("Final: current_angle = {}°, target_angle = {}°", 
              turret_rotation.current_angle, turret_rotation.target_angle);
     
@@ -359,7 +363,7 @@ fn test_turret_system_integration() {
     // Add the turret control system
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create parent (tank chassis) at origin, rotated 45 degrees
@@ -377,16 +381,11 @@ fn test_turret_system_integration() {
     let turret_entity = app.world.spawn((
         Transform::default(), // Local transform starts at identity
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 0.0,
             target_angle: 0.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 3600.0, // Very fast for testing
-            barrel_length: 2.0,
-        },
+        turret_upper(3600.0), // Very fast for testing
         MechUpperPart,
     )).id();
     
@@ -411,7 +410,7 @@ fn test_turret_system_integration() {
     println!("  Parent has Hero: {}", app.world.get::<Hero>(parent_entity).is_some());
     println!("  Parent has AttackTarget: {}", app.world.get::<AttackTarget>(parent_entity).is_some());
     println!("  Turret has MechUpperPart: {}", app.world.get::<MechUpperPart>(turret_entity).is_some());
-    println!("  Turret has TurretRotation: {}", app.world.get::<TurretRotation>(turret_entity).is_some());
+    println!("  Turret has MechRotation: {}", app.world.get::<MechRotation>(turret_entity).is_some());
     println!("  Turret parent: {:?}", app.world.get::<Parent>(turret_entity).map(|p| p.get()));
     
     // Check positions
@@ -424,7 +423,7 @@ fn test_turret_system_integration() {
     let (parent_pos, parent_rot_deg, turret_before_info) = {
         let parent_transform = app.world.get::<Transform>(parent_entity).unwrap();
         let turret_transform_before = app.world.get::<Transform>(turret_entity).unwrap();
-        let turret_rotation_before = app.world.get::<TurretRotation>(turret_entity).unwrap();
+        let turret_rotation_before = app.world.get::<MechRotation>(turret_entity).unwrap();
         
         let (y, x, z) = parent_transform.rotation.to_euler(EulerRot::YXZ);
         println!("  Parent euler angles (YXZ): y={:.2}°, x={:.2}°, z={:.2}°", 
@@ -452,7 +451,7 @@ fn test_turret_system_integration() {
     for i in 0..2000 {
         app.update();
         if i % 400 == 0 {
-            let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+            let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
             println!("  Update {}: current_angle = {}°, target_angle = {}°", 
                      i, turret_rotation.current_angle, turret_rotation.target_angle);
         }
@@ -460,7 +459,7 @@ fn test_turret_system_integration() {
     
     // Check state after turret control
     let turret_transform_after = app.world.get::<Transform>(turret_entity).unwrap();
-    let turret_rotation_after = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation_after = app.world.get::<MechRotation>(turret_entity).unwrap();
     let turret_global_after = app.world.get::<GlobalTransform>(turret_entity).unwrap();
     
     println!("\nAfter turret control:");
@@ -4,6 +4,14 @@ use rust_and_ruin::mech::*;
 use rust_and_ruin::components::*;
 use rust_and_ruin::systems::turret_control::*;
 use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::upper_body_control::*;
+
+/// Full-traverse turret upper body turning at `rotation_speed` degrees per second.
+fn turret_upper(rotation_speed: f32) -> MechUpperBody {
+    let mut upper = create_turret_upper();
+    upper.rotation_capability.rotation_speed = rotation_speed;
+    upper
+}
 
 #[test]
 fn test_turret_faces_enemy_correctly() {
@@ -18,7 +26,7 @@ fn test_turret_faces_enemy_correctly() {
     app.insert_resource(Time::<()>::default());
     
     // Add the turret control system
-    app.add_systems(Update, turret_control_system);
+    app.add_systems(Update, upper_body_control_system);
     
     // Create a mech with turret at origin
     let mech_entity = app.world.spawn((
@@ -30,16 +38,11 @@ fn test_turret_faces_enemy_correctly() {
     let turret_entity = app.world.spawn((
         Transform::from_rotation(Quat::from_rotation_y(0.0)),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 0.0,
             target_angle: 0.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 360.0, // Very fast for testing
-            barrel_length: 2.0,
-        },
+        turret_upper(360.0), // Very fast for testing
     )).id();
     
     // Set up parent-child relationship
@@ -60,20 +63,20 @@ fn test_turret_faces_enemy_correctly() {
     
     // Get the target angle and set current angle to match (simulating instant rotation)
     {
-        let mut turret_rotation = app.world.get_mut::<TurretRotation>(turret_entity).unwrap();
+        let mut turret_rotation = app.world.get_mut::<MechRotation>(turret_entity).unwrap();
         turret_rotation.current_angle = turret_rotation.target_angle;
     }
     
     // Apply the rotation to the transform
     {
-        let current_angle_radians = app.world.get::<TurretRotation>(turret_entity).unwrap().current_angle.to_radians();
+        let current_angle_radians = app.world.get::<MechRotation>(turret_entity).unwrap().current_angle.to_radians();
         let mut turret_transform = app.world.get_mut::<Transform>(turret_entity).unwrap();
         turret_transform.rotation = Quat::from_rotation_y(current_angle_radians);
     }
     
     // Check turret rotation
     let turret_transform = app.world.get::<Transform>(turret_entity).unwrap();
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     
     // Get the forward direction of the turret
     let forward = turret_transform.rotation * Vec3::Z;
@@ -134,7 +137,7 @@ fn test_turret_rotation_for_all_directions() {
         
         app.insert_resource(MouseWorldPosition { position: Vec2::ZERO });
         app.insert_resource(Time::<()>::default());
-        app.add_systems(Update, turret_control_system);
+        app.add_systems(Update, upper_body_control_system);
         
         // Create mech at origin
         let mech_entity = app.world.spawn((
@@ -146,16 +149,11 @@ fn test_turret_rotation_for_all_directions() {
         let turret_entity = app.world.spawn((
             Transform::from_rotation(Quat::from_rotation_y(0.0)),
             GlobalTransform::default(),
-            TurretRotation {
+            MechRotation {
                 current_angle: 0.0,
                 target_angle: 0.0,
             },
-            TurretCannon {
-                fire_rate: 1.0,
-                projectile_damage: 10.0,
-                rotation_speed: 360.0,
-                barrel_length: 2.0,
-            },
+            turret_upper(360.0),
         )).id();
         
         app.world.entity_mut(turret_entity).set_parent(mech_entity);
@@ -172,7 +170,7 @@ fn test_turret_rotation_for_all_directions() {
         // Run frame
         app.update();
         
-        let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+        let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
         
         println!("{}: target angle = {}", test_case.description, turret_rotation.target_angle);
         
@@ -243,7 +241,7 @@ fn test_turret_forward_direction_calculation() {
 fn test_auto_fire_system(
     mut commands: Commands,
     hero_query: Query<(&Transform, &Children, &AttackTarget), With<Hero>>,
-    upper_query: Query<(&Transform, &TurretCannon, &TurretRotation, Option<&Children>), With<MechUpperPart>>,
+    upper_query: Query<(&Transform, &TurretCannon, &MechRotation, Option<&Children>), With<MechUpperPart>>,
     enemy_query: Query<&Transform, With<Enemy>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
@@ -395,7 +393,7 @@ fn test_turret_only_fires_when_facing_target() {
             Transform::from_rotation(Quat::from_rotation_y(test_case.turret_angle.to_radians())),
             GlobalTransform::default(),
             MechUpperPart,
-            TurretRotation {
+            MechRotation {
                 current_angle: test_case.turret_angle,
                 target_angle: test_case.turret_angle, // Simulating turret at rest
             },
@@ -458,8 +456,8 @@ fn test_turret_only_fires_when_facing_target() {
         if app.world.get::<TurretCannon>(turret_entity).is_some() {
             println!("Turret has TurretCannon component");
         }
-        if app.world.get::<TurretRotation>(turret_entity).is_some() {
-            println!("Turret has TurretRotation component");
+        if app.world.get::<MechRotation>(turret_entity).is_some() {
+            println!("Turret has MechRotation component");
         }
         if app.world.get::<Children>(turret_entity).is_some() {
             println!("Turret has Children component");
@@ -1,10 +1,19 @@
 use bevy::prelude::*;
-use rust_and_ruin::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
 use rust_and_ruin::mech::*;
 use rust_and_ruin::components::*;
-use rust_and_ruin::systems::turret_control::*;
 use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::angles::shortest_angle_difference;
 use rust_and_ruin::systems::attack_target_propagation::*;
+use rust_and_ruin::systems::upper_body_control::*;
+
+/// Full-traverse turret upper body turning at `rotation_speed` degrees per second.
+fn turret_upper(rotation_speed: f32) -> MechUpperBody {
+    let mut upper = create_turret_upper();
+    upper.rotation_capability.rotation_speed = rotation_speed;
+    upper
+}
 
 /// Test that turret control works with nested hierarchy (Hero -> tank_base -> turret)
 #[test]
@@ -16,7 +25,7 @@ fn test_turret_control_with_nested_hierarchy() {
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create hero entity with AttackTarget
@@ -37,16 +46,11 @@ fn test_turret_control_with_nested_hierarchy() {
     let turret_entity = app.world.spawn((
         Transform::from_rotation(Quat::from_rotation_y(180.0_f32.to_radians())),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 180.0,
             target_angle: 180.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 360.0, // Fast rotation for testing
-            barrel_length: 2.0,
-        },
+        turret_upper(360.0), // Fast rotation for testing
         MechUpperPart,
     )).id();
     
@@ -67,7 +71,7 @@ fn test_turret_control_with_nested_hierarchy() {
     // Run update - turret should NOT rotate because AttackTarget is on grandparent
     app.update();
     
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     
     // Without propagation, turret won't find the AttackTarget
     // It will maintain its current angle by calculating a maintain position
@@ -90,7 +94,7 @@ fn test_turret_control_with_immediate_parent_attack_target() {
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create hero entity
@@ -111,16 +115,11 @@ fn test_turret_control_with_immediate_parent_attack_target() {
     let turret_entity = app.world.spawn((
         Transform::from_rotation(Quat::from_rotation_y(180.0_f32.to_radians())),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 180.0,
             target_angle: 180.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 360.0, // Fast rotation for testing
-            barrel_length: 2.0,
-        },
+        turret_upper(360.0), // Fast rotation for testing
         MechUpperPart,
     )).id();
     
@@ -141,7 +140,7 @@ fn test_turret_control_with_immediate_parent_attack_target() {
     // Run update - turret SHOULD rotate because AttackTarget is on immediate parent
     app.update();
     
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     
     // With AttackTarget on immediate parent, turret should start rotating
     assert_eq!(turret_rotation.target_angle, 0.0, 
@@ -161,7 +160,8 @@ fn test_turret_control_with_attack_target_propagation() {
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
         propagate_attack_target_system,
-        turret_control_system,
+        apply_deferred,
+        upper_body_control_system,
     ).chain());
     
     // Create hero entity with AttackTarget
@@ -182,16 +182,11 @@ fn test_turret_control_with_attack_target_propagation() {
     let turret_entity = app.world.spawn((
         Transform::from_rotation(Quat::from_rotation_y(180.0_f32.to_radians())),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 180.0,
             target_angle: 180.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 360.0, // Fast rotation for testing
-            barrel_length: 2.0,
-        },
+        turret_upper(360.0), // Fast rotation for testing
         MechUpperPart,
     )).id();
     
@@ -216,7 +211,7 @@ fn test_turret_control_with_attack_target_propagation() {
     let tank_has_target = app.world.get::<AttackTarget>(tank_base).is_some();
     assert!(tank_has_target, "AttackTarget should be propagated to tank_base");
     
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     
     // With propagation, turret should now target the enemy
     // In this coordinate system with atan2(x, y), +X direction is 90°
@@ -224,18 +219,18 @@ fn test_turret_control_with_attack_target_propagation() {
         "Turret should target right (+X = 90 degrees) with AttackTarget propagation");
 }
 
-/// Test that turret maintains tracking when tank rotates
+/// Test that turret maintains tracking when tank rotates.
+/// The upper body hangs directly off the root, as in a `MechHierarchy` mech, so it turns against the hull.
 #[test]
 fn test_turret_tracking_during_tank_rotation() {
     let mut app = App::new();
     app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
     app.insert_resource(MouseWorldPosition { position: Vec2::ZERO });
-    app.insert_resource(Time::<()>::default());
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        propagate_attack_target_system,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create hero entity at origin
@@ -252,26 +247,20 @@ fn test_turret_tracking_during_tank_rotation() {
         MechLowerPart,
     )).id();
     
-    // Create turret as child of tank_base, initially facing forward (0°)
+    // Create turret as a sibling of tank_base, initially facing forward (0°)
     let turret_entity = app.world.spawn((
-        Transform::default(),
+        Transform::from_xyz(0.0, 0.75, 0.0),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 0.0,
             target_angle: 0.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 360.0, // Fast rotation for testing
-            barrel_length: 2.0,
-        },
+        turret_upper(3600.0), // Reaches its target within a frame
         MechUpperPart,
     )).id();
     
-    // Set up hierarchy: hero -> tank_base -> turret
-    app.world.entity_mut(hero_entity).push_children(&[tank_base]);
-    app.world.entity_mut(tank_base).push_children(&[turret_entity]);
+    // Set up hierarchy: hero -> (tank_base, turret)
+    app.world.entity_mut(hero_entity).push_children(&[tank_base, turret_entity]);
     
     // Create enemy to the right (+X direction)
     let enemy_entity = app.world.spawn((
@@ -283,31 +272,29 @@ fn test_turret_tracking_during_tank_rotation() {
     // Add AttackTarget to hero
     app.world.entity_mut(hero_entity).insert(AttackTarget { entity: enemy_entity });
     
-    // Update once to establish tracking
+    // Update to establish tracking; the first frame has no elapsed time
+    app.update();
     app.update();
     
     // Turret should be targeting right initially
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
-    println!("Initial turret target angle: {}", turret_rotation.target_angle);
-    // In this coordinate system, enemy at +X seems to be 90°, not 270°
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     assert_eq!(turret_rotation.target_angle, 90.0, 
         "Turret should initially target right (90°)");
+    assert!((turret_rotation.current_angle - 90.0).abs() < 0.01,
+        "Turret should have turned onto the target, got {}", turret_rotation.current_angle);
     
     // Now rotate the tank 90° counter-clockwise
     let mut hero_transform = app.world.get_mut::<Transform>(hero_entity).unwrap();
     hero_transform.rotation = Quat::from_rotation_y(90.0_f32.to_radians());
     
-    // Update to propagate transforms and recalculate turret angle
     app.update();
     
-    // After tank rotates 90° CCW, the enemy (still at +X) is now to the tank's right in local space
     // World angle to enemy is still 90°, but tank is facing 90°, so local angle should be 0°
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
-    println!("After 90° tank rotation: current_angle={}, target_angle={}", 
-        turret_rotation.current_angle, turret_rotation.target_angle);
     // current_angle stores the local angle, target_angle stores the world angle
-    assert_eq!(turret_rotation.current_angle, 0.0, 
-        "After tank rotates 90° CCW, turret should face forward (0°) in local space");
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
+    assert_eq!(turret_rotation.target_angle, 90.0);
+    assert!(shortest_angle_difference(turret_rotation.current_angle, 0.0).abs() < 0.01,
+        "After tank rotates 90° CCW, turret should face forward (0°) in local space, got {}", turret_rotation.current_angle);
     
     // Rotate tank another 90° CCW (total 180°)
     let mut hero_transform = app.world.get_mut::<Transform>(hero_entity).unwrap();
@@ -315,18 +302,8 @@ fn test_turret_tracking_during_tank_rotation() {
     
     app.update();
     
-    // After tank rotates 180°, enemy (at world +X) is now to the tank's left in local space  
     // World angle to enemy is 90°, tank is facing 180°, so local angle = 90° - 180° = -90° = 270°
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
-    println!("After 180° tank rotation: current_angle={}, target_angle={}", 
-        turret_rotation.current_angle, turret_rotation.target_angle);
-    // Account for floating point precision and angle normalization
-    let expected_angle = 270.0;
-    let actual_angle = if turret_rotation.current_angle < 0.0 {
-        turret_rotation.current_angle + 360.0
-    } else {
-        turret_rotation.current_angle
-    };
-    assert!((actual_angle - expected_angle).abs() < 1.0, 
-        "After tank rotates 180°, turret should face left (270°) in local space, got {}", actual_angle);
-}
\ No newline at end of file
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
+    assert!(shortest_angle_difference(turret_rotation.current_angle, 270.0).abs() < 0.01,
+        "After tank rotates 180°, turret should face left (270°) in local space, got {}", turret_rotation.current_angle);
+}
@@ -0,0 +1,185 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::resources::MouseWorldPosition;
+use rust_and_ruin::systems::*;
+
+fn create_turret_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.init_resource::<MouseWorldPosition>();
+    app
+}
+
+#[test]
+fn test_traverse_edges_are_inclusive() {
+    let limits = TurretLimits::casemate(30.0);
+
+    assert!(limits.is_within_traverse(30.0));
+    assert!(limits.is_within_traverse(-30.0));
+    assert!(!limits.is_within_traverse(30.01));
+    assert!(!limits.is_within_traverse(-179.9));
+    assert_eq!(limits.clamp_traverse(45.0), 30.0);
+    assert_eq!(limits.clamp_traverse(-180.0), -30.0);
+
+    assert!(TurretLimits::default().is_within_traverse(180.0));
+}
+
+#[test]
+fn test_limited_turret_stops_at_arc_edge() {
+    let limits = TurretLimits::casemate(30.0);
+
+    // Target just past the right edge is held at +30
+    let angle = rotate_within_traverse(25.0, 31.0, &limits, 90.0, 1.0);
+    assert!((angle - 30.0).abs() < 0.001, "got {}", angle);
+
+    // Target at 330 (-30) is reachable exactly
+    let angle = rotate_within_traverse(0.0, 330.0, &limits, 90.0, 1.0);
+    assert!((angle - 330.0).abs() < 0.001, "got {}", angle);
+}
+
+#[test]
+fn test_limited_turret_never_sweeps_through_rear() {
+    let limits = TurretLimits::casemate(170.0);
+
+    // From +160 to -160 the short way is through 180, which is outside the arc
+    let angle = rotate_within_traverse(160.0, 200.0, &limits, 10.0, 1.0);
+    assert!((angle - 150.0).abs() < 0.001, "Should turn back through the front, got {}", angle);
+
+    // Unlimited turrets still take the short way
+    let angle = rotate_within_traverse(160.0, 200.0, &TurretLimits::default(), 10.0, 1.0);
+    assert!((angle - 170.0).abs() < 0.001, "got {}", angle);
+}
+
+#[test]
+fn test_elevation_limits() {
+    let limits = TurretLimits::default();
+    let elevation = required_elevation(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 1.0 + 10.0, 10.0));
+    assert!((elevation - 45.0).abs() < 0.001);
+    assert!(limits.is_within_elevation(elevation));
+
+    let depression = required_elevation(Vec3::new(0.0, 5.0, 0.0), Vec3::new(3.0, 0.0, 0.0));
+    assert!(!limits.is_within_elevation(depression), "{} is below the depression limit", depression);
+    assert_eq!(limits.clamp_elevation(depression), limits.min_elevation);
+}
+
+fn spawn_casemate_hero(app: &mut App, enemy_position: Vec3) -> (Entity, Entity) {
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_translation(enemy_position)))).id();
+    let hero = app.world.spawn((
+        Hero,
+        MechMovement::default(),
+        AttackTarget { entity: enemy },
+        TransformBundle::default(),
+    )).id();
+    let upper = app.world.spawn((
+        MechUpperPart,
+        create_casemate_upper(),
+        MechRotation { target_angle: 0.0, current_angle: 0.0 },
+        TransformBundle::default(),
+    )).id();
+    app.world.entity_mut(hero).push_children(&[upper]);
+    (hero, upper)
+}
+
+#[test]
+fn test_upper_body_control_clamps_to_traverse() {
+    let mut app = create_turret_app();
+    app.add_systems(Update, upper_body_control_system);
+
+    // Enemy 90° to the right of the hull
+    let (_, upper) = spawn_casemate_hero(&mut app, Vec3::new(10.0, 0.0, 0.0));
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let angle = app.world.get::<MechRotation>(upper).unwrap().current_angle;
+    assert!((angle - 30.0).abs() < 0.001, "Turret should rest on the arc edge, got {}", angle);
+}
+
+#[test]
+fn test_hull_turns_when_target_leaves_arc() {
+    let mut app = create_turret_app();
+    app.add_systems(Update, traverse_hull_to_target_system);
+
+    let (hero, _) = spawn_casemate_hero(&mut app, Vec3::new(10.0, 0.0, 0.0));
+    app.update();
+
+    let movement = app.world.get::<MechMovement>(hero).unwrap();
+    assert_eq!(movement.movement_state, MechMovementState::Rotating);
+    assert!((movement.target_rotation - 90.0).abs() < 0.001);
+}
+
+#[test]
+fn test_hull_stays_put_inside_arc_or_while_moving() {
+    let mut app = create_turret_app();
+    app.add_systems(Update, traverse_hull_to_target_system);
+
+    // Just inside the 30° arc
+    let inside = Vec3::new(29.5f32.to_radians().sin(), 0.0, 29.5f32.to_radians().cos()) * 10.0;
+    let (hero, _) = spawn_casemate_hero(&mut app, inside);
+    app.update();
+    assert_eq!(app.world.get::<MechMovement>(hero).unwrap().movement_state, MechMovementState::Idle);
+
+    // Movement orders are not overridden
+    let (moving_hero, _) = spawn_casemate_hero(&mut app, Vec3::new(-10.0, 0.0, 0.0));
+    app.world.entity_mut(moving_hero).insert(MoveTarget { position: Vec2::new(0.0, 5.0) });
+    app.update();
+    assert_eq!(app.world.get::<MechMovement>(moving_hero).unwrap().movement_state, MechMovementState::Idle);
+}
+
+#[test]
+fn test_upper_body_control_honours_casemate_limits() {
+    let mut app = create_turret_app();
+    app.add_systems(Update, upper_body_control_system);
+
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(-10.0, 0.0, -1.0)))).id();
+    let mech = app.world.spawn((AttackTarget { entity: enemy }, TransformBundle::default())).id();
+    let upper = app.world.spawn((
+        create_casemate_upper(),
+        MechRotation { target_angle: 0.0, current_angle: 0.0 },
+        TransformBundle::default(),
+    )).id();
+    app.world.entity_mut(mech).push_children(&[upper]);
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let angle = app.world.get::<MechRotation>(upper).unwrap().current_angle;
+    assert!((angle - 330.0).abs() < 0.001, "Upper should rest on the -30° edge, got {}", angle);
+}
+
+#[test]
+fn test_weapons_pitch_onto_target_within_elevation_limits() {
+    let mut app = create_turret_app();
+    app.add_systems(Update, weapon_elevation_system);
+
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(0.0, 10.0, 10.0)))).id();
+    let mech = app.world.spawn((AttackTarget { entity: enemy }, TransformBundle::default())).id();
+    let upper = app.world.spawn((create_turret_upper(), TransformBundle::default())).id();
+    let weapon = app.world.spawn((create_cannon_weapon("main".to_string()), TransformBundle::default())).id();
+    app.world.entity_mut(mech).push_children(&[upper]);
+    app.world.entity_mut(upper).push_children(&[weapon]);
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let barrel_forward = app.world.get::<Transform>(weapon).unwrap().rotation * Vec3::Z;
+    let pitch = barrel_forward.y.atan2(barrel_forward.z).to_degrees();
+    assert!((pitch - 45.0).abs() < 0.01, "Barrel should pitch up to the 45° target, got {}", pitch);
+
+    // Targets below the depression limit leave the barrel on the limit
+    app.world.get_mut::<Transform>(enemy).unwrap().translation = Vec3::new(0.0, -10.0, 10.0);
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let barrel_forward = app.world.get::<Transform>(weapon).unwrap().rotation * Vec3::Z;
+    let pitch = barrel_forward.y.atan2(barrel_forward.z).to_degrees();
+    let limits = TurretLimits::default();
+    assert!((pitch - limits.min_elevation).abs() < 0.01, "Barrel should stop at {}, got {}", limits.min_elevation, pitch);
+}
@@ -1,11 +1,24 @@
 use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
 use rust_and_ruin::*;
 use rust_and_ruin::mech::*;
 use rust_and_ruin::components::*;
+use rust_and_ruin::systems::angles::*;
 use rust_and_ruin::systems::turret_control::*;
 use rust_and_ruin::systems::movement::*;
 use rust_and_ruin::systems::input::*;
+use rust_and_ruin::systems::picking::SelectionRingAssets;
+use rust_and_ruin::systems::spatial_index::*;
 use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::upper_body_control::*;
+
+/// Full-traverse turret upper body turning at `rotation_speed` degrees per second.
+fn turret_upper(rotation_speed: f32) -> MechUpperBody {
+    let mut upper = create_turret_upper();
+    upper.rotation_capability.rotation_speed = rotation_speed;
+    upper
+}
 
 #[test]
 fn test_q_key_sets_attack_target() {
@@ -14,9 +27,13 @@ fn test_q_key_sets_attack_target() {
     app.insert_resource(MouseWorldPosition { position: Vec2::new(5.0, 0.0) }); // Near enemy
     app.insert_resource(Input::<KeyCode>::default());
     app.insert_resource(Input::<MouseButton>::default());
+    app.init_resource::<PickingState>();
     app.insert_resource(Assets::<Mesh>::default());
     app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<SpatialIndex>();
     
+    app.add_systems(PreUpdate, spatial_index_system);
+    app.init_resource::<SelectionRingAssets>();
     app.add_systems(Update, enemy_selection_system);
     
     // Create hero without attack target
@@ -56,13 +73,17 @@ fn test_turret_rotates_to_face_target_after_q() {
     app.insert_resource(Time::<()>::default());
     app.insert_resource(Input::<KeyCode>::default());
     app.insert_resource(Input::<MouseButton>::default());
+    app.init_resource::<PickingState>();
     app.insert_resource(Assets::<Mesh>::default());
     app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<SpatialIndex>();
     
+    app.add_systems(PreUpdate, spatial_index_system);
+    app.init_resource::<SelectionRingAssets>();
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
         enemy_selection_system,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create mech with turret facing backward
@@ -75,16 +96,11 @@ fn test_turret_rotates_to_face_target_after_q() {
     let turret_entity = app.world.spawn((
         Transform::from_rotation(Quat::from_rotation_y(180.0_f32.to_radians())),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 180.0,
             target_angle: 180.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 360.0, // Fast for testing
-            barrel_length: 2.0,
-        },
+        turret_upper(360.0), // Fast for testing
     )).id();
     
     app.world.entity_mut(turret_entity).set_parent(mech_entity);
@@ -105,7 +121,7 @@ fn test_turret_rotates_to_face_target_after_q() {
     }
     
     // Check turret rotation
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     
     // Turret should be aiming right (90 degrees) at the enemy
     assert!(
@@ -127,11 +143,12 @@ fn test_turret_maintains_lock_while_tank_moves() {
     app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
     app.insert_resource(MouseWorldPosition { position: Vec2::ZERO });
     app.insert_resource(Time::<()>::default());
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
         movement_system,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create mech at origin
@@ -145,16 +162,11 @@ fn test_turret_maintains_lock_while_tank_moves() {
     let turret_entity = app.world.spawn((
         Transform::default(),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 0.0,
             target_angle: 0.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 3600.0, // Very fast for testing
-            barrel_length: 2.0,
-        },
+        turret_upper(3600.0), // Very fast for testing
     )).id();
     
     app.world.entity_mut(turret_entity).set_parent(mech_entity);
@@ -196,7 +208,7 @@ fn test_turret_maintains_lock_while_tank_moves() {
         
         // Get current positions
         let mech_transform = app.world.get::<Transform>(mech_entity).unwrap();
-        let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+        let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
         
         let mech_pos = Vec2::new(mech_transform.translation.x, mech_transform.translation.z);
         let enemy_pos = Vec2::new(10.0, 10.0); // Fixed enemy position
@@ -206,7 +218,7 @@ fn test_turret_maintains_lock_while_tank_moves() {
         let expected_angle_normalized = normalize_angle(expected_angle);
         
         // Account for parent rotation
-        let parent_rotation_degrees = normalize_angle(mech_transform.rotation.to_euler(EulerRot::YXZ).1.to_degrees());
+        let parent_rotation_degrees = normalize_angle(mech_transform.rotation.to_euler(EulerRot::YXZ).0.to_degrees());
         let expected_local_angle = normalize_angle(expected_angle_normalized - parent_rotation_degrees);
         
         let angle_diff = shortest_angle_difference(turret_rotation.current_angle, expected_local_angle).abs();
@@ -229,10 +241,11 @@ fn test_turret_compensates_for_chassis_rotation_with_lock() {
     app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
     app.insert_resource(MouseWorldPosition { position: Vec2::ZERO });
     app.insert_resource(Time::<()>::default());
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Fixed enemy position
@@ -259,16 +272,11 @@ fn test_turret_compensates_for_chassis_rotation_with_lock() {
         let turret_entity = app.world.spawn((
             Transform::default(),
             GlobalTransform::default(),
-            TurretRotation {
+            MechRotation {
                 current_angle: 0.0,
                 target_angle: 0.0,
             },
-            TurretCannon {
-                fire_rate: 1.0,
-                projectile_damage: 10.0,
-                rotation_speed: 360.0,
-                barrel_length: 2.0,
-            },
+            turret_upper(360.0),
         )).id();
         
         app.world.entity_mut(turret_entity).set_parent(mech_entity);
@@ -311,11 +319,12 @@ fn test_turret_tracks_during_circular_movement() {
     app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
     app.insert_resource(MouseWorldPosition { position: Vec2::ZERO });
     app.insert_resource(Time::<()>::default());
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
         movement_system,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create mech
@@ -329,16 +338,11 @@ fn test_turret_tracks_during_circular_movement() {
     let turret_entity = app.world.spawn((
         Transform::default(),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 0.0,
             target_angle: 0.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 3600.0, // Very fast for testing
-            barrel_length: 2.0,
-        },
+        turret_upper(3600.0), // Very fast for testing
     )).id();
     
     app.world.entity_mut(turret_entity).set_parent(mech_entity);
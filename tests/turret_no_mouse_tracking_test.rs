@@ -1,8 +1,15 @@
 use bevy::prelude::*;
 use rust_and_ruin::mech::*;
 use rust_and_ruin::components::*;
-use rust_and_ruin::systems::turret_control::*;
 use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::upper_body_control::*;
+
+/// Full-traverse turret upper body turning at `rotation_speed` degrees per second.
+fn turret_upper(rotation_speed: f32) -> MechUpperBody {
+    let mut upper = create_turret_upper();
+    upper.rotation_capability.rotation_speed = rotation_speed;
+    upper
+}
 
 /// Test that turret does not track mouse when there's no AttackTarget
 #[test]
@@ -13,7 +20,7 @@ fn test_turret_ignores_mouse_without_attack_target() {
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create mech at origin
@@ -27,16 +34,11 @@ fn test_turret_ignores_mouse_without_attack_target() {
     let turret_entity = app.world.spawn((
         Transform::default(),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 0.0,
             target_angle: 0.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 180.0,
-            barrel_length: 2.0,
-        },
+        turret_upper(180.0),
         MechUpperPart,
     )).id();
     
@@ -51,7 +53,7 @@ fn test_turret_ignores_mouse_without_attack_target() {
     }
     
     // Check that turret hasn't moved
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     assert_eq!(
         turret_rotation.current_angle, 0.0,
         "Turret should maintain its angle when there's no AttackTarget"
@@ -66,7 +68,7 @@ fn test_turret_ignores_mouse_without_attack_target() {
     }
     
     // Turret should still not have moved
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     assert_eq!(
         turret_rotation.current_angle, 0.0,
         "Turret should continue to maintain its angle regardless of mouse movement"
@@ -83,7 +85,7 @@ fn test_turret_maintains_angle_when_target_removed() {
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create mech
@@ -96,16 +98,11 @@ fn test_turret_maintains_angle_when_target_removed() {
     let turret_entity = app.world.spawn((
         Transform::default(),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 0.0,
             target_angle: 0.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 360.0, // Fast rotation for test
-            barrel_length: 2.0,
-        },
+        turret_upper(360.0), // Fast rotation for test
         MechUpperPart,
     )).id();
     
@@ -125,7 +122,7 @@ fn test_turret_maintains_angle_when_target_removed() {
     app.update();
     
     // Verify turret is targeting enemy (90 degrees)
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     assert_eq!(turret_rotation.target_angle, 90.0, "Turret should target enemy at 90 degrees");
     
     // Now remove the attack target
@@ -140,7 +137,7 @@ fn test_turret_maintains_angle_when_target_removed() {
     }
     
     // Turret should maintain its angle, not track mouse
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     let turret_transform = app.world.get::<Transform>(turret_entity).unwrap();
     
     // The turret was moving towards 90 degrees, so it should be at or near that angle
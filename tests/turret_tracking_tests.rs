@@ -1,9 +1,17 @@
 use bevy::prelude::*;
 use rust_and_ruin::mech::*;
 use rust_and_ruin::components::*;
-use rust_and_ruin::systems::turret_control::*;
+use rust_and_ruin::systems::angles::*;
 use rust_and_ruin::systems::movement::*;
 use rust_and_ruin::resources::*;
+use rust_and_ruin::systems::upper_body_control::*;
+
+/// Full-traverse turret upper body turning at `rotation_speed` degrees per second.
+fn turret_upper(rotation_speed: f32) -> MechUpperBody {
+    let mut upper = create_turret_upper();
+    upper.rotation_capability.rotation_speed = rotation_speed;
+    upper
+}
 
 /// Test that turret rotates towards target when initially facing away
 #[test]
@@ -15,7 +23,7 @@ fn test_turret_rotates_towards_target_when_facing_away() {
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create mech at origin facing forward (+Z)
@@ -28,16 +36,11 @@ fn test_turret_rotates_towards_target_when_facing_away() {
     let turret_entity = app.world.spawn((
         Transform::from_rotation(Quat::from_rotation_y(180.0_f32.to_radians())),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 180.0,
             target_angle: 180.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 90.0, // 90 degrees per second
-            barrel_length: 2.0,
-        },
+        turret_upper(90.0), // 90 degrees per second
         MechUpperPart,
     )).id();
     
@@ -59,7 +62,7 @@ fn test_turret_rotates_towards_target_when_facing_away() {
     }
     
     // Check that turret is rotating towards target
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     
     // Target should be at 0 degrees (forward)
     assert_eq!(turret_rotation.target_angle, 0.0, "Turret should target forward (0 degrees)");
@@ -80,7 +83,7 @@ fn test_turret_maintains_lock_during_chassis_movement() {
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
         movement_system,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create mech at origin
@@ -94,16 +97,11 @@ fn test_turret_maintains_lock_during_chassis_movement() {
     let turret_entity = app.world.spawn((
         Transform::from_rotation(Quat::from_rotation_y(90.0_f32.to_radians())),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 90.0,
             target_angle: 90.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 360.0, // Fast rotation for testing
-            barrel_length: 2.0,
-        },
+        turret_upper(360.0), // Fast rotation for testing
         MechUpperPart,
     )).id();
     
@@ -135,7 +133,7 @@ fn test_turret_maintains_lock_during_chassis_movement() {
     // Get current positions
     let mech_transform = app.world.get::<Transform>(mech_entity).unwrap();
     let enemy_transform = app.world.get::<Transform>(enemy_entity).unwrap();
-    let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+    let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
     
     // Calculate expected angle from new mech position to enemy
     let mech_pos = Vec2::new(mech_transform.translation.x, mech_transform.translation.z);
@@ -161,7 +159,7 @@ fn test_turret_compensates_for_chassis_rotation() {
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create enemy at fixed position (10, 0, 0)
@@ -197,16 +195,11 @@ fn test_turret_compensates_for_chassis_rotation() {
         let turret_entity = app.world.spawn((
             Transform::from_rotation(Quat::from_rotation_y(expected_local_angle.to_radians())),
             GlobalTransform::default(),
-            TurretRotation {
+            MechRotation {
                 current_angle: expected_local_angle,
                 target_angle: world_angle_to_enemy,
             },
-            TurretCannon {
-                fire_rate: 1.0,
-                projectile_damage: 10.0,
-                rotation_speed: 360.0,
-                barrel_length: 2.0,
-            },
+            turret_upper(360.0),
             MechUpperPart,
         )).id();
         
@@ -217,8 +210,8 @@ fn test_turret_compensates_for_chassis_rotation() {
         app.update();
         
         // Debug: Check turret rotation values
-        let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
-        println!("{}: TurretRotation - current: {}, target: {}", 
+        let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
+        println!("{}: MechRotation - current: {}, target: {}", 
                  description, turret_rotation.current_angle, turret_rotation.target_angle);
         
         // Get global transform of turret
@@ -259,7 +252,7 @@ fn test_turret_tracks_moving_target() {
     
     app.add_systems(Update, (
         bevy::transform::systems::propagate_transforms,
-        turret_control_system,
+        upper_body_control_system,
     ).chain());
     
     // Create stationary mech
@@ -272,16 +265,11 @@ fn test_turret_tracks_moving_target() {
     let turret_entity = app.world.spawn((
         Transform::default(),
         GlobalTransform::default(),
-        TurretRotation {
+        MechRotation {
             current_angle: 0.0,
             target_angle: 0.0,
         },
-        TurretCannon {
-            fire_rate: 1.0,
-            projectile_damage: 10.0,
-            rotation_speed: 180.0, // 180 degrees per second
-            barrel_length: 2.0,
-        },
+        turret_upper(180.0), // 180 degrees per second
         MechUpperPart,
     )).id();
     
@@ -313,7 +301,7 @@ fn test_turret_tracks_moving_target() {
             app.update();
         }
         
-        let turret_rotation = app.world.get::<TurretRotation>(turret_entity).unwrap();
+        let turret_rotation = app.world.get::<MechRotation>(turret_entity).unwrap();
         let expected_angle = calculate_turret_angle(Vec2::ZERO, Vec2::new(enemy_pos.x, enemy_pos.z));
         let expected_angle_normalized = normalize_angle(expected_angle);
         
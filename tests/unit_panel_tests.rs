@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::systems::weapon_cooldown_system;
+use rust_and_ruin::ui::*;
+
+fn create_panel_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HierarchyPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    app.add_plugins(UnitInfoPanelPlugin);
+    app
+}
+
+fn panel_text(app: &mut App) -> String {
+    let mut query = app.world.query_filtered::<&Text, With<UnitInfoText>>();
+    query.single(&app.world).sections[0].value.clone()
+}
+
+fn panel_display(app: &mut App) -> Display {
+    let mut query = app.world.query_filtered::<&Style, With<UnitInfoPanel>>();
+    query.single(&app.world).display
+}
+
+#[test]
+fn test_cooldown_progress_and_bar() {
+    assert_eq!(cooldown_progress(0.0, 1.5), 0.0);
+    assert_eq!(cooldown_progress(0.75, 1.5), 0.5);
+    assert_eq!(cooldown_progress(10.0, 1.5), 1.0);
+    assert_eq!(format_progress_bar(0.5), "[#####-----]");
+    assert_eq!(format_progress_bar(1.0), "[##########]");
+}
+
+#[test]
+fn test_panel_hidden_without_selection() {
+    let mut app = create_panel_app();
+    app.world.spawn((Hero, MechMovement::default()));
+    app.update();
+
+    assert_eq!(panel_display(&mut app), Display::None);
+}
+
+#[test]
+fn test_panel_shows_driven_mech() {
+    let mut app = create_panel_app();
+    app.add_systems(Update, weapon_cooldown_system);
+    let hero = app.world.spawn((
+        Hero,
+        Selected,
+        Health::new(80.0),
+        MechMovement::default(),
+        create_tank_treads_lower(),
+    )).id();
+    let upper = app.world.spawn(create_turret_upper()).id();
+    let weapon = app.world.spawn(create_cannon_weapon("main".to_string())).id();
+    app.world.entity_mut(hero).push_children(&[upper]);
+    app.world.entity_mut(upper).push_children(&[weapon]);
+    app.update();
+
+    assert_eq!(panel_display(&mut app), Display::Flex);
+    let text = panel_text(&mut app);
+    assert!(text.contains("Health: 80/80"), "{}", text);
+    assert!(text.contains("Lower: Treads (speed 5.0"), "{}", text);
+    assert!(text.contains("Cannon [main]: dmg 25, range 15"), "{}", text);
+    assert!(text.contains("RELOADING"), "{}", text);
+
+    // Cooldown finishes after the fire rate has elapsed
+    for _ in 0..20 {
+        app.update();
+    }
+    assert!(panel_text(&mut app).contains("READY"));
+}
+
+#[test]
+fn test_panel_lists_hierarchy_weapons_in_hardpoint_order() {
+    let mut app = create_panel_app();
+
+    let lower = app.world.spawn(create_tank_treads_lower()).id();
+    let upper = app.world.spawn(create_dual_turret_upper()).id();
+    let right = app.world.spawn(MechWeapon {
+        weapon_stats: CannonWeapon::heavy().weapon_stats,
+        hardpoint_id: "right".to_string(),
+        last_fire_time: 1.0,
+    }).id();
+    let left = app.world.spawn(MechWeapon {
+        weapon_stats: CannonWeapon::light().weapon_stats,
+        hardpoint_id: "left".to_string(),
+        last_fire_time: 0.0,
+    }).id();
+
+    let mut hierarchy = MechHierarchy::new();
+    hierarchy.lower = Some(lower);
+    hierarchy.upper = Some(upper);
+    hierarchy.weapons = vec![right, left];
+    app.world.spawn((Mech::new("Strider"), Selected, hierarchy));
+    app.update();
+
+    let text = panel_text(&mut app);
+    assert!(text.starts_with("Strider\n"), "{}", text);
+    assert!(text.contains("Upper: Turret (2 hardpoints"), "{}", text);
+
+    let left_index = text.find("Cannon [left]: dmg 15, range 12").expect(&text);
+    let right_index = text.find("Cannon [right]: dmg 40, range 20").expect(&text);
+    assert!(left_index < right_index, "Weapons should follow hardpoint order:\n{}", text);
+    assert!(text.contains("[#####-----] RELOADING"), "Heavy cannon is halfway through its 2s cooldown:\n{}", text);
+}
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use rust_and_ruin::camera::CameraShakeEvent;
+use rust_and_ruin::components::*;
+use rust_and_ruin::mech::*;
+use rust_and_ruin::systems::movement::attack_move_system;
+use rust_and_ruin::systems::weapon_control::weapon_control_system;
+use rust_and_ruin::systems::projectile_pool::{ProjectileAssets, ProjectilePool};
+use rust_and_ruin::systems::weapon_range::*;
+
+#[test]
+fn test_engagement_range_uses_shortest_weapon() {
+    assert_eq!(engagement_range(&[]), None);
+    assert_eq!(engagement_range(&[20.0, 12.0, 15.0]), Some(12.0));
+}
+
+#[test]
+fn test_firing_arc_spans_tolerance_either_side_of_facing() {
+    let points = firing_arc_points(Vec3::ZERO, 90.0, 5.0, 10.0, 4);
+
+    assert_eq!(points.len(), 4 + 3);
+    assert_eq!(points[0], Vec3::ZERO);
+    assert_eq!(*points.last().unwrap(), Vec3::ZERO);
+
+    for point in &points[1..points.len() - 1] {
+        assert!((point.length() - 10.0).abs() < 0.001);
+        let angle = point.x.atan2(point.z).to_degrees();
+        assert!((85.0 - 0.001..=95.0 + 0.001).contains(&angle), "angle {}", angle);
+    }
+    let middle = points[3];
+    assert!((middle - Vec3::new(10.0, 0.0, 0.0)).length() < 0.001);
+}
+
+fn spawn_hero_with_weapon(app: &mut App, range: f32) -> (Entity, Entity) {
+    let hero = app.world.spawn((Hero, TransformBundle::default())).id();
+    let upper = app.world.spawn((
+        MechUpperPart,
+        create_turret_upper(),
+        MechRotation { target_angle: 0.0, current_angle: 0.0 },
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 0.75, 0.0)),
+    )).id();
+    let mut weapon = create_cannon_weapon("main".to_string());
+    weapon.weapon_stats.range = range;
+    let weapon = app.world.spawn((weapon, CannonWeapon::default(), TransformBundle::default())).id();
+    app.world.entity_mut(hero).push_children(&[upper]);
+    app.world.entity_mut(upper).push_children(&[weapon]);
+    (hero, weapon)
+}
+
+#[test]
+fn test_attack_move_closes_to_weapon_range() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HierarchyPlugin));
+    app.add_systems(Update, attack_move_system);
+
+    let (hero, _) = spawn_hero_with_weapon(&mut app, 6.0);
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 20.0)))).id();
+    app.world.entity_mut(hero).insert(AttackTarget { entity: enemy });
+    app.update();
+
+    let target = app.world.get::<MoveTarget>(hero).map(|target| target.position).unwrap();
+    assert!((target - Vec2::new(0.0, 14.5)).length() < 0.001, "Should stop just inside the 6 unit range, got {:?}", target);
+}
+
+#[test]
+fn test_attack_move_ignores_units_without_weapons() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HierarchyPlugin));
+    app.add_systems(Update, attack_move_system);
+
+    let hero = app.world.spawn((Hero, TransformBundle::default())).id();
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 20.0)))).id();
+    app.world.entity_mut(hero).insert(AttackTarget { entity: enemy });
+    app.update();
+
+    assert!(app.world.get::<MoveTarget>(hero).is_none());
+}
+
+#[test]
+fn test_weapons_only_fire_inside_their_own_range() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, HierarchyPlugin));
+    app.insert_resource(Assets::<Mesh>::default());
+    app.insert_resource(Assets::<StandardMaterial>::default());
+    app.init_resource::<ProjectileAssets>();
+    app.init_resource::<ProjectilePool>();
+    app.add_event::<CameraShakeEvent>();
+    app.add_systems(Update, weapon_control_system);
+
+    let (hero, weapon) = spawn_hero_with_weapon(&mut app, 5.0);
+    app.world.get_mut::<MechWeapon>(weapon).unwrap().last_fire_time = 10.0;
+    let enemy = app.world.spawn((Enemy, TransformBundle::from_transform(Transform::from_xyz(0.0, 0.75, 8.0)))).id();
+    app.world.entity_mut(hero).insert(AttackTarget { entity: enemy });
+    app.update();
+    app.update();
+
+    let shells = app.world.query::<&TankShell>().iter(&app.world).count();
+    assert_eq!(shells, 0, "Enemy at 8 units is outside a 5 unit range");
+
+    app.world.get_mut::<MechWeapon>(weapon).unwrap().weapon_stats.range = 12.0;
+    app.update();
+
+    let shells = app.world.query::<&TankShell>().iter(&app.world).count();
+    assert_eq!(shells, 1);
+}